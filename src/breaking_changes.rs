@@ -0,0 +1,225 @@
+use crate::godot::GodotVersion;
+
+/// A GDScript change that breaks projects moving to a newer Godot version
+#[derive(Debug)]
+pub struct BreakingChange {
+    /// The first version ("major.minor") with the change
+    pub introduced_in: (u64, u64),
+    pub description: &'static str,
+    /// POSIX extended regex matching the old code
+    pub pattern: &'static str,
+    /// What `pattern` is rewritten to (with `\1`-style groups), or `None` if it
+    /// can't be rewritten textually
+    pub replacement: Option<&'static str>,
+    /// Whether a rewritten line still needs a human to check it
+    pub needs_review: bool,
+}
+
+/// GDScript changes between Godot releases, oldest first. Patterns, replacements and
+/// descriptions can't contain '/', which delimits them in the generated sed script,
+/// and descriptions can't contain '&', which sed would expand.
+pub const BREAKING_CHANGES: &[BreakingChange] = &[
+    BreakingChange {
+        introduced_in: (4, 0),
+        description: "tool is now the @tool annotation",
+        pattern: r"^tool[[:space:]]*$",
+        replacement: Some("@tool"),
+        needs_review: false,
+    },
+    BreakingChange {
+        introduced_in: (4, 0),
+        description: "onready is now the @onready annotation",
+        pattern: r"^([[:space:]]*)onready var ",
+        replacement: Some(r"\1@onready var "),
+        needs_review: false,
+    },
+    BreakingChange {
+        introduced_in: (4, 0),
+        description: "export is now the @export annotation",
+        pattern: r"^([[:space:]]*)export var ",
+        replacement: Some(r"\1@export var "),
+        needs_review: false,
+    },
+    BreakingChange {
+        introduced_in: (4, 0),
+        description: "export(hint) is now @export_* annotations such as @export_range",
+        pattern: r"^([[:space:]]*)export\(",
+        replacement: None,
+        needs_review: true,
+    },
+    BreakingChange {
+        introduced_in: (4, 0),
+        description: "yield(object, signal) is now await object.signal",
+        pattern: r#"yield\(([^,]+), *"([A-Za-z_][A-Za-z0-9_]*)"\)"#,
+        replacement: Some(r"await \1.\2"),
+        needs_review: true,
+    },
+    BreakingChange {
+        introduced_in: (4, 0),
+        description: "setget is now a property with set and get functions",
+        pattern: r"[[:space:]]setget[[:space:]]",
+        replacement: None,
+        needs_review: true,
+    },
+    BreakingChange {
+        introduced_in: (4, 0),
+        description: "connect(signal, target, method) now takes a Callable",
+        pattern: r#"connect\("[A-Za-z_][A-Za-z0-9_]*", *[^,()]+, *""#,
+        replacement: None,
+        needs_review: true,
+    },
+    BreakingChange {
+        introduced_in: (4, 0),
+        description: "PackedScene.instance() is now instantiate()",
+        pattern: r"\.instance\(\)",
+        replacement: Some(".instantiate()"),
+        needs_review: false,
+    },
+    BreakingChange {
+        introduced_in: (4, 0),
+        description: "empty() is now is_empty()",
+        pattern: r"\.empty\(\)",
+        replacement: Some(".is_empty()"),
+        needs_review: false,
+    },
+    BreakingChange {
+        introduced_in: (4, 0),
+        description: "deg2rad() is now deg_to_rad()",
+        pattern: r"\bdeg2rad\(",
+        replacement: Some("deg_to_rad("),
+        needs_review: false,
+    },
+    BreakingChange {
+        introduced_in: (4, 0),
+        description: "rad2deg() is now rad_to_deg()",
+        pattern: r"\brad2deg\(",
+        replacement: Some("rad_to_deg("),
+        needs_review: false,
+    },
+    BreakingChange {
+        introduced_in: (4, 0),
+        description: "KinematicBody2D is now CharacterBody2D",
+        pattern: r"\bKinematicBody2D\b",
+        replacement: Some("CharacterBody2D"),
+        needs_review: false,
+    },
+    BreakingChange {
+        introduced_in: (4, 0),
+        description: "KinematicBody is now CharacterBody3D",
+        pattern: r"\bKinematicBody\b",
+        replacement: Some("CharacterBody3D"),
+        needs_review: false,
+    },
+    BreakingChange {
+        introduced_in: (4, 0),
+        description: "Spatial is now Node3D",
+        pattern: r"\bSpatial\b",
+        replacement: Some("Node3D"),
+        needs_review: false,
+    },
+    BreakingChange {
+        introduced_in: (4, 0),
+        description: "master, puppet and remote are now the @rpc annotation",
+        pattern: r"^([[:space:]]*)(master|puppet|remote|remotesync) func ",
+        replacement: None,
+        needs_review: true,
+    },
+];
+
+/// The changes a project meets moving from `from` to `to`: those introduced after
+/// `from`'s minor release, up to and including `to`'s
+pub fn known_breaking_changes_from(
+    from: &GodotVersion,
+    to: &GodotVersion,
+) -> Vec<&'static BreakingChange> {
+    let minor = |v: &GodotVersion| (v.version.major, v.version.minor);
+    BREAKING_CHANGES
+        .iter()
+        .filter(|change| change.introduced_in > minor(from) && change.introduced_in <= minor(to))
+        .collect()
+}
+
+/// Write a GNU sed script (for `sed -E -i -f`) that applies `changes`. Lines it can't
+/// rewrite, or whose rewrite needs checking, get a `# TODO: verify` comment appended,
+/// once: running the script again leaves lines that have one alone. The patterns use
+/// GNU extensions such as `\b`, and `-i` without a suffix is GNU syntax too, so BSD and
+/// macOS sed can't run it.
+pub fn sed_script(from: &GodotVersion, to: &GodotVersion, changes: &[&BreakingChange]) -> String {
+    let mut script = format!(
+        "# Migrates GDScript from Godot {} to {}, generated by gdenv\n\
+         # Run it on a clean working tree, then review the diff and every TODO:\n\
+         #   find . -name '*.gd' -exec sed -E -i -f <this file> {{}} +\n\
+         # It needs GNU sed; on macOS, install gnu-sed and run gsed instead.\n",
+        from.godot_version_string(),
+        to.godot_version_string()
+    );
+
+    for change in changes {
+        let todo = format!(
+            r"/# TODO: verify/!s/$/  # TODO: verify ({})/",
+            change.description
+        );
+        script.push_str(&format!("\n# {}\n", change.description));
+        script.push_str(&match (change.replacement, change.needs_review) {
+            (Some(replacement), false) => format!("s/{}/{}/g\n", change.pattern, replacement),
+            (Some(replacement), true) => format!(
+                "/{pattern}/{{\n  s/{pattern}/{replacement}/g\n  {todo}\n}}\n",
+                pattern = change.pattern,
+                replacement = replacement,
+                todo = todo
+            ),
+            (None, _) => format!("/{}/{{\n  {}\n}}\n", change.pattern, todo),
+        });
+    }
+
+    script
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn v(version: &str) -> GodotVersion {
+        GodotVersion::new(version, false).unwrap()
+    }
+
+    #[test]
+    fn test_known_breaking_changes_from() {
+        assert_eq!(
+            known_breaking_changes_from(&v("3.5.3"), &v("4.2")).len(),
+            BREAKING_CHANGES.len()
+        );
+        assert!(known_breaking_changes_from(&v("4.0"), &v("4.3")).is_empty());
+        assert!(known_breaking_changes_from(&v("3.4"), &v("3.6")).is_empty());
+    }
+
+    #[test]
+    fn test_sed_script() {
+        for change in BREAKING_CHANGES {
+            let parts = [change.pattern, change.description];
+            assert!(
+                parts
+                    .iter()
+                    .chain(&change.replacement)
+                    .all(|part| !part.contains('/'))
+                    && !change.description.contains('&'),
+                "{}",
+                change.description
+            );
+            assert!(change.replacement.is_some() || change.needs_review);
+        }
+
+        let changes = known_breaking_changes_from(&v("3.5"), &v("4.2"));
+        let script = sed_script(&v("3.5"), &v("4.2"), &changes);
+        assert!(script.starts_with("# Migrates GDScript from Godot 3.5.0 to 4.2.0"));
+        assert!(script.contains("s/\\.instance\\(\\)/.instantiate()/g\n"));
+        assert!(script.contains("It needs GNU sed"));
+        assert!(script.contains(
+            "  s/yield\\(([^,]+), *\"([A-Za-z_][A-Za-z0-9_]*)\"\\)/await \\1.\\2/g\n  \
+             /# TODO: verify/!s/$/  # TODO: verify (yield(object, signal) is now await object.signal)/\n}"
+        ));
+        assert!(script.contains(
+            "/[[:space:]]setget[[:space:]]/{\n  /# TODO: verify/!s/$/  # TODO: verify (setget is now"
+        ));
+    }
+}
@@ -0,0 +1,298 @@
+use anyhow::Result;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::PathBuf;
+
+use crate::{config::Config, github::GitHubRelease, godot::GodotVersion};
+
+/// A released Godot version, when it was published and the archives it offers
+#[derive(Debug, Clone)]
+pub struct CatalogEntry {
+    pub version: GodotVersion,
+    pub published_at: DateTime<Utc>,
+    /// File names of the release's assets
+    pub archives: Vec<String>,
+}
+
+/// The known Godot releases, sorted from oldest to newest version
+#[derive(Debug, Clone, Default)]
+pub struct VersionCatalog {
+    entries: Vec<CatalogEntry>,
+}
+
+/// High-level insights about the release history
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct CatalogStatistics {
+    pub total_versions: usize,
+    pub versions_by_major: BTreeMap<u64, usize>,
+    pub stable_versions: usize,
+    pub prerelease_versions: usize,
+    pub average_days_between_stable: Option<f64>,
+    pub oldest_version: Option<String>,
+    pub newest_version: Option<String>,
+}
+
+impl VersionCatalog {
+    pub fn new(mut entries: Vec<CatalogEntry>) -> Self {
+        entries.sort_by(|a, b| a.version.cmp(&b.version));
+        entries.dedup_by(|a, b| a.version == b.version);
+        Self { entries }
+    }
+
+    /// Build a catalog from GitHub releases, skipping tags that don't parse
+    pub fn from_releases(releases: &[GitHubRelease]) -> Self {
+        Self::new(
+            releases
+                .iter()
+                .filter_map(|release| {
+                    Some(CatalogEntry {
+                        version: GodotVersion::from_tag(&release.tag_name).ok()?,
+                        published_at: release.published_at,
+                        archives: release.assets.iter().map(|a| a.name.clone()).collect(),
+                    })
+                })
+                .collect(),
+        )
+    }
+
+    /// Get the versions with a standard build for `platform_suffix`, oldest first
+    pub fn filter_by_platform(&self, platform_suffix: &str) -> Vec<&CatalogEntry> {
+        self.entries
+            .iter()
+            .filter(|entry| {
+                let archive = entry.version.archive_name_for_platform(platform_suffix);
+                entry.archives.contains(&archive)
+            })
+            .collect()
+    }
+
+    pub fn statistics(&self) -> CatalogStatistics {
+        let mut versions_by_major = BTreeMap::new();
+        for entry in &self.entries {
+            *versions_by_major
+                .entry(entry.version.version.major)
+                .or_insert(0) += 1;
+        }
+
+        let mut stable_dates: Vec<DateTime<Utc>> = self
+            .entries
+            .iter()
+            .filter(|entry| !entry.version.is_prerelease())
+            .map(|entry| entry.published_at)
+            .collect();
+        stable_dates.sort();
+
+        let average_days_between_stable = if stable_dates.len() > 1 {
+            let span = *stable_dates.last().unwrap() - stable_dates[0];
+            Some(span.num_seconds() as f64 / 86_400.0 / (stable_dates.len() - 1) as f64)
+        } else {
+            None
+        };
+
+        let stable_versions = stable_dates.len();
+
+        CatalogStatistics {
+            total_versions: self.entries.len(),
+            versions_by_major,
+            stable_versions,
+            prerelease_versions: self.entries.len() - stable_versions,
+            average_days_between_stable,
+            oldest_version: self.entries.first().map(|e| e.version.to_string()),
+            newest_version: self.entries.last().map(|e| e.version.to_string()),
+        }
+    }
+}
+
+/// File in the gdenv root holding the user's additions to the catalog, like pins
+pub const CATALOG_FILE: &str = "catalog.json";
+
+/// What the user recorded about one version in [`CATALOG_FILE`]
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CatalogFileEntry {
+    /// Cleanups never remove the version (with `pruning.keep_pinned`)
+    pub pinned: bool,
+}
+
+/// How cleanups like `uninstall --unused` treat the catalog
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct PruningSettings {
+    pub keep_pinned: bool,
+}
+
+impl Default for PruningSettings {
+    fn default() -> Self {
+        Self { keep_pinned: true }
+    }
+}
+
+/// The contents of [`CATALOG_FILE`]. Entries are keyed by version, e.g. "3.5.3", and
+/// cover both the standard and the .NET build.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct CatalogFile {
+    pub versions: BTreeMap<String, CatalogFileEntry>,
+    pub pruning: PruningSettings,
+}
+
+impl CatalogFile {
+    pub fn path(config: &Config) -> PathBuf {
+        config.root_dir.join(CATALOG_FILE)
+    }
+
+    /// Load the catalog file, or an empty one if there is none yet
+    pub fn load(config: &Config) -> Result<Self> {
+        let path = Self::path(config);
+        if !path.exists() {
+            return Ok(Self::default());
+        }
+        Ok(serde_json::from_str(&fs::read_to_string(path)?)?)
+    }
+
+    pub fn save(&self, config: &Config) -> Result<()> {
+        fs::create_dir_all(&config.root_dir)?;
+        fs::write(Self::path(config), serde_json::to_string_pretty(self)?)?;
+        Ok(())
+    }
+
+    /// Pin a version, returning whether it wasn't pinned before
+    pub fn pin(&mut self, version: &GodotVersion) -> bool {
+        let entry = self
+            .versions
+            .entry(version.godot_version_string())
+            .or_default();
+        !std::mem::replace(&mut entry.pinned, true)
+    }
+
+    /// Unpin a version, returning whether it was pinned
+    pub fn unpin(&mut self, version: &GodotVersion) -> bool {
+        let key = version.godot_version_string();
+        let was_pinned = self.versions.get(&key).is_some_and(|e| e.pinned);
+        if let Some(entry) = self.versions.get_mut(&key) {
+            entry.pinned = false;
+        }
+        // Drop entries that no longer record anything
+        self.versions
+            .retain(|_, entry| *entry != CatalogFileEntry::default());
+        was_pinned
+    }
+
+    pub fn is_pinned(&self, version: &GodotVersion) -> bool {
+        self.versions
+            .get(&version.godot_version_string())
+            .is_some_and(|entry| entry.pinned)
+    }
+
+    /// The pinned versions, oldest first
+    pub fn pinned(&self) -> Vec<GodotVersion> {
+        let mut pinned: Vec<GodotVersion> = self
+            .versions
+            .iter()
+            .filter(|(_, entry)| entry.pinned)
+            .filter_map(|(version, _)| GodotVersion::new(version, false).ok())
+            .collect();
+        pinned.sort();
+        pinned
+    }
+
+    /// Whether cleanups must keep `version`
+    pub fn retains(&self, version: &GodotVersion) -> bool {
+        self.pruning.keep_pinned && self.is_pinned(version)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    fn entry(version: &str, day: u32) -> CatalogEntry {
+        CatalogEntry {
+            version: GodotVersion::new(version, false).unwrap(),
+            published_at: Utc.with_ymd_and_hms(2024, 1, day, 0, 0, 0).unwrap(),
+            archives: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn test_statistics() {
+        let catalog = VersionCatalog::new(vec![
+            entry("4.2.1", 11),
+            entry("3.5.3", 1),
+            entry("4.3.0-beta2", 15),
+            entry("4.2.0", 5),
+        ]);
+
+        let stats = catalog.statistics();
+        assert_eq!(stats.total_versions, 4);
+        assert_eq!(stats.versions_by_major, BTreeMap::from([(3, 1), (4, 3)]));
+        assert_eq!(stats.stable_versions, 3);
+        assert_eq!(stats.prerelease_versions, 1);
+        assert_eq!(stats.average_days_between_stable, Some(5.0));
+        assert_eq!(stats.oldest_version.as_deref(), Some("3.5.3"));
+        assert_eq!(stats.newest_version.as_deref(), Some("4.3.0-beta2"));
+    }
+
+    #[test]
+    fn test_filter_by_platform() {
+        let with_archives = |version: &str, archives: &[&str]| CatalogEntry {
+            archives: archives.iter().map(|a| a.to_string()).collect(),
+            ..entry(version, 1)
+        };
+        let catalog = VersionCatalog::new(vec![
+            with_archives(
+                "4.2.1",
+                &[
+                    "Godot_v4.2.1-stable_linux.x86_64.zip",
+                    "Godot_v4.2.1-stable_win64.exe.zip",
+                ],
+            ),
+            with_archives("4.3", &["Godot_v4.3-stable_win64.exe.zip"]),
+            with_archives("3.5.3", &["Godot_v3.5.3-stable_x11.64.zip"]),
+        ]);
+
+        let versions = |platform: &str| -> Vec<String> {
+            catalog
+                .filter_by_platform(platform)
+                .iter()
+                .map(|e| e.version.to_string())
+                .collect()
+        };
+        assert_eq!(versions("linux.x86_64"), ["4.2.1"]);
+        assert_eq!(versions("win64.exe"), ["4.2.1", "4.3.0"]);
+        assert_eq!(versions("x11.64"), ["3.5.3"]);
+        assert!(versions("macos.universal").is_empty());
+    }
+
+    #[test]
+    fn test_catalog_file_pins() {
+        let root = tempfile::tempdir().unwrap();
+        let config = Config::with_data_dir(root.path());
+        let v = |version: &str, is_dotnet: bool| GodotVersion::new(version, is_dotnet).unwrap();
+
+        let mut catalog = CatalogFile::load(&config).unwrap();
+        assert!(catalog.pin(&v("3.5.3", false)));
+        assert!(!catalog.pin(&v("3.5.3", true)));
+        assert!(catalog.pin(&v("4.2.2", false)));
+        catalog.save(&config).unwrap();
+
+        let mut catalog = CatalogFile::load(&config).unwrap();
+        assert_eq!(catalog.pinned(), [v("3.5.3", false), v("4.2.2", false)]);
+        assert!(catalog.retains(&v("3.5.3", true)));
+        assert!(!catalog.retains(&v("4.3", false)));
+
+        assert!(catalog.unpin(&v("4.2.2", false)));
+        assert!(!catalog.unpin(&v("4.2.2", false)));
+        assert_eq!(catalog.versions.len(), 1);
+
+        // Pins only protect versions while pruning.keep_pinned is on
+        let catalog: CatalogFile = serde_json::from_str(
+            r#"{"versions": {"3.5.3": {"pinned": true}}, "pruning": {"keep_pinned": false}}"#,
+        )
+        .unwrap();
+        assert!(catalog.is_pinned(&v("3.5.3", false)));
+        assert!(!catalog.retains(&v("3.5.3", false)));
+    }
+}
@@ -2,9 +2,19 @@ use anyhow::Result;
 use clap::{Parser, Subcommand};
 
 use crate::commands::{
-    cache::CacheCommand, current::CurrentCommand, install::InstallCommand,
-    installed::InstalledCommand, list::ListCommand, uninstall::UninstallCommand,
-    update::UpdateCommand, use_cmd::UseCommand,
+    adopt::AdoptCommand, available::AvailableCommand, build_id::BuildIdCommand,
+    cache::CacheCommand, catalog::CatalogCommand, checksum::ChecksumCommand,
+    complete::CompleteCommand, completions::CompletionsCommand, config::ConfigCommand,
+    current::CurrentCommand, doctor::DoctorCommand, dotnet_sdk_version::DotnetSdkVersionCommand,
+    env::EnvCommand, hook::HookCommand, import::ImportCommand,
+    import_from_steam::ImportFromSteamCommand, info::InfoCommand,
+    inspect_project::InspectProjectCommand, install::InstallCommand, installed::InstalledCommand,
+    lint_lockfile::LintLockfileCommand, list::ListCommand, lock::LockCommand,
+    migrate_installs::MigrateInstallsCommand, project::ProjectCommand, run::RunCommand,
+    source::SourceCommand, status::StatusCommand, sync::SyncCommand, uninstall::UninstallCommand,
+    update::UpdateCommand, upgrade_tooling::UpgradeToolingCommand, url::UrlCommand,
+    use_cmd::UseCommand, validate_pin::ValidatePinCommand, verify::VerifyCommand,
+    watch::WatchCommand, which::WhichCommand,
 };
 
 #[derive(Parser)]
@@ -21,9 +31,16 @@ pub enum Commands {
     /// Download and install a specific version of Godot
     Install(InstallCommand),
 
+    /// Install several Godot versions, summarizing any failures at the end
+    Sync(SyncCommand),
+
     /// List available Godot versions from remote
+    #[command(alias = "ls-remote")]
     List(ListCommand),
 
+    /// List every Godot release on GitHub, newest first
+    Available(AvailableCommand),
+
     /// List installed Godot versions
     Installed(InstalledCommand),
 
@@ -33,27 +50,142 @@ pub enum Commands {
     /// Uninstall a specific Godot version
     Uninstall(UninstallCommand),
 
+    /// Register an existing Godot binary (from a path, $GODOT_BIN or PATH) with gdenv
+    Adopt(AdoptCommand),
+
+    /// Register the Godot versions installed through Steam
+    ImportFromSteam(ImportFromSteamCommand),
+
+    /// Print the path of a Godot executable
+    Which(WhichCommand),
+
+    /// Print the download URL of a Godot version's archive, or of every platform's
+    Url(UrlCommand),
+
     /// Show the currently active Godot version
     Current(CurrentCommand),
 
+    /// Show the name, release tag and install location of a Godot version
+    Info(InfoCommand),
+
+    /// Show the Godot version pinned for the current directory
+    Status(StatusCommand),
+
+    /// Check your setup for common problems
+    Doctor(DoctorCommand),
+
+    /// Check that .godot-version pins a released (and optionally installed) Godot version
+    ValidatePin(ValidatePinCommand),
+
+    /// Check that installed Godot executables aren't truncated or corrupt
+    Verify(VerifyCommand),
+
+    /// Integrate gdenv with your shell
+    Env(EnvCommand),
+
     /// Update the cache of available Godot versions
     Update(UpdateCommand),
 
+    /// Update gdenv and move every installed Godot to its latest patch release
+    UpgradeTooling(UpgradeToolingCommand),
+
+    /// Move all installations and data to a new gdenv root
+    MigrateInstalls(MigrateInstallsCommand),
+
     /// Manage download cache
     Cache(CacheCommand),
+
+    /// Inspect the catalog of released Godot versions
+    Catalog(CatalogCommand),
+
+    /// Print a stable ID for a Godot version, platform and build flavor (for CI cache keys)
+    BuildId(BuildIdCommand),
+
+    /// Print the Godot.NET.Sdk NuGet package version matching a Godot version
+    DotnetSdkVersion(DotnetSdkVersionCommand),
+
+    /// Print the SHA-256 of an installed Godot binary, or one digest for all of them
+    Checksum(ChecksumCommand),
+
+    /// Run the active Godot version
+    Run(RunCommand),
+
+    /// Download the source code of a Godot release (e.g. to build a custom engine from it)
+    Source(SourceCommand),
+
+    /// Import a project's assets with its Godot version (e.g. to warm `.godot/imported` in CI)
+    Import(ImportCommand),
+
+    /// Show the Godot metadata of a project directory
+    InspectProject(InspectProjectCommand),
+
+    /// Work with a Godot project (e.g. export it in CI)
+    Project(ProjectCommand),
+
+    /// Read and change gdenv settings
+    Config(ConfigCommand),
+
+    /// Keep .godot-version in sync with project.godot
+    Lock(LockCommand),
+
+    /// Warn about common mistakes in a project's .godot-version and gdenv.toml
+    LintLockfile(LintLockfileCommand),
+
+    /// Manage global hooks that run for every install/uninstall
+    Hook(HookCommand),
+
+    /// Watch a project and reimport its assets when files change
+    Watch(WatchCommand),
+
+    /// Generate shell completion scripts
+    Completions(CompletionsCommand),
+
+    /// Print completion candidates (used by shell completion scripts)
+    #[command(name = "__complete", hide = true)]
+    Complete(CompleteCommand),
 }
 
 impl Cli {
     pub async fn run(self) -> Result<()> {
         match self.command {
             Commands::Install(cmd) => cmd.run().await,
+            Commands::Sync(cmd) => cmd.run().await,
             Commands::List(cmd) => cmd.run().await,
+            Commands::Available(cmd) => cmd.run().await,
             Commands::Installed(cmd) => cmd.run().await,
             Commands::Use(cmd) => cmd.run().await,
             Commands::Uninstall(cmd) => cmd.run().await,
+            Commands::Adopt(cmd) => cmd.run().await,
+            Commands::ImportFromSteam(cmd) => cmd.run().await,
+            Commands::Which(cmd) => cmd.run().await,
             Commands::Current(cmd) => cmd.run().await,
+            Commands::Url(cmd) => cmd.run().await,
+            Commands::Info(cmd) => cmd.run().await,
+            Commands::Status(cmd) => cmd.run().await,
+            Commands::Doctor(cmd) => cmd.run().await,
+            Commands::ValidatePin(cmd) => cmd.run().await,
+            Commands::Verify(cmd) => cmd.run().await,
+            Commands::Env(cmd) => cmd.run().await,
             Commands::Update(cmd) => cmd.run().await,
+            Commands::UpgradeTooling(cmd) => cmd.run().await,
+            Commands::MigrateInstalls(cmd) => cmd.run().await,
             Commands::Cache(cmd) => cmd.run().await,
+            Commands::Catalog(cmd) => cmd.run().await,
+            Commands::BuildId(cmd) => cmd.run().await,
+            Commands::DotnetSdkVersion(cmd) => cmd.run().await,
+            Commands::Checksum(cmd) => cmd.run().await,
+            Commands::Run(cmd) => cmd.run().await,
+            Commands::Source(cmd) => cmd.run().await,
+            Commands::Import(cmd) => cmd.run().await,
+            Commands::InspectProject(cmd) => cmd.run().await,
+            Commands::Project(cmd) => cmd.run().await,
+            Commands::Config(cmd) => cmd.run().await,
+            Commands::Lock(cmd) => cmd.run().await,
+            Commands::LintLockfile(cmd) => cmd.run().await,
+            Commands::Hook(cmd) => cmd.run().await,
+            Commands::Watch(cmd) => cmd.run().await,
+            Commands::Completions(cmd) => cmd.run().await,
+            Commands::Complete(cmd) => cmd.run().await,
         }
     }
 }
@@ -0,0 +1,114 @@
+use anyhow::{anyhow, Result};
+use clap::Args;
+use std::path::{Path, PathBuf};
+
+use crate::{
+    config::Config,
+    godot::GodotVersion,
+    installer::{InstallSource, Installer},
+    paths, ui,
+};
+
+#[derive(Args)]
+pub struct AdoptCommand {
+    /// Path to the Godot binary (defaults to $GODOT_BIN, then `godot` on PATH)
+    pub path: Option<PathBuf>,
+
+    /// Also make the adopted version the active one
+    #[arg(long)]
+    pub default: bool,
+}
+
+impl AdoptCommand {
+    pub async fn run(self) -> Result<()> {
+        let config = Config::new()?;
+        let executable = self.find_executable(&config)?;
+        let version = adopt(&config, &executable, InstallSource::Adopted)?;
+
+        ui::success(&format!(
+            "Adopted Godot v{} from {}",
+            version,
+            executable.display()
+        ));
+
+        if self.default {
+            Installer::new(config).set_active_version(&version)?;
+        } else {
+            ui::info(&format!(
+                "Use 'gdenv use {}' to make it the active version",
+                version.godot_version_string()
+            ));
+        }
+
+        Ok(())
+    }
+
+    fn find_executable(&self, config: &Config) -> Result<PathBuf> {
+        if let Some(path) = &self.path {
+            return Ok(path.clone());
+        }
+
+        if let Some(path) = std::env::var_os("GODOT_BIN") {
+            ui::info("Using $GODOT_BIN");
+            return Ok(PathBuf::from(path));
+        }
+
+        let path = which::which("godot")
+            .map_err(|_| anyhow!("No Godot found: pass a path or set $GODOT_BIN"))?;
+
+        // Don't adopt the symlink gdenv itself puts on PATH
+        let shim = config.bin_dir.join("godot");
+        let case_insensitive = paths::is_case_insensitive(&config.installations_dir);
+        if paths::path_eq(&path, &shim, case_insensitive)
+            || path.canonicalize().ok() == shim.canonicalize().ok()
+        {
+            return Err(anyhow!(
+                "The 'godot' on PATH is already managed by gdenv; pass a path to adopt another binary"
+            ));
+        }
+
+        Ok(path)
+    }
+}
+
+/// Detect the version of `executable` and register it as an installation from `source`
+pub fn adopt(config: &Config, executable: &Path, source: InstallSource) -> Result<GodotVersion> {
+    if !executable.is_file() {
+        return Err(anyhow!("{} is not a file", executable.display()));
+    }
+
+    let version = GodotVersion::try_from_path(executable)?;
+    Installer::new(config.clone()).adopt(&version, executable, source)?;
+    Ok(version)
+}
+
+#[cfg(all(test, unix))]
+mod tests {
+    use super::*;
+    use std::fs;
+    use std::os::unix::fs::PermissionsExt;
+
+    #[test]
+    fn test_adopt_registers_binary() {
+        let root = tempfile::tempdir().unwrap();
+        let config = Config::with_data_dir(&root.path().join("gdenv"));
+
+        let binary = root.path().join("godot");
+        fs::write(&binary, "#!/bin/sh\necho 4.2.1.stable.official.b09f793f5\n").unwrap();
+        fs::set_permissions(&binary, fs::Permissions::from_mode(0o755)).unwrap();
+
+        let version = adopt(&config, &binary, InstallSource::Adopted).unwrap();
+        assert_eq!(version, GodotVersion::new("4.2.1", false).unwrap());
+
+        let installer = Installer::new(config.clone());
+        assert_eq!(installer.list_installed().unwrap(), vec![version.clone()]);
+        assert_eq!(
+            installer.get_executable(&version).unwrap(),
+            binary.canonicalize().unwrap()
+        );
+        assert_eq!(installer.source_for(&version), InstallSource::Adopted);
+
+        // Adopting the same version twice is refused
+        assert!(adopt(&config, &binary, InstallSource::Adopted).is_err());
+    }
+}
@@ -0,0 +1,105 @@
+use anyhow::Result;
+use clap::Args;
+use colored::*;
+
+use crate::{
+    config::Config,
+    github::{GitHubClient, GitHubRelease},
+    godot::GodotVersion,
+    ui,
+};
+
+#[derive(Args)]
+pub struct AvailableCommand {
+    /// Leave out prereleases (dev, alpha, beta and rc builds)
+    #[arg(long)]
+    pub stable_only: bool,
+
+    /// Mark the releases that have a .NET build
+    #[arg(long)]
+    pub dotnet: bool,
+}
+
+impl AvailableCommand {
+    pub async fn run(self) -> Result<()> {
+        let config = Config::new()?;
+        let releases = GitHubClient::from_config(&config)
+            .get_godot_releases(!self.stable_only)
+            .await?;
+        let versions = available_versions(&releases, self.stable_only);
+
+        if versions.is_empty() {
+            ui::warning("No Godot releases found");
+            return Ok(());
+        }
+
+        println!("📋 Godot releases, newest first:");
+        for (version, has_dotnet) in &versions {
+            let name = version.godot_version_string();
+            let name = if version.is_prerelease() {
+                name.yellow()
+            } else {
+                name.normal()
+            };
+            if self.dotnet && *has_dotnet {
+                println!("  {} {}", name, "(.NET)".cyan());
+            } else {
+                println!("  {}", name);
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// The versions of `releases` newest first, with whether each has a .NET build.
+/// Releases whose tag isn't a Godot version are skipped.
+fn available_versions(releases: &[GitHubRelease], stable_only: bool) -> Vec<(GodotVersion, bool)> {
+    let mut versions: Vec<(GodotVersion, bool)> = releases
+        .iter()
+        .filter_map(|release| {
+            let version = GodotVersion::from_tag(&release.tag_name).ok()?;
+            Some((version, release.has_dotnet_build()))
+        })
+        .filter(|(version, _)| !stable_only || !version.is_prerelease())
+        .collect();
+    versions.sort_by(|(a, _), (b, _)| b.cmp(a));
+    versions.dedup_by(|(a, _), (b, _)| a == b);
+    versions
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::github::fake_release as release;
+
+    #[test]
+    fn test_available_versions() {
+        let releases = [
+            release("4.2.1-stable", &["Godot_v4.2.1-stable_linux.x86_64.zip"]),
+            release(
+                "4.3-stable",
+                &[
+                    "Godot_v4.3-stable_linux.x86_64.zip",
+                    "Godot_v4.3-stable_mono_linux_x86_64.zip",
+                ],
+            ),
+            release("4.3-beta2", &[]),
+            release("not-a-version", &[]),
+        ];
+
+        let v = |version: &str| GodotVersion::from_tag(version).unwrap();
+        assert_eq!(
+            available_versions(&releases, false),
+            [
+                (v("4.3-stable"), true),
+                (v("4.3-beta2"), false),
+                (v("4.2.1-stable"), false)
+            ]
+        );
+        assert_eq!(
+            available_versions(&releases, true),
+            [(v("4.3-stable"), true), (v("4.2.1-stable"), false)]
+        );
+    }
+}
@@ -0,0 +1,38 @@
+use anyhow::Result;
+use clap::Args;
+use std::path::Path;
+
+use crate::{config::Config, godot::GodotVersion, installer::Installer};
+
+#[derive(Args)]
+pub struct BuildIdCommand {
+    /// The Godot version (defaults to the project's pinned or the active version)
+    pub version: Option<String>,
+
+    /// Use the .NET build of the version
+    #[arg(long)]
+    pub dotnet: bool,
+
+    /// Use the standard build even when the default_dotnet setting is on
+    #[arg(long, conflicts_with = "dotnet")]
+    pub no_dotnet: bool,
+}
+
+impl BuildIdCommand {
+    pub async fn run(self) -> Result<()> {
+        let mut config = Config::new()?;
+        let is_dotnet = config
+            .settings
+            .apply_dotnet_flags(self.dotnet, self.no_dotnet);
+
+        let version = match &self.version {
+            Some(v) => GodotVersion::new(v, is_dotnet)?,
+            None => Installer::new(config).resolve_version_for(Path::new("."))?,
+        };
+
+        // Print only the ID so the output can be used directly as a cache key
+        println!("{}", version.build_id()?);
+
+        Ok(())
+    }
+}
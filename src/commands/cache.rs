@@ -2,7 +2,11 @@ use anyhow::Result;
 use clap::{Args, Subcommand};
 use std::fs;
 
-use crate::{config::Config, ui};
+use crate::{
+    config::Config,
+    godot::GodotVersion,
+    ui::{self, format_size},
+};
 
 #[derive(Args)]
 pub struct CacheCommand {
@@ -79,6 +83,12 @@ impl CacheCommand {
         } else {
             ui::info(&format!("Cache size: {}", format_size(cache_size)));
             ui::info(&format!("Cached files: {}", file_count));
+
+            let versions = self.cached_versions(config)?;
+            if !versions.is_empty() {
+                let versions: Vec<String> = versions.iter().map(|v| v.to_string()).collect();
+                ui::info(&format!("Cached versions: {}", versions.join(", ")));
+            }
             ui::info("Run 'gdenv cache clear' to free up space");
         }
 
@@ -103,6 +113,19 @@ impl CacheCommand {
         Ok(total_size)
     }
 
+    /// The versions of the archives in the cache, newest first
+    fn cached_versions(&self, config: &Config) -> Result<Vec<GodotVersion>> {
+        let mut versions: Vec<GodotVersion> = fs::read_dir(&config.cache_dir)?
+            .filter_map(|entry| entry.ok())
+            .filter_map(|entry| {
+                GodotVersion::from_archive_name(&entry.file_name().to_string_lossy()).ok()
+            })
+            .collect();
+        versions.sort_by(|a, b| b.cmp(a));
+        versions.dedup();
+        Ok(versions)
+    }
+
     fn count_cache_files(&self, config: &Config) -> Result<usize> {
         let mut count = 0;
 
@@ -120,20 +143,3 @@ impl CacheCommand {
         Ok(count)
     }
 }
-
-fn format_size(bytes: u64) -> String {
-    const UNITS: &[&str] = &["B", "KB", "MB", "GB"];
-    let mut size = bytes as f64;
-    let mut unit_index = 0;
-
-    while size >= 1024.0 && unit_index < UNITS.len() - 1 {
-        size /= 1024.0;
-        unit_index += 1;
-    }
-
-    if unit_index == 0 {
-        format!("{} {}", bytes, UNITS[unit_index])
-    } else {
-        format!("{:.1} {}", size, UNITS[unit_index])
-    }
-}
@@ -0,0 +1,237 @@
+use anyhow::Result;
+use clap::{Args, Subcommand, ValueEnum};
+use std::fs;
+use std::path::PathBuf;
+
+use crate::{
+    catalog::{CatalogFile, VersionCatalog},
+    config::Config,
+    github::GitHubClient,
+    godot::GodotVersion,
+    installer::Installer,
+    requirements, ui,
+};
+
+#[derive(Args)]
+pub struct CatalogCommand {
+    #[command(subcommand)]
+    pub action: CatalogAction,
+}
+
+#[derive(Subcommand)]
+pub enum CatalogAction {
+    /// Show statistics about the Godot release history
+    Stats {
+        /// Print the statistics as JSON
+        #[arg(long)]
+        json: bool,
+    },
+    /// List released Godot versions, marking the pinned ones
+    List {
+        /// Only list pinned versions
+        #[arg(long)]
+        pinned: bool,
+    },
+    /// Pin a version so cleanups like `uninstall --unused` never remove it
+    Pin {
+        /// The version to pin, e.g. 3.5.3 (covers its .NET build too)
+        version: String,
+    },
+    /// Remove the pin from a version
+    Unpin {
+        /// The version to unpin
+        version: String,
+    },
+    /// Write Godot versions in a format other tools understand
+    Export {
+        /// The output format; "pip" writes a requirements file that `gdenv install -r` reads
+        #[arg(long, value_enum)]
+        format: ExportFormat,
+
+        /// Only export the installed versions instead of every released one
+        #[arg(long)]
+        installed_only: bool,
+
+        /// File to write to (default: standard output)
+        #[arg(long, short)]
+        output: Option<PathBuf>,
+    },
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum ExportFormat {
+    /// pip-style requirements, one `godot==<version>` line per version
+    #[value(alias = "pip-requirements-style")]
+    Pip,
+}
+
+impl CatalogCommand {
+    pub async fn run(self) -> Result<()> {
+        let config = Config::new()?;
+        let github_client = GitHubClient::from_config(&config);
+
+        match self.action {
+            CatalogAction::Stats { json } => {
+                let releases = github_client.get_godot_releases(true).await?;
+                let stats = VersionCatalog::from_releases(&releases).statistics();
+
+                if json {
+                    println!("{}", serde_json::to_string_pretty(&stats)?);
+                    return Ok(());
+                }
+
+                println!("📊 Godot release statistics:");
+                ui::info(&format!("Total versions: {}", stats.total_versions));
+                for (major, count) in &stats.versions_by_major {
+                    println!("  • Godot {}.x: {}", major, count);
+                }
+                ui::info(&format!(
+                    "Stable: {}, prerelease: {}",
+                    stats.stable_versions, stats.prerelease_versions
+                ));
+                if let Some(days) = stats.average_days_between_stable {
+                    ui::info(&format!(
+                        "Average time between stable releases: {:.1} days",
+                        days
+                    ));
+                }
+                if let (Some(oldest), Some(newest)) = (&stats.oldest_version, &stats.newest_version)
+                {
+                    ui::info(&format!("Oldest: {}, newest: {}", oldest, newest));
+                }
+            }
+            CatalogAction::List { pinned } => {
+                let catalog_file = CatalogFile::load(&config)?;
+                let versions = if pinned {
+                    catalog_file.pinned()
+                } else {
+                    let mut versions: Vec<GodotVersion> = github_client
+                        .get_godot_releases(true)
+                        .await?
+                        .iter()
+                        .filter_map(|r| GodotVersion::from_tag(&r.tag_name).ok())
+                        .collect();
+                    versions.sort();
+                    versions
+                };
+
+                if versions.is_empty() {
+                    ui::info(if pinned {
+                        "No pinned versions; pin one with 'gdenv catalog pin <version>'"
+                    } else {
+                        "No releases found"
+                    });
+                }
+                for version in &versions {
+                    let pin = if catalog_file.is_pinned(version) {
+                        " 📌 pinned"
+                    } else {
+                        ""
+                    };
+                    println!("  • {}{}", version, pin);
+                }
+            }
+            CatalogAction::Pin { version } => {
+                let version: GodotVersion = version.parse()?;
+                let mut catalog_file = CatalogFile::load(&config)?;
+                if catalog_file.pin(&version) {
+                    catalog_file.save(&config)?;
+                    ui::success(&format!("Pinned Godot v{}", version.godot_version_string()));
+                } else {
+                    ui::info(&format!(
+                        "Godot v{} is already pinned",
+                        version.godot_version_string()
+                    ));
+                }
+                if !catalog_file.pruning.keep_pinned {
+                    ui::warning(&format!(
+                        "pruning.keep_pinned is off in {}, so cleanups still remove pinned versions",
+                        CatalogFile::path(&config).display()
+                    ));
+                }
+            }
+            CatalogAction::Unpin { version } => {
+                let version: GodotVersion = version.parse()?;
+                let mut catalog_file = CatalogFile::load(&config)?;
+                if catalog_file.unpin(&version) {
+                    catalog_file.save(&config)?;
+                    ui::success(&format!(
+                        "Unpinned Godot v{}",
+                        version.godot_version_string()
+                    ));
+                } else {
+                    ui::info(&format!(
+                        "Godot v{} isn't pinned",
+                        version.godot_version_string()
+                    ));
+                }
+            }
+            CatalogAction::Export {
+                format: ExportFormat::Pip,
+                installed_only,
+                output,
+            } => {
+                let versions = if installed_only {
+                    Installer::new(config).list_installed()?
+                } else {
+                    github_client
+                        .get_godot_releases(true)
+                        .await?
+                        .iter()
+                        .filter_map(|r| GodotVersion::from_tag(&r.tag_name).ok())
+                        .collect()
+                };
+
+                let content = pip_requirements(versions);
+                match output {
+                    Some(path) => {
+                        fs::write(&path, content)?;
+                        ui::success(&format!("Wrote {}", path.display()));
+                    }
+                    None => print!("{}", content),
+                }
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// One pinned requirement per version, oldest first
+fn pip_requirements(mut versions: Vec<GodotVersion>) -> String {
+    versions.sort();
+    versions.dedup();
+    versions
+        .iter()
+        .map(|version| format!("{}\n", requirements::format(version)))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_pip_requirements_round_trip() {
+        let versions = vec![
+            GodotVersion::new("4.3", false).unwrap(),
+            GodotVersion::new("4.2.1", true).unwrap(),
+            GodotVersion::new("4.2.1", false).unwrap(),
+        ];
+        let content = pip_requirements(versions.clone());
+        assert_eq!(
+            content,
+            "godot==4.2.1\ngodot[dotnet]==4.2.1\ngodot==4.3.0\n"
+        );
+
+        let mut parsed: Vec<_> = requirements::parse(&content)
+            .unwrap()
+            .into_iter()
+            .map(|c| c.exact.unwrap())
+            .collect();
+        parsed.sort();
+        let mut versions = versions;
+        versions.sort();
+        assert_eq!(parsed, versions);
+    }
+}
@@ -0,0 +1,49 @@
+use anyhow::Result;
+use clap::Args;
+
+use crate::{
+    config::Config,
+    godot::GodotVersion,
+    installer::{self, Installer},
+};
+
+#[derive(Args)]
+pub struct ChecksumCommand {
+    /// The installed Godot version whose binary to hash
+    #[arg(required_unless_present = "all", conflicts_with = "all")]
+    pub version: Option<String>,
+
+    /// Use the .NET build of the version
+    #[arg(long)]
+    pub dotnet: bool,
+
+    /// Use the standard build even when the default_dotnet setting is on
+    #[arg(long, conflicts_with = "dotnet")]
+    pub no_dotnet: bool,
+
+    /// Print a single digest covering every installation (e.g. for a CI cache key)
+    #[arg(long)]
+    pub all: bool,
+}
+
+impl ChecksumCommand {
+    pub async fn run(self) -> Result<()> {
+        let mut config = Config::new()?;
+        let is_dotnet = config
+            .settings
+            .apply_dotnet_flags(self.dotnet, self.no_dotnet);
+        let installer = Installer::new(config);
+
+        // Print only the digest so the output can be used directly as a cache key
+        match &self.version {
+            Some(version) => {
+                let version = GodotVersion::new(version, is_dotnet)?;
+                let executable = installer.get_executable(&version)?;
+                println!("{}", installer::file_sha256(&executable)?);
+            }
+            None => println!("{}", installer.compute_total_checksum()?),
+        }
+
+        Ok(())
+    }
+}
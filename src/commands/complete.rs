@@ -0,0 +1,65 @@
+use anyhow::Result;
+use clap::{Args, ValueEnum};
+use std::io::{self, Write};
+
+use crate::{config::Config, installer::Installer};
+
+#[derive(Args)]
+pub struct CompleteCommand {
+    /// What to complete
+    #[arg(value_enum)]
+    pub kind: CompleteKind,
+}
+
+#[derive(Clone, Copy, ValueEnum)]
+pub enum CompleteKind {
+    /// Installed Godot versions
+    Versions,
+}
+
+impl CompleteCommand {
+    pub async fn run(self) -> Result<()> {
+        // Completion must stay fast and quiet, so this never touches the network
+        let installer = Installer::new(Config::default());
+
+        match self.kind {
+            CompleteKind::Versions => write_installed_versions(&installer, &mut io::stdout()),
+        }
+    }
+}
+
+fn write_installed_versions(installer: &Installer, out: &mut impl Write) -> Result<()> {
+    let mut versions: Vec<String> = installer
+        .list_installed()?
+        .iter()
+        .map(|v| v.godot_version_string())
+        .collect();
+    versions.dedup();
+
+    for version in versions {
+        writeln!(out, "{}", version)?;
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+
+    #[test]
+    fn test_complete_versions_lists_installed() {
+        let root = tempfile::tempdir().unwrap();
+        let config = Config::with_data_dir(root.path());
+        fs::create_dir_all(config.installations_dir.join("godot-4.2.1")).unwrap();
+        fs::create_dir_all(config.installations_dir.join("godot-4.2.1-dotnet")).unwrap();
+        fs::create_dir_all(config.installations_dir.join("godot-4.3.0-beta2")).unwrap();
+        fs::create_dir_all(config.installations_dir.join("not-godot")).unwrap();
+
+        let mut out = Vec::new();
+        write_installed_versions(&Installer::new(config), &mut out).unwrap();
+
+        assert_eq!(String::from_utf8(out).unwrap(), "4.2.1\n4.3.0-beta2\n");
+    }
+}
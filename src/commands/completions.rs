@@ -0,0 +1,106 @@
+use anyhow::{anyhow, Result};
+use clap::{Args, CommandFactory};
+use clap_complete::Shell;
+use std::io;
+
+use crate::cli::Cli;
+
+#[derive(Args)]
+pub struct CompletionsCommand {
+    /// The shell to generate completions for
+    #[arg(value_enum)]
+    pub shell: Shell,
+
+    /// Also complete installed versions for `use` and `uninstall`
+    #[arg(long)]
+    pub dynamic: bool,
+}
+
+impl CompletionsCommand {
+    pub async fn run(self) -> Result<()> {
+        let mut command = Cli::command();
+        clap_complete::generate(self.shell, &mut command, "gdenv", &mut io::stdout());
+
+        if self.dynamic {
+            let hook = dynamic_hook(self.shell).ok_or_else(|| {
+                anyhow!("Dynamic completions are not supported for {}", self.shell)
+            })?;
+            println!("{}", hook);
+        }
+
+        Ok(())
+    }
+}
+
+/// Shell snippet that completes installed versions by calling `gdenv __complete versions`
+fn dynamic_hook(shell: Shell) -> Option<&'static str> {
+    match shell {
+        Shell::Bash => Some(
+            r#"
+_gdenv_dynamic() {
+    if [[ ${COMP_CWORD} -eq 2 ]]; then
+        case "${COMP_WORDS[1]}" in
+            use|uninstall)
+                COMPREPLY=($(compgen -W "$(gdenv __complete versions 2>/dev/null)" -- "${COMP_WORDS[COMP_CWORD]}"))
+                return 0
+                ;;
+        esac
+    fi
+    _gdenv "$@"
+}
+complete -F _gdenv_dynamic -o bashdefault -o default gdenv"#,
+        ),
+        Shell::Zsh => Some(
+            r#"
+_gdenv_dynamic() {
+    if (( CURRENT == 3 )) && [[ ${words[2]} == (use|uninstall) ]]; then
+        local -a versions
+        versions=(${(f)"$(gdenv __complete versions 2>/dev/null)"})
+        compadd -a versions
+        return
+    fi
+    _gdenv "$@"
+}
+compdef _gdenv_dynamic gdenv"#,
+        ),
+        Shell::Fish => Some(
+            r#"
+complete -c gdenv -n "__fish_seen_subcommand_from use uninstall" -f -a "(gdenv __complete versions 2>/dev/null)""#,
+        ),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_scripts_cover_every_subcommand() {
+        let mut command = Cli::command();
+        let subcommands: Vec<String> = command
+            .get_subcommands()
+            .filter(|sub| !sub.is_hide_set())
+            .map(|sub| sub.get_name().to_string())
+            .collect();
+
+        for shell in [Shell::Bash, Shell::Zsh, Shell::Fish, Shell::PowerShell] {
+            let mut script = Vec::new();
+            clap_complete::generate(shell, &mut command, "gdenv", &mut script);
+            let script = String::from_utf8(script).unwrap();
+            for subcommand in &subcommands {
+                assert!(
+                    script.contains(subcommand.as_str()),
+                    "{} {}",
+                    shell,
+                    subcommand
+                );
+            }
+        }
+
+        assert!(dynamic_hook(Shell::Bash)
+            .unwrap()
+            .contains("__complete versions"));
+        assert!(dynamic_hook(Shell::PowerShell).is_none());
+    }
+}
@@ -0,0 +1,116 @@
+use anyhow::{anyhow, Result};
+use clap::{Args, Subcommand};
+
+use crate::{
+    config::{self, Config, Settings},
+    installer::Installer,
+    ui,
+};
+
+#[derive(Args)]
+pub struct ConfigCommand {
+    #[command(subcommand)]
+    pub action: ConfigAction,
+}
+
+#[derive(Subcommand)]
+pub enum ConfigAction {
+    /// Print the effective value of a setting
+    Get {
+        /// The setting to read
+        key: String,
+    },
+    /// Save a setting to the config file
+    Set {
+        /// The setting to change
+        key: String,
+        /// The new value (comma-separated for lists, empty to clear)
+        value: String,
+    },
+    /// List all settings with their effective values
+    List,
+    /// Print the path of the config file
+    Path,
+}
+
+impl ConfigCommand {
+    pub async fn run(self) -> Result<()> {
+        let config = Config::new()?;
+
+        match self.action {
+            ConfigAction::Get { key } => println!("{}", config.settings.get(&key)?),
+            ConfigAction::Set { key, value } => {
+                // Only persist what's in the file, not environment overrides
+                let effective = config.clone();
+                let mut config = config;
+                config.settings = Settings::load_file(&config.settings_path())?;
+                config.settings.set(&key, &value)?;
+                if key == "install_dir_template" {
+                    check_installs_keep_names(&effective, &config.settings)?;
+                }
+                config::save(&config)?;
+                ui::success(&format!("Set {} = {}", key, config.settings.get(&key)?));
+            }
+            ConfigAction::List => {
+                for key in Settings::KEYS {
+                    println!("{} = {}", key, config.settings.get(key)?);
+                }
+            }
+            ConfigAction::Path => println!("{}", config.settings_path().display()),
+        }
+
+        Ok(())
+    }
+}
+
+/// Refuse to change `install_dir_template` while there are installations named by the
+/// current one: gdenv would no longer find them under the new names
+fn check_installs_keep_names(config: &Config, settings: &Settings) -> Result<()> {
+    let new_config = Config {
+        settings: settings.clone(),
+        ..config.clone()
+    };
+    if new_config.install_name_template() == config.install_name_template() {
+        return Ok(());
+    }
+
+    let installed = Installer::new(config.clone()).list_installed()?;
+    if installed.is_empty() {
+        return Ok(());
+    }
+    Err(anyhow!(
+        "{} installation(s) in {} are named by the current template '{}' and would no \
+         longer be found. Uninstall them first, or rename their directories to match the new \
+         template and change it in {}",
+        installed.len(),
+        config.installations_dir.display(),
+        config.install_name_template(),
+        config.settings_path().display()
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::godot::GodotVersion;
+    use crate::installer::fake_install;
+
+    #[test]
+    fn test_template_change_refused_while_installed() {
+        let root = tempfile::tempdir().unwrap();
+        let config = Config::with_data_dir(root.path());
+        let mut settings = config.settings.clone();
+        settings
+            .set("install_dir_template", "{version}{dotnet}")
+            .unwrap();
+
+        assert!(check_installs_keep_names(&config, &settings).is_ok());
+
+        fake_install(&config, &GodotVersion::new("4.2.1", false).unwrap(), "");
+        let error = check_installs_keep_names(&config, &settings).unwrap_err();
+        assert!(error.to_string().contains("1 installation(s)"), "{}", error);
+
+        // Setting the same template again is fine
+        assert!(check_installs_keep_names(&config, &config.settings).is_ok());
+    }
+}
@@ -2,7 +2,7 @@ use anyhow::Result;
 use clap::Args;
 use std::path::Path;
 
-use crate::{config::Config, installer::Installer, ui};
+use crate::{config::Config, installer::Installer, project, ui};
 
 #[derive(Args)]
 pub struct CurrentCommand {
@@ -24,6 +24,15 @@ impl CurrentCommand {
                     ui::success(&format!("Active Godot version: {}", version));
                     ui::info(&format!("Location: {}", config.active_symlink.display()));
 
+                    if let Some(pin) = project::discover_project_version(Path::new("."))? {
+                        if !pin.allows(&version) {
+                            ui::info(&format!(
+                                "This project's .godot-version pins {}, which gdenv uses here instead",
+                                pin
+                            ));
+                        }
+                    }
+
                     // Show executable path info
                     let godot_executable = config.bin_dir.join("godot");
                     if godot_executable.exists() {
@@ -0,0 +1,145 @@
+use anyhow::Result;
+use clap::Args;
+use std::path::Path;
+
+use crate::{
+    config::Config,
+    connectivity::{self, PROBE_TIMEOUT},
+    github::GitHubClient,
+    godot::{GodotVersion, RenderingMethod, WSL_MODE_VAR, WSL_NATIVE_WINDOWS},
+    installer::Installer,
+    platform::PlatformTarget,
+    project, ui,
+};
+
+#[derive(Args)]
+pub struct DoctorCommand {
+    /// Check support for this rendering method instead of the active version's default
+    #[arg(long, value_enum)]
+    pub rendering_method: Option<RenderingMethod>,
+}
+
+impl DoctorCommand {
+    pub async fn run(self) -> Result<()> {
+        let config = Config::new()?;
+        let installer = Installer::new(config.clone());
+        let platform = PlatformTarget::current();
+        let mut problems = 0;
+
+        println!("🩺 Checking your gdenv setup...");
+
+        let active_version = installer.get_active_version()?;
+        match &active_version {
+            Some(version) => ui::success(&format!("Active version: {}", version)),
+            None => {
+                problems += 1;
+                ui::warning("No active Godot version. Use 'gdenv use <version>' to set one.");
+            }
+        }
+
+        let on_path = std::env::var_os("PATH")
+            .is_some_and(|path| std::env::split_paths(&path).any(|dir| dir == config.bin_dir));
+        if on_path {
+            ui::success(&format!("{} is on your PATH", config.bin_dir.display()));
+        } else {
+            problems += 1;
+            ui::warning(&format!(
+                "{} is not on your PATH, run 'gdenv current' for setup instructions",
+                config.bin_dir.display()
+            ));
+        }
+
+        if GodotVersion::is_running_in_wsl() {
+            if GodotVersion::target_os() == "windows" {
+                ui::info(
+                    "Running inside WSL with Windows builds; launch Godot with 'wslview' or \
+                     'cmd.exe /C'",
+                );
+            } else {
+                ui::info(&format!(
+                    "Running inside WSL with Linux builds; set {}={} to use Windows builds instead",
+                    WSL_MODE_VAR, WSL_NATIVE_WINDOWS
+                ));
+            }
+        }
+
+        if let Some(warning) = platform.libc().official_build_warning() {
+            problems += 1;
+            ui::warning(warning);
+        }
+
+        let rendering_method = self.rendering_method.or_else(|| {
+            active_version
+                .as_ref()
+                .and_then(|v| v.preferred_rendering_method())
+        });
+        if let Some(method) = rendering_method {
+            if !method.uses_vulkan() {
+                ui::success(&format!("{} doesn't need Vulkan", method));
+            } else if platform.has_vulkan() {
+                ui::success(&format!("Vulkan is available for {}", method));
+            } else {
+                problems += 1;
+                ui::warning(&format!(
+                    "{} needs Vulkan, but no Vulkan loader was found. Install your GPU's Vulkan \
+                     driver or use --rendering-method gl_compatibility.",
+                    method
+                ));
+            }
+        }
+
+        if project::uses_jolt_addon(Path::new(".")) {
+            match &active_version {
+                Some(version) if !version.supports_jolt() => {
+                    problems += 1;
+                    ui::warning(&format!(
+                        "This project uses the godot-jolt addon, which needs Godot 4.2 or newer, \
+                         but the active version is v{}",
+                        version
+                    ));
+                }
+                Some(version) => ui::success(&format!("Godot v{} supports Jolt physics", version)),
+                None => {}
+            }
+        }
+
+        if config.settings.offline {
+            ui::info("Offline mode is on, skipping the connectivity check");
+        } else {
+            match connectivity::active_proxy(&config) {
+                Some(proxy) => ui::info(&format!("Using proxy {}", proxy)),
+                None => ui::info("No proxy configured"),
+            }
+
+            let github_client = GitHubClient::from_config(&config);
+            let results = connectivity::probe_all(
+                github_client.http_client()?,
+                &connectivity::sources(&config),
+                PROBE_TIMEOUT,
+            )
+            .await;
+            for result in results {
+                let line = format!(
+                    "{}: {} in {} ms",
+                    result.url,
+                    result.status,
+                    result.latency.as_millis()
+                );
+                if result.status.is_reachable() {
+                    ui::success(&line);
+                } else {
+                    problems += 1;
+                    ui::warning(&line);
+                }
+            }
+        }
+
+        if problems == 0 {
+            ui::success("No problems found");
+        } else {
+            ui::info(&format!("{} problem(s) found", problems));
+        }
+
+        Ok(())
+    }
+}
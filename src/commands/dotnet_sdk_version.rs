@@ -0,0 +1,34 @@
+use anyhow::{anyhow, Result};
+use clap::Args;
+use std::path::Path;
+
+use crate::{config::Config, godot::GodotVersion, installer::Installer};
+
+#[derive(Args)]
+pub struct DotnetSdkVersionCommand {
+    /// The Godot version (defaults to the project's pinned or the active version)
+    pub version: Option<String>,
+}
+
+impl DotnetSdkVersionCommand {
+    pub async fn run(self) -> Result<()> {
+        let config = Config::new()?;
+
+        let version = match &self.version {
+            Some(v) => GodotVersion::new(v, true)?,
+            None => Installer::new(config).resolve_version_for(Path::new("."))?,
+        };
+
+        let nuget_version = version.nuget_package_version().ok_or_else(|| {
+            anyhow!(
+                "Godot v{} predates the Godot.NET.Sdk NuGet package (Godot 4 and later)",
+                version.godot_version_string()
+            )
+        })?;
+
+        // Print only the version so it can be substituted into a <PackageReference>
+        println!("{}", nuget_version);
+
+        Ok(())
+    }
+}
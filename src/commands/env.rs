@@ -0,0 +1,208 @@
+use anyhow::{anyhow, Result};
+use clap::{ArgGroup, Args};
+use std::fs;
+use std::path::Path;
+
+use crate::ui;
+
+const BLOCK_START: &str = "# >>> gdenv shell integration >>>";
+const BLOCK_END: &str = "# <<< gdenv shell integration <<<";
+
+const ZSH_HOOK: &str = r#"_gdenv_chpwd() {
+    gdenv status --quiet
+}
+autoload -Uz add-zsh-hook
+add-zsh-hook chpwd _gdenv_chpwd"#;
+
+const BASH_HOOK: &str = r#"_gdenv_prompt() {
+    if [[ "$PWD" != "$_GDENV_LAST_PWD" ]]; then
+        _GDENV_LAST_PWD="$PWD"
+        gdenv status --quiet
+    fi
+}
+_GDENV_LAST_PWD="$PWD"
+PROMPT_COMMAND="_gdenv_prompt${PROMPT_COMMAND:+;$PROMPT_COMMAND}""#;
+
+const FISH_CD: &str = r#"function cd --description 'Change directory and show the pinned Godot version'
+    builtin cd $argv; or return
+    gdenv status --quiet
+end"#;
+
+#[derive(Args)]
+#[command(group(
+    ArgGroup::new("shell")
+        .required(true)
+        .args(["integrate_zsh", "integrate_bash", "integrate_fish"])
+))]
+pub struct EnvCommand {
+    /// Show the pinned Godot version on every `cd` in zsh (edits ~/.zshrc)
+    #[arg(long)]
+    pub integrate_zsh: bool,
+
+    /// Show the pinned Godot version on every `cd` in bash (edits ~/.bashrc)
+    #[arg(long)]
+    pub integrate_bash: bool,
+
+    /// Show the pinned Godot version on every `cd` in fish (writes ~/.config/fish/functions/cd.fish)
+    #[arg(long)]
+    pub integrate_fish: bool,
+
+    /// Remove the integration instead of installing it
+    #[arg(long)]
+    pub remove: bool,
+}
+
+impl EnvCommand {
+    pub async fn run(self) -> Result<()> {
+        let home = dirs::home_dir().ok_or_else(|| anyhow!("Could not find home directory"))?;
+
+        if self.integrate_fish {
+            return self.integrate_fish(&home.join(".config/fish/functions/cd.fish"));
+        }
+
+        let (rc_file, hook) = if self.integrate_zsh {
+            (home.join(".zshrc"), ZSH_HOOK)
+        } else {
+            (home.join(".bashrc"), BASH_HOOK)
+        };
+
+        let content = match fs::read_to_string(&rc_file) {
+            Ok(content) => content,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => String::new(),
+            Err(e) => return Err(e.into()),
+        };
+
+        if self.remove {
+            match remove_block(&content) {
+                Some(updated) => {
+                    fs::write(&rc_file, updated)?;
+                    ui::success(&format!(
+                        "Removed shell integration from {}",
+                        rc_file.display()
+                    ));
+                }
+                None => ui::info(&format!(
+                    "No shell integration found in {}",
+                    rc_file.display()
+                )),
+            }
+            return Ok(());
+        }
+
+        match add_block(&content, hook) {
+            Some(updated) => {
+                fs::write(&rc_file, updated)?;
+                ui::success(&format!("Added shell integration to {}", rc_file.display()));
+                ui::info("Restart your shell to activate it");
+            }
+            None => ui::info(&format!(
+                "Shell integration is already installed in {}",
+                rc_file.display()
+            )),
+        }
+
+        Ok(())
+    }
+
+    fn integrate_fish(&self, cd_function: &Path) -> Result<()> {
+        let existing = fs::read_to_string(cd_function).ok();
+        let ours = existing
+            .as_deref()
+            .is_some_and(|content| content.contains(BLOCK_START));
+
+        if self.remove {
+            if ours {
+                fs::remove_file(cd_function)?;
+                ui::success(&format!("Removed {}", cd_function.display()));
+            } else {
+                ui::info("No fish shell integration found");
+            }
+            return Ok(());
+        }
+
+        if ours {
+            ui::info(&format!(
+                "Shell integration is already installed in {}",
+                cd_function.display()
+            ));
+            return Ok(());
+        }
+        if existing.is_some() {
+            return Err(anyhow!(
+                "{} already exists and wasn't created by gdenv; add 'gdenv status --quiet' to it manually",
+                cd_function.display()
+            ));
+        }
+
+        if let Some(parent) = cd_function.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        fs::write(
+            cd_function,
+            format!("{}\n{}\n{}\n", BLOCK_START, FISH_CD, BLOCK_END),
+        )?;
+        ui::success(&format!(
+            "Added shell integration to {}",
+            cd_function.display()
+        ));
+        Ok(())
+    }
+}
+
+/// Append the marked gdenv block to a shell file, or `None` if it's already there
+fn add_block(content: &str, hook: &str) -> Option<String> {
+    if content.contains(BLOCK_START) {
+        return None;
+    }
+
+    let mut updated = content.to_string();
+    if !updated.is_empty() && !updated.ends_with('\n') {
+        updated.push('\n');
+    }
+    if !updated.is_empty() {
+        updated.push('\n');
+    }
+    updated.push_str(&format!("{}\n{}\n{}\n", BLOCK_START, hook, BLOCK_END));
+    Some(updated)
+}
+
+/// Remove the marked gdenv block from a shell file, or `None` if it isn't there
+fn remove_block(content: &str) -> Option<String> {
+    let start = content.find(BLOCK_START)?;
+    let end = content[start..].find(BLOCK_END)? + start + BLOCK_END.len();
+
+    let before = content[..start].trim_end_matches('\n');
+    let after = content[end..].trim_start_matches('\n');
+
+    let mut updated = before.to_string();
+    if !before.is_empty() {
+        updated.push('\n');
+    }
+    if !after.is_empty() {
+        if !before.is_empty() {
+            updated.push('\n');
+        }
+        updated.push_str(after);
+    }
+    Some(updated)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_add_and_remove_block() {
+        let original = "export EDITOR=vim\n";
+
+        let added = add_block(original, ZSH_HOOK).unwrap();
+        assert!(added.starts_with(original));
+        assert!(added.contains("add-zsh-hook chpwd _gdenv_chpwd"));
+
+        // Adding twice is a no-op
+        assert_eq!(add_block(&added, ZSH_HOOK), None);
+
+        assert_eq!(remove_block(&added).unwrap(), original);
+        assert_eq!(remove_block(original), None);
+    }
+}
@@ -0,0 +1,49 @@
+use anyhow::Result;
+use clap::Args;
+
+use crate::{
+    config::Config,
+    hooks::{GlobalHooks, HookEvent},
+    ui,
+};
+
+#[derive(Args)]
+pub struct HookCommand {
+    /// The event to hook into
+    #[arg(value_enum)]
+    pub event: HookEvent,
+
+    /// Shell command to run for the event (omit to show the current hook)
+    pub command: Option<String>,
+
+    /// Remove the hook registered for the event
+    #[arg(long, conflicts_with = "command")]
+    pub remove: bool,
+}
+
+impl HookCommand {
+    pub async fn run(self) -> Result<()> {
+        let config = Config::new()?;
+        let mut hooks = GlobalHooks::load(&config)?;
+
+        if self.remove {
+            hooks.set(self.event, None);
+            hooks.save(&config)?;
+            ui::success("Hook removed");
+        } else if let Some(command) = self.command {
+            hooks.set(self.event, Some(command));
+            hooks.save(&config)?;
+            ui::success(&format!(
+                "Hook saved to {}",
+                GlobalHooks::path(&config).display()
+            ));
+        } else {
+            match hooks.get(self.event) {
+                Some(command) => println!("{}", command),
+                None => ui::info("No hook registered for this event"),
+            }
+        }
+
+        Ok(())
+    }
+}
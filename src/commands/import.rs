@@ -0,0 +1,77 @@
+use anyhow::{anyhow, Result};
+use clap::Args;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+use crate::{config::Config, installer::Installer, project::PROJECT_FILE, ui};
+
+#[derive(Args)]
+pub struct ImportCommand {
+    /// Path to the Godot project directory
+    #[arg(default_value = ".")]
+    pub path: PathBuf,
+}
+
+impl ImportCommand {
+    pub async fn run(self) -> Result<()> {
+        if !self.path.join(PROJECT_FILE).exists() {
+            return Err(anyhow!(
+                "No {} found in {}",
+                PROJECT_FILE,
+                self.path.display()
+            ));
+        }
+
+        let installer = Installer::new(Config::new()?);
+        let version = installer.resolve_version_for(&self.path)?;
+        let godot = installer.get_executable(&version)?;
+
+        ui::info(&format!(
+            "Importing {} with Godot v{}",
+            self.path.display(),
+            version
+        ));
+
+        let status = import_command(&godot, &self.path).status()?;
+
+        if !status.success() {
+            ui::error("Import failed, see the Godot output above");
+            std::process::exit(status.code().unwrap_or(1));
+        }
+
+        ui::success("Import complete");
+        Ok(())
+    }
+}
+
+/// Build the command that makes the editor import a project's assets and exit
+pub fn import_command(godot: &Path, project_dir: &Path) -> Command {
+    let mut command = Command::new(godot);
+    command
+        .args(["--headless", "--import", "--path"])
+        .arg(project_dir);
+    command
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::ffi::OsStr;
+
+    #[test]
+    fn test_import_command_args() {
+        let command = import_command(
+            Path::new("/opt/godot/Godot_v4.2.1-stable_linux.x86_64"),
+            Path::new("/work/my game"),
+        );
+
+        assert_eq!(
+            command.get_program(),
+            "/opt/godot/Godot_v4.2.1-stable_linux.x86_64"
+        );
+        assert_eq!(
+            command.get_args().collect::<Vec<_>>(),
+            ["--headless", "--import", "--path", "/work/my game"].map(OsStr::new)
+        );
+    }
+}
@@ -0,0 +1,250 @@
+use anyhow::{anyhow, Result};
+use clap::Args;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use crate::{
+    commands::adopt::adopt,
+    config::Config,
+    godot::GodotVersion,
+    installer::{InstallSource, Installer},
+    ui,
+};
+
+/// Directory Steam installs the Godot Engine app into, inside a library's `steamapps/common`
+const STEAM_APP_DIR: &str = "Godot Engine";
+
+#[derive(Args)]
+pub struct ImportFromSteamCommand {
+    /// Steam library to look in (the directory containing `steamapps`) instead of
+    /// the default Steam locations
+    #[arg(long, value_name = "PATH")]
+    pub steam_library: Option<PathBuf>,
+}
+
+impl ImportFromSteamCommand {
+    pub async fn run(self) -> Result<()> {
+        let config = Config::new()?;
+
+        let libraries = match &self.steam_library {
+            Some(library) => vec![library.clone()],
+            None => steam_libraries(&default_steam_roots()),
+        };
+
+        let binaries: Vec<PathBuf> = libraries
+            .iter()
+            .flat_map(|library| find_godot_binaries(library))
+            .collect();
+        if binaries.is_empty() {
+            return Err(anyhow!(
+                "No Steam installation of Godot found in {}; pass --steam-library to point at your Steam library",
+                libraries
+                    .iter()
+                    .map(|l| l.display().to_string())
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            ));
+        }
+
+        let installer = Installer::new(config.clone());
+        for binary in &binaries {
+            let version = match GodotVersion::try_from_path(binary) {
+                Ok(version) => version,
+                Err(e) => {
+                    ui::warning(&e.to_string());
+                    continue;
+                }
+            };
+
+            if installer.is_installed(&version) {
+                ui::info(&format!(
+                    "Godot v{} is already installed, skipping {}",
+                    version,
+                    binary.display()
+                ));
+                continue;
+            }
+
+            adopt(&config, binary, InstallSource::Steam)?;
+            ui::success(&format!(
+                "Imported Godot v{} from Steam ({})",
+                version,
+                binary.display()
+            ));
+        }
+
+        Ok(())
+    }
+}
+
+/// The usual locations of the Steam client on this OS
+fn default_steam_roots() -> Vec<PathBuf> {
+    let home = dirs::home_dir().unwrap_or_default();
+
+    if cfg!(target_os = "windows") {
+        let program_files = std::env::var_os("ProgramFiles(x86)")
+            .map(PathBuf::from)
+            .unwrap_or_else(|| PathBuf::from(r"C:\Program Files (x86)"));
+        vec![program_files.join("Steam")]
+    } else if cfg!(target_os = "macos") {
+        vec![home.join("Library/Application Support/Steam")]
+    } else {
+        vec![
+            home.join(".steam/steam"),
+            home.join(".local/share/Steam"),
+            // Flatpak
+            home.join(".var/app/com.valvesoftware.Steam/.local/share/Steam"),
+        ]
+    }
+}
+
+/// Every Steam library known to the clients at `roots`: each root itself,
+/// plus the extra libraries listed in its `libraryfolders.vdf`
+fn steam_libraries(roots: &[PathBuf]) -> Vec<PathBuf> {
+    let mut libraries: Vec<PathBuf> = Vec::new();
+
+    for root in roots.iter().filter(|root| root.is_dir()) {
+        let mut found = vec![root.clone()];
+        if let Ok(content) = fs::read_to_string(root.join("steamapps/libraryfolders.vdf")) {
+            found.extend(library_folder_paths(&content));
+        }
+
+        for library in found {
+            // ~/.steam/steam is usually a symlink to one of the other roots
+            let canonical = library.canonicalize().unwrap_or(library);
+            if !libraries.contains(&canonical) {
+                libraries.push(canonical);
+            }
+        }
+    }
+
+    libraries
+}
+
+/// Read the `"path"` entries of a `libraryfolders.vdf` file
+fn library_folder_paths(vdf: &str) -> Vec<PathBuf> {
+    vdf.lines()
+        .filter_map(|line| {
+            let mut fields = line.split('"').filter(|field| !field.trim().is_empty());
+            match (fields.next(), fields.next()) {
+                // Backslashes are escaped in VDF strings
+                (Some("path"), Some(path)) => Some(PathBuf::from(path.replace(r"\\", r"\"))),
+                _ => None,
+            }
+        })
+        .collect()
+}
+
+/// Find the Godot binaries of the Steam app in a library
+fn find_godot_binaries(library: &Path) -> Vec<PathBuf> {
+    let app_dir = library.join("steamapps/common").join(STEAM_APP_DIR);
+
+    let app_bundle = app_dir.join("Godot.app/Contents/MacOS/Godot");
+    if app_bundle.is_file() {
+        return vec![app_bundle];
+    }
+
+    let Ok(entries) = fs::read_dir(&app_dir) else {
+        return Vec::new();
+    };
+    let mut binaries: Vec<PathBuf> = entries
+        .filter_map(|entry| entry.ok().map(|e| e.path()))
+        .filter(|path| path.is_file())
+        .filter(|path| {
+            path.file_name()
+                .and_then(|name| name.to_str())
+                .is_some_and(is_godot_binary_name)
+        })
+        .collect();
+    binaries.sort();
+    binaries
+}
+
+/// Whether a file in the Steam app directory is an editor binary, e.g. "godot.x86_64"
+/// or "godot.windows.opt.tools.64.exe", rather than a data or console wrapper file
+fn is_godot_binary_name(name: &str) -> bool {
+    let name = name.to_lowercase();
+    name.starts_with("godot")
+        && !name.ends_with(".pck")
+        && !name.contains("console")
+        && (cfg!(not(target_os = "windows")) || name.ends_with(".exe"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_library_folder_paths() {
+        let vdf = r#""libraryfolders"
+{
+	"0"
+	{
+		"path"		"/home/me/.local/share/Steam"
+		"label"		""
+		"apps"
+		{
+			"404790"		"123456789"
+		}
+	}
+	"1"
+	{
+		"path"		"D:\\SteamLibrary"
+	}
+}
+"#;
+        assert_eq!(
+            library_folder_paths(vdf),
+            [
+                PathBuf::from("/home/me/.local/share/Steam"),
+                PathBuf::from(r"D:\SteamLibrary")
+            ]
+        );
+    }
+
+    #[test]
+    fn test_steam_libraries_and_binaries() {
+        let dir = tempfile::tempdir().unwrap();
+        let root = dir.path().join("Steam");
+        let extra = dir.path().join("SteamLibrary");
+        let app_dir = extra.join("steamapps/common").join(STEAM_APP_DIR);
+        fs::create_dir_all(root.join("steamapps")).unwrap();
+        fs::create_dir_all(&app_dir).unwrap();
+        fs::write(
+            root.join("steamapps/libraryfolders.vdf"),
+            format!(
+                "\"libraryfolders\"\n{{\n\t\"0\"\n\t{{\n\t\t\"path\"\t\t\"{}\"\n\t}}\n}}\n",
+                extra.display()
+            ),
+        )
+        .unwrap();
+
+        let binary = if cfg!(windows) {
+            "godot.windows.opt.tools.64.exe"
+        } else {
+            "godot.x86_64"
+        };
+        fs::write(app_dir.join(binary), "").unwrap();
+        fs::write(app_dir.join("godot.pck"), "").unwrap();
+
+        let libraries = steam_libraries(&[root.clone(), dir.path().join("missing")]);
+        assert_eq!(
+            libraries,
+            [root.canonicalize().unwrap(), extra.canonicalize().unwrap()]
+        );
+
+        let found: Vec<_> = libraries
+            .iter()
+            .flat_map(|library| find_godot_binaries(library))
+            .collect();
+        assert_eq!(
+            found,
+            [extra
+                .canonicalize()
+                .unwrap()
+                .join("steamapps/common")
+                .join(STEAM_APP_DIR)
+                .join(binary)]
+        );
+    }
+}
@@ -0,0 +1,54 @@
+use anyhow::Result;
+use clap::Args;
+use std::path::Path;
+
+use crate::{config::Config, godot::GodotVersion, installer::Installer, ui};
+
+#[derive(Args)]
+pub struct InfoCommand {
+    /// The Godot version (defaults to the project's pinned or the active version)
+    pub version: Option<String>,
+
+    /// Use the .NET build of the version
+    #[arg(long)]
+    pub dotnet: bool,
+
+    /// Use the standard build even when the default_dotnet setting is on
+    #[arg(long, conflicts_with = "dotnet")]
+    pub no_dotnet: bool,
+}
+
+impl InfoCommand {
+    pub async fn run(self) -> Result<()> {
+        let mut config = Config::new()?;
+        let is_dotnet = config
+            .settings
+            .apply_dotnet_flags(self.dotnet, self.no_dotnet);
+        let installer = Installer::new(config.clone());
+
+        let version = match &self.version {
+            Some(v) => GodotVersion::new(v, is_dotnet)?,
+            None => installer.resolve_version_for(Path::new("."))?,
+        };
+
+        println!("🎮 {}", version.to_marketing_name());
+        ui::info(&format!("Version: {}", version.godot_version_string()));
+        ui::info(&format!("Release tag: {}", version.to_tag()));
+        ui::info(&format!(
+            "Build: {} ({})",
+            if version.is_dotnet {
+                ".NET"
+            } else {
+                "standard"
+            },
+            version.channel()
+        ));
+
+        match installer.get_executable(&version) {
+            Ok(executable) => ui::info(&format!("Installed: {}", executable.display())),
+            Err(_) => ui::info("Installed: no"),
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,148 @@
+use anyhow::{anyhow, Result};
+use clap::Args;
+use serde::Serialize;
+use std::path::{Path, PathBuf};
+
+use crate::{
+    config::Config,
+    godot::GodotVersion,
+    installer::Installer,
+    lockfile::{LockFile, Pin},
+    project::{self, ProjectWalker, PROJECT_FILE},
+    ui,
+};
+
+#[derive(Args)]
+pub struct InspectProjectCommand {
+    /// Path to the Godot project directory
+    #[arg(default_value = ".")]
+    pub path: PathBuf,
+
+    /// Print the report as JSON
+    #[arg(long)]
+    pub json: bool,
+}
+
+#[derive(Debug, Serialize)]
+struct ProjectReport {
+    project_path: PathBuf,
+    project_version: Option<String>,
+    lockfile: Option<PathBuf>,
+    lockfile_version: Option<String>,
+    versions_match: Option<bool>,
+    uses_dotnet: bool,
+    export_presets: usize,
+    gdscript_files: usize,
+    editor_version: Option<String>,
+}
+
+impl InspectProjectCommand {
+    pub async fn run(self) -> Result<()> {
+        let config = Config::new()?;
+        let installer = Installer::new(config);
+
+        let project_file = self.path.join(PROJECT_FILE);
+        if !project_file.exists() {
+            return Err(anyhow!(
+                "No {} found in {}",
+                PROJECT_FILE,
+                self.path.display()
+            ));
+        }
+
+        let report = inspect(&self.path, &installer.list_installed()?)?;
+
+        if self.json {
+            println!("{}", serde_json::to_string_pretty(&report)?);
+            return Ok(());
+        }
+
+        let unknown = || "unknown".to_string();
+        println!("🔎 Project: {}", report.project_path.display());
+        ui::info(&format!(
+            "Required Godot version: {}",
+            report.project_version.clone().unwrap_or_else(unknown)
+        ));
+        match (&report.lockfile, &report.lockfile_version) {
+            (Some(path), Some(version)) => ui::info(&format!(
+                "Lockfile version: {} ({})",
+                version,
+                path.display()
+            )),
+            _ => ui::info("Lockfile version: none"),
+        }
+        match report.versions_match {
+            Some(true) => ui::success("Lockfile matches project.godot"),
+            Some(false) => ui::warning("Lockfile does not match project.godot"),
+            None => {}
+        }
+        ui::info(&format!(
+            "Uses .NET: {}",
+            if report.uses_dotnet { "yes" } else { "no" }
+        ));
+        ui::info(&format!("Export presets: {}", report.export_presets));
+        ui::info(&format!("GDScript files: {}", report.gdscript_files));
+        match &report.editor_version {
+            Some(version) => ui::info(&format!("Opens with installed Godot v{}", version)),
+            None => ui::warning("No installed Godot version can open this project"),
+        }
+
+        Ok(())
+    }
+}
+
+fn inspect(project_dir: &Path, installed: &[GodotVersion]) -> Result<ProjectReport> {
+    let project_version = GodotVersion::from_project_file(&project_dir.join(PROJECT_FILE))?;
+
+    let lockfile_path = LockFile::find(project_dir);
+    let lockfile = lockfile_path.as_deref().map(LockFile::read).transpose()?;
+
+    let walker = ProjectWalker::new(project_dir);
+    let uses_dotnet = project_version.as_ref().is_some_and(|v| v.is_dotnet)
+        || !walker.files_with_extension("csproj")?.is_empty();
+
+    // project.godot only records "major.minor", so that's all we can compare
+    // .NET is reported separately, so only the version is compared
+    let versions_match = match (&project_version, &lockfile) {
+        (Some(project), Some(lock)) => Some(lock.matches_project(&GodotVersion {
+            is_dotnet: lock.pin.version().is_some_and(|v| v.is_dotnet),
+            ..project.clone()
+        })),
+        _ => None,
+    };
+
+    // Prefer the pinned version (or the newest install in the pinned range), then the
+    // newest install of the project's series
+    let editor_version = lockfile
+        .as_ref()
+        .and_then(|lock| lock.pin.resolve(installed))
+        .or_else(|| {
+            let project = project_version.as_ref()?;
+            installed
+                .iter()
+                .rev()
+                .find(|v| {
+                    v.version.major == project.version.major
+                        && v.version.minor == project.version.minor
+                        && v.is_dotnet == uses_dotnet
+                })
+                .cloned()
+        })
+        .map(|v| v.to_string());
+
+    Ok(ProjectReport {
+        project_path: project_dir.to_path_buf(),
+        project_version: project_version
+            .map(|v| format!("{}.{}", v.version.major, v.version.minor)),
+        lockfile: lockfile.as_ref().map(|lock| lock.path.clone()),
+        lockfile_version: lockfile.map(|lock| match lock.pin {
+            Pin::Version(version) => version.to_string(),
+            Pin::Range(range) => range.to_string(),
+        }),
+        versions_match,
+        uses_dotnet,
+        export_presets: project::count_export_presets(project_dir)?,
+        gdscript_files: walker.files_with_extension("gd")?.len(),
+        editor_version,
+    })
+}
@@ -1,9 +1,39 @@
 use anyhow::{anyhow, Result};
 use clap::Args;
+use colored::*;
+use futures_util::{stream, StreamExt};
+use serde::Serialize;
+use std::fmt;
+use std::fs;
+use std::future::Future;
+use std::io::{self, IsTerminal};
+use std::path::{Path, PathBuf};
 
-use crate::{config::Config, github::GitHubClient, godot::GodotVersion, installer::Installer, ui};
+use crate::{
+    config::Config,
+    disk::DiskSpaceChecker,
+    download::part_path,
+    dry_run::DryRunMode,
+    github::{GitHubAsset, GitHubClient, GitHubRelease},
+    godot::{GodotVersion, GodotVersionReq, MacosVersion, Source},
+    hooks::{GlobalHooks, HookEvent},
+    installer::{self, Installer},
+    platform::PlatformTarget,
+    remote,
+    requirements::{self, VersionConstraint},
+    ui::{self, format_size},
+    verify,
+};
 
-#[derive(Args)]
+/// Downloads above this size ask for confirmation in interactive sessions
+const LARGE_DOWNLOAD_BYTES: u64 = 200 * 1024 * 1024;
+
+#[derive(Args, Clone, Default)]
+#[command(after_help = "Examples:\n  \
+    gdenv install 4.2.1\n  \
+    gdenv install --latest --dotnet\n  \
+    gdenv install --from-git-tag $(git describe --tags --abbrev=0)\n  \
+    gdenv install -r requirements.godot.txt")]
 pub struct InstallCommand {
     /// The Godot version to install (e.g., 4.2.1, 4.1.0-stable)
     /// If not provided, reads from .godot-version file
@@ -13,10 +43,36 @@ pub struct InstallCommand {
     #[arg(long)]
     pub dotnet: bool,
 
+    /// Use the standard build even when the default_dotnet setting is on
+    #[arg(long, conflicts_with = "dotnet")]
+    pub no_dotnet: bool,
+
     /// Force reinstall even if version is already installed
-    #[arg(long, short)]
+    #[arg(long, short, alias = "reinstall")]
     pub force: bool,
 
+    /// Skip versions that are already installed (the default), and with --latest
+    /// or --latest-prerelease skip if an installed version is at least as new
+    #[arg(long, alias = "skip-existing", conflicts_with = "force")]
+    pub only_if_newer: bool,
+
+    /// Don't ask for confirmation before large downloads
+    #[arg(long, short = 'y', alias = "assume-yes")]
+    pub yes: bool,
+
+    /// Make sure there's enough free disk space before downloading
+    #[arg(long)]
+    pub check_disk_space: bool,
+
+    /// Install the Godot version named by a git tag such as "engine/4.2.1" or "godot-v4.3"
+    #[arg(long, value_name = "TAG", conflicts_with_all = ["version", "latest", "latest_prerelease"])]
+    pub from_git_tag: Option<String>,
+
+    /// Install every version in a pip-style requirements file (lines like "godot==4.2.1",
+    /// "godot[dotnet]>=4.1,<4.3"), picking the newest release that matches each range
+    #[arg(long, short = 'r', value_name = "FILE", conflicts_with_all = ["version", "from_git_tag", "latest", "latest_prerelease"])]
+    pub requirements: Option<PathBuf>,
+
     /// Install the latest stable release
     #[arg(long, conflicts_with_all = ["version", "latest_prerelease"])]
     pub latest: bool,
@@ -24,18 +80,137 @@ pub struct InstallCommand {
     /// Install the latest prerelease (beta, rc, etc.)
     #[arg(long, conflicts_with_all = ["version", "latest"])]
     pub latest_prerelease: bool,
+
+    /// Download source to try first, falling back to the others (default: the prefer_source setting)
+    #[arg(long, value_enum)]
+    pub prefer_source: Option<Source>,
+
+    /// Only download from this source, without falling back
+    #[arg(long, value_enum, conflicts_with = "prefer_source")]
+    pub only_source: Option<Source>,
+
+    /// Show what would be downloaded and where it would go, without changing anything.
+    /// Exits with 1 if the install would fail.
+    #[arg(long)]
+    pub dry_run: bool,
+
+    /// With --dry-run, print the plan as a single line of JSON for other tools to consume
+    #[arg(long, requires = "dry_run")]
+    pub json: bool,
+
+    /// Install even if the version doesn't support this macOS version
+    #[arg(long)]
+    pub ignore_os_compat: bool,
+
+    /// Install the build for another platform, e.g. "linux.x86_64" for Linux server builds
+    /// made on Windows. It goes next to the other installations, but can't be used or run.
+    #[arg(long, value_name = "SUFFIX", conflicts_with_all = ["requirements", "dry_run"])]
+    pub platform: Option<String>,
+
+    /// Check that the version's archive for every platform is on the download source and
+    /// listed in its checksum file, without installing anything (for mirror maintainers)
+    #[arg(long, conflicts_with_all = ["from_git_tag", "requirements", "latest", "latest_prerelease", "dry_run", "platform"])]
+    pub verify_all_platforms: bool,
+
+    /// With --verify-all-platforms, how many requests to send at once (default: the
+    /// concurrency setting)
+    #[arg(long, value_name = "N", requires = "verify_all_platforms")]
+    pub concurrency: Option<usize>,
+
+    /// Only download and verify the archive into the cache; a later install of the
+    /// version extracts it without downloading again
+    #[arg(long, conflicts_with_all = ["dry_run", "platform", "verify_all_platforms"])]
+    pub no_extract: bool,
+
+    /// Don't check the archive against the release's SHA512-SUMS.txt, e.g. on an
+    /// air-gapped mirror without checksum files
+    #[arg(long)]
+    pub skip_verify: bool,
 }
 
 impl InstallCommand {
-    pub async fn run(self) -> Result<()> {
-        let config = Config::new()?;
-        let github_client = GitHubClient::new(config.github_api_url.clone());
+    /// An install of exactly `version` that doesn't ask for confirmation, for commands
+    /// that install a version on the user's behalf
+    pub fn for_version(version: &GodotVersion) -> Self {
+        Self {
+            version: Some(version.godot_version_string()),
+            dotnet: version.is_dotnet,
+            no_dotnet: !version.is_dotnet,
+            yes: true,
+            ..Default::default()
+        }
+    }
+
+    fn dry_run_mode(&self) -> DryRunMode {
+        DryRunMode::new(self.dry_run, self.json)
+    }
+
+    pub async fn run(mut self) -> Result<()> {
+        if let Some(path) = self.requirements.take() {
+            return self.install_requirements(Config::new()?, &path).await;
+        }
+        if self.verify_all_platforms {
+            return self.verify_all_platforms(Config::new()?).await;
+        }
+        self.run_with(Config::new()?).await
+    }
+
+    pub async fn run_with(self, mut config: Config) -> Result<()> {
+        let dotnet = config
+            .settings
+            .apply_dotnet_flags(self.dotnet, self.no_dotnet);
+        let github_client = GitHubClient::from_config(&config);
         let installer = Installer::new(config.clone());
+        let hooks = GlobalHooks::load(&config)?;
+        let tagged_version = self
+            .from_git_tag
+            .as_deref()
+            .map(GodotVersion::from_git_tag)
+            .transpose()?;
+        let is_dotnet = dotnet || tagged_version.as_ref().is_some_and(|v| v.is_dotnet);
+        let dry_run = self.dry_run_mode();
+        let json = dry_run == DryRunMode::Json;
+
+        let explicit_version = if let Some(version) = &tagged_version {
+            Some(version.godot_version_string())
+        } else if self.latest || self.latest_prerelease {
+            None
+        } else {
+            match &self.version {
+                Some(v) => Some(v.clone()),
+                None => {
+                    // Try to read from .godot-version file
+                    let pinned = self.read_godot_version_file()?;
+                    if GodotVersionReq::is_range(&pinned) {
+                        let range: GodotVersionReq = pinned.parse()?;
+                        let resolved = self
+                            .resolve_range(&range, &installer, &github_client, is_dotnet)
+                            .await?;
+                        Some(resolved.godot_version_string())
+                    } else {
+                        Some(pinned)
+                    }
+                }
+            }
+        };
+
+        // An explicit version that's already installed needs no network access at all
+        if let Some(version_string) = &explicit_version {
+            let requested_version = GodotVersion::new(version_string, is_dotnet)?;
+            let installed = self.platform.is_none() && installer.is_installed(&requested_version);
+            if !self.force && !dry_run.is_dry_run() && installed {
+                ui::info(&format!(
+                    "Godot v{} is already installed, skipping (use --force to reinstall)",
+                    requested_version
+                ));
+                return Ok(());
+            }
+        }
 
         // Fetch available releases from GitHub first (needed for --latest flags)
         // Include prereleases if we're looking for latest prerelease OR if the requested version looks like a prerelease
         let include_prereleases = self.latest_prerelease
-            || self.version.as_ref().is_some_and(|v| {
+            || explicit_version.as_ref().is_some_and(|v| {
                 v.contains("-beta")
                     || v.contains("-rc")
                     || v.contains("-alpha")
@@ -46,34 +221,31 @@ impl InstallCommand {
             .await?;
 
         // Get the version to install
-        let version_string = if self.latest {
-            // Find latest stable release
-            releases
-                .iter()
-                .find(|r| !r.prerelease)
-                .and_then(|r| r.version())
-                .ok_or_else(|| anyhow!("No stable releases found"))?
-        } else if self.latest_prerelease {
-            // Find latest release (including prereleases)
-            releases
-                .first()
-                .and_then(|r| r.version())
-                .ok_or_else(|| anyhow!("No releases found"))?
-        } else {
-            match self.version {
-                Some(v) => v,
-                None => {
-                    // Try to read from .godot-version file
-                    self.read_godot_version_file()?
-                }
+        let version_string = match explicit_version {
+            Some(version) => version,
+            None if self.latest => {
+                // Find latest stable release
+                releases
+                    .iter()
+                    .find(|r| !r.prerelease)
+                    .and_then(|r| r.version())
+                    .ok_or_else(|| anyhow!("No stable releases found"))?
+            }
+            None => {
+                // Find latest release (including prereleases)
+                releases
+                    .first()
+                    .and_then(|r| r.version())
+                    .ok_or_else(|| anyhow!("No releases found"))?
             }
         };
 
         // Parse the requested version
-        let is_dotnet = self.dotnet;
         let requested_version = GodotVersion::new(&version_string, is_dotnet)?;
 
-        if self.latest {
+        if json {
+            // Keep stdout to the plan
+        } else if self.latest {
             ui::info(&format!("Found latest stable version: {}", version_string));
         } else if self.latest_prerelease {
             ui::info(&format!(
@@ -82,13 +254,31 @@ impl InstallCommand {
             ));
         }
 
-        println!("🤖 Installing Godot v{}", requested_version);
+        if self.only_if_newer && (self.latest || self.latest_prerelease) {
+            let newest_installed = installer
+                .list_installed()?
+                .into_iter()
+                .filter(|v| v.is_dotnet == is_dotnet)
+                .max();
+            if let Some(newest) = newest_installed {
+                if newest.version >= requested_version.version {
+                    ui::info(&format!(
+                        "Godot v{} is already installed and up to date, skipping",
+                        newest
+                    ));
+                    return Ok(());
+                }
+            }
+        }
+
+        if !json {
+            println!("🤖 Installing Godot v{}", requested_version);
+        }
 
         // Check if already installed (unless force flag is set)
-        let install_path = config
-            .installations_dir
-            .join(requested_version.installation_name());
-        if install_path.exists() && !self.force {
+        let install_path = config.install_path(&requested_version);
+        let installed = self.platform.is_none() && installer.is_installed(&requested_version);
+        if installed && !self.force && !dry_run.is_dry_run() && !self.no_extract {
             ui::warning(&format!(
                 "Godot v{} is already installed",
                 requested_version
@@ -101,55 +291,242 @@ impl InstallCommand {
         let release = releases
             .iter()
             .find(|r| {
-                if let Some(version) = r.version() {
-                    // Try to match both the normalized version and the original input
-                    version == requested_version.godot_version_string() || version == version_string
-                } else {
-                    false
-                }
+                // Compare parsed tags so "4.3", "4.3.0" and "4.3-stable" all match
+                GodotVersion::from_tag(&r.tag_name)
+                    .is_ok_and(|v| v.version == requested_version.version)
             })
             .ok_or_else(|| anyhow!("Godot version {} not found", requested_version))?;
 
+        if !self.ignore_os_compat && self.platform.is_none() {
+            check_macos_compat(
+                &requested_version,
+                PlatformTarget::current().macos_version(),
+            )?;
+        }
+
+        if let Some(platform) = &self.platform {
+            return self
+                .install_for_platform(
+                    &config,
+                    &github_client,
+                    release,
+                    &requested_version,
+                    platform,
+                )
+                .await;
+        }
+
         // Find the appropriate asset for our platform
         let asset = release
             .find_godot_asset(is_dotnet)
             .ok_or_else(|| anyhow!("No compatible Godot build found for this platform"))?;
 
-        ui::info(&format!("Found: {}", asset.name));
-        ui::info(&format!("Size: {} MB", asset.size / 1024 / 1024));
+        if !json {
+            ui::info(&format!("Found: {}", asset.name));
+            if let Some(warning) = PlatformTarget::current().libc().official_build_warning() {
+                ui::warning(warning);
+            }
+        }
+
+        let sources = match self.only_source {
+            Some(source) => Source::download_order(source, true),
+            None => Source::download_order(
+                self.prefer_source.unwrap_or(config.settings.prefer_source),
+                false,
+            ),
+        };
+
+        if json {
+            let url = source_url(sources[0], asset, &requested_version)?;
+            let size_bytes = match asset.size {
+                0 => remote::url_size(github_client.http_client()?, &url)
+                    .await
+                    .unwrap_or(None),
+                size => Some(size),
+            };
+            let sha512 = remote::release_sha512(github_client.http_client()?, release, &asset.name)
+                .await
+                .unwrap_or(None);
+            let plan = InstallPlan {
+                already_installed: installer.is_installed(&requested_version),
+                ..InstallPlan::new(&config, &requested_version, url, asset, size_bytes, sha512)
+            };
+            println!("{}", serde_json::to_string(&plan)?);
+            return Ok(());
+        }
+
+        if dry_run == DryRunMode::Report {
+            let url = source_url(sources[0], asset, &requested_version)?;
+            let would_succeed = dry_run_report(
+                &config,
+                &github_client,
+                &requested_version,
+                &url,
+                &install_path,
+                self.force,
+            )
+            .await;
+            if !would_succeed {
+                return Err(anyhow!("Godot v{} can't be installed", requested_version));
+            }
+            if installed && !self.force {
+                ui::success(&format!(
+                    "Godot v{} is already installed, nothing to do",
+                    requested_version
+                ));
+                return Ok(());
+            }
+        }
 
         // Create cache directory
         let cache_file = config.cache_dir.join(&asset.name);
 
+        // The dry run report already covered the size and disk space
+        if !dry_run.is_dry_run() {
+            // Fall back to asking the download server if the release doesn't list a size
+            let archive_size = if asset.size > 0 {
+                Some(asset.size)
+            } else {
+                remote::asset_size(github_client.http_client()?, &requested_version)
+                    .await
+                    .unwrap_or(None)
+            };
+
+            if let Some(archive_size) = archive_size {
+                ui::info(&format!(
+                    "Download size: {}, installed size: ~{}",
+                    format_size(archive_size),
+                    format_size(requested_version.estimated_install_size(archive_size))
+                ));
+
+                if self.check_disk_space {
+                    DiskSpaceChecker::new(&requested_version)
+                        .check(&config.installations_dir, archive_size)?;
+                }
+
+                let interactive = io::stdin().is_terminal();
+                if archive_size > LARGE_DOWNLOAD_BYTES
+                    && !cache_file.exists()
+                    && !self.yes
+                    && interactive
+                    && !ui::confirm("This is a large download. Continue?")?
+                {
+                    ui::info("Install cancelled");
+                    return Ok(());
+                }
+            }
+
+            if self.check_disk_space && archive_size.is_none() {
+                ui::warning("Download size is unknown, skipping the disk space check");
+            }
+        }
+
+        if !self.no_extract {
+            run_hook(
+                &hooks,
+                HookEvent::PreInstall,
+                &requested_version,
+                &install_path,
+                dry_run,
+            )?;
+        }
+
         // Download if not cached
         if !cache_file.exists() {
-            ui::info("Downloading Godot...");
-            github_client
-                .download_asset_with_progress(asset, &cache_file)
-                .await?;
+            if !dry_run.is_dry_run() {
+                ui::info("Downloading Godot...");
+            }
+            let urls = self.download_urls(&config, &requested_version, &asset.name, |source| {
+                source_url(source, asset, &requested_version)
+            })?;
+            let downloaded = download_with_fallback(&urls, &cache_file, dry_run, |url| {
+                let (github_client, cache_file, asset) = (&github_client, &cache_file, &asset);
+                async move {
+                    github_client
+                        .download_with_progress(&url, &asset.name, asset.size, cache_file)
+                        .await
+                }
+            })
+            .await;
+            if let Err(e) = downloaded {
+                // A partial download is kept as .part to resume; never leave a file at the archive path
+                let _ = std::fs::remove_file(&cache_file);
+                return Err(e);
+            }
         } else {
             ui::info("Using cached download");
         }
 
+        self.verify_download(&github_client, release, &asset.name, &cache_file, dry_run)
+            .await?;
+
+        if self.no_extract {
+            ui::success(&format!(
+                "Cached Godot v{} at {}",
+                requested_version,
+                cache_file.display()
+            ));
+            ui::info(&format!(
+                "Run 'gdenv install {}' to extract it",
+                requested_version.godot_version_string()
+            ));
+            return Ok(());
+        }
+
+        // Replacing an editor that's open fails halfway through on Windows
+        if install_path.exists() && !self.force {
+            installer
+                .ensure_not_running(&requested_version, &installer::running_executables())
+                .map_err(|e| anyhow!("{}\nUse --force to replace it anyway", e))?;
+        }
+
         // Install the version
         let install_path = installer
-            .install_version_from_archive(&requested_version, &cache_file)
+            .install_version_from_archive(&requested_version, &cache_file, dry_run)
             .await?;
 
+        if !dry_run.is_dry_run() {
+            match installer.executable_size_warning(&requested_version) {
+                Ok(Some(warning)) => ui::warning(&warning),
+                Ok(None) => {}
+                Err(e) => ui::warning(&format!("Could not check the executable: {}", e)),
+            }
+        }
+
+        run_hook(
+            &hooks,
+            HookEvent::PostInstall,
+            &requested_version,
+            &install_path,
+            dry_run,
+        )?;
+
         // Only set as active version if no version is currently active
         if installer.get_active_version()?.is_none() {
-            installer.set_active_version_with_message(&requested_version, false)?;
-            ui::info(&format!(
-                "Set Godot v{} as active version (first installation)",
-                requested_version
-            ));
-        } else {
+            if !dry_run.skip(|| {
+                format!(
+                    "set Godot v{} as the active version (first installation)",
+                    requested_version
+                )
+            }) {
+                installer.set_active_version_with_message(&requested_version, false)?;
+                ui::info(&format!(
+                    "Set Godot v{} as active version (first installation)",
+                    requested_version
+                ));
+            }
+        } else if !dry_run.is_dry_run() {
             ui::info(&format!(
                 "Installation complete. Use 'gdenv use {}' to switch to this version.",
                 requested_version.godot_version_string()
             ));
         }
 
+        if dry_run.is_dry_run() {
+            ui::success(&format!("Godot v{} can be installed", requested_version));
+            return Ok(());
+        }
+
         ui::success(&format!(
             "Successfully installed Godot v{}",
             requested_version
@@ -160,10 +537,309 @@ impl InstallCommand {
         Ok(())
     }
 
-    fn read_godot_version_file(&self) -> Result<String> {
-        use std::fs;
-        use std::path::Path;
+    /// Install the version each line of a requirements file resolves to, with the
+    /// other options of this command
+    async fn install_requirements(self, config: Config, path: &Path) -> Result<()> {
+        let content = fs::read_to_string(path)
+            .map_err(|e| anyhow!("Could not read {}: {}", path.display(), e))?;
+        let constraints = requirements::parse(&content)
+            .map_err(|e| anyhow!("Invalid requirements in {}: {}", path.display(), e))?;
+
+        // Pinned versions install without looking up releases
+        let available = if constraints.iter().all(|c| c.exact.is_some()) {
+            Vec::new()
+        } else {
+            GitHubClient::from_config(&config)
+                .get_godot_releases(false)
+                .await?
+                .iter()
+                .filter_map(|r| GodotVersion::from_tag(&r.tag_name).ok())
+                .collect()
+        };
+
+        let versions = constraints
+            .iter()
+            .map(|constraint| {
+                constraint
+                    .resolve(&available)
+                    .map(|v| requirements::format(&v))
+                    .ok_or_else(|| anyhow!("No Godot release matches {}", constraint.range))
+            })
+            .collect::<Result<Vec<_>>>()?;
+
+        // Every version is pinned by now, so each label parses back to its version
+        let summary = install_many(&versions, true, |version| {
+            let config = config.clone();
+            let options = self.clone();
+            async move {
+                let version = version.parse::<VersionConstraint>()?.exact.unwrap();
+                InstallCommand {
+                    version: Some(version.godot_version_string()),
+                    dotnet: version.is_dotnet,
+                    no_dotnet: !version.is_dotnet,
+                    requirements: None,
+                    ..options
+                }
+                .run_with(config)
+                .await
+            }
+        })
+        .await;
+
+        summary.print(self.dry_run);
 
+        if !summary.failed.is_empty() {
+            return Err(anyhow!(
+                "{} of {} versions failed to install",
+                summary.failed.len(),
+                versions.len()
+            ));
+        }
+        Ok(())
+    }
+
+    /// Install the build of `version` for `platform` into its own directory, leaving
+    /// the active version and hooks alone since the build can't run here
+    async fn install_for_platform(
+        &self,
+        config: &Config,
+        github_client: &GitHubClient,
+        release: &GitHubRelease,
+        version: &GodotVersion,
+        platform: &str,
+    ) -> Result<()> {
+        if !version.platform_suffixes().contains(&platform) {
+            return Err(anyhow!(
+                "Godot v{} has no '{}' builds (available: {})",
+                version,
+                platform,
+                version.platform_suffixes().join(", ")
+            ));
+        }
+
+        let archive_name = version.archive_name_for_platform(platform);
+        let asset = release
+            .assets
+            .iter()
+            .find(|a| a.name == archive_name)
+            .ok_or_else(|| anyhow!("Godot v{} has no {} build", version, platform))?;
+
+        let install_path = config.platform_install_path(version, platform);
+        if install_path.exists() && !self.force {
+            ui::warning(&format!(
+                "Godot v{} for {} is already installed",
+                version, platform
+            ));
+            ui::info("Use --force to reinstall");
+            return Ok(());
+        }
+
+        let cache_file = config.cache_dir.join(&asset.name);
+        if cache_file.exists() {
+            ui::info("Using cached download");
+        } else {
+            ui::info(&format!("Downloading {}...", asset.name));
+            let urls = self.download_urls(config, version, &asset.name, |source| {
+                Ok(match source {
+                    Source::GitHub => asset.browser_download_url.clone(),
+                    _ => version.download_url_for_platform(source, platform),
+                })
+            })?;
+            let downloaded = download_with_fallback(&urls, &cache_file, DryRunMode::Off, |url| {
+                let cache_file = &cache_file;
+                async move {
+                    github_client
+                        .download_with_progress(&url, &asset.name, asset.size, cache_file)
+                        .await
+                }
+            })
+            .await;
+            if let Err(e) = downloaded {
+                let _ = std::fs::remove_file(&cache_file);
+                return Err(e);
+            }
+        }
+
+        self.verify_download(
+            github_client,
+            release,
+            &asset.name,
+            &cache_file,
+            DryRunMode::Off,
+        )
+        .await?;
+        Installer::new(config.clone()).extract_archive_to(&cache_file, &install_path)?;
+        ui::success(&format!(
+            "Installed Godot v{} for {} to {}",
+            version,
+            platform,
+            install_path.display()
+        ));
+        ui::info(&format!(
+            "Executable: {}",
+            install_path
+                .join(version.get_executable_path_for(platform))
+                .display()
+        ));
+        Ok(())
+    }
+
+    /// Check a downloaded (or cached) archive against the release's SHA-512 checksum
+    /// before anything is extracted from it, removing it if it doesn't match
+    async fn verify_download(
+        &self,
+        github_client: &GitHubClient,
+        release: &GitHubRelease,
+        archive_name: &str,
+        archive: &Path,
+        dry_run: DryRunMode,
+    ) -> Result<()> {
+        if self.skip_verify {
+            ui::warning("Skipping checksum verification (--skip-verify)");
+            return Ok(());
+        }
+        if dry_run.skip(|| format!("verify {} against the release's checksum", archive_name)) {
+            return Ok(());
+        }
+
+        let expected =
+            verify::expected_sha512(github_client.http_client()?, release, archive_name).await?;
+        if let Err(e) = verify::verify_archive(archive, &expected) {
+            let _ = std::fs::remove_file(archive);
+            return Err(e);
+        }
+        ui::info("Checksum verified");
+        Ok(())
+    }
+
+    /// The URLs to download an archive from, in the order to try them: each download
+    /// source's, from `source_url`, then the configured mirrors unless `--only-source`
+    /// rules out falling back
+    fn download_urls(
+        &self,
+        config: &Config,
+        version: &GodotVersion,
+        archive_name: &str,
+        source_url: impl Fn(Source) -> Result<String>,
+    ) -> Result<Vec<String>> {
+        let sources = match self.only_source {
+            Some(source) => Source::download_order(source, true),
+            None => Source::download_order(
+                self.prefer_source.unwrap_or(config.settings.prefer_source),
+                false,
+            ),
+        };
+        let mut urls = sources
+            .into_iter()
+            .map(source_url)
+            .collect::<Result<Vec<_>>>()?;
+        if self.only_source.is_none() {
+            urls.extend(
+                config
+                    .settings
+                    .mirrors
+                    .iter()
+                    .map(|mirror| mirror_url(mirror, version, archive_name)),
+            );
+        }
+        Ok(urls)
+    }
+
+    /// Check every platform's archive of the version on the download source, reporting
+    /// which are missing or absent from the checksum file
+    async fn verify_all_platforms(self, mut config: Config) -> Result<()> {
+        let version = match &self.version {
+            Some(version) => version.clone(),
+            None => self.read_godot_version_file()?,
+        };
+        let version = GodotVersion::new(
+            &version,
+            config
+                .settings
+                .apply_dotnet_flags(self.dotnet, self.no_dotnet),
+        )?;
+        let source = self
+            .only_source
+            .or(self.prefer_source)
+            .unwrap_or(config.settings.prefer_source);
+        let concurrency = self.concurrency.unwrap_or(config.settings.concurrency);
+
+        ui::info(&format!(
+            "Verifying the archives of Godot v{} on {}",
+            version, source
+        ));
+        let github_client = GitHubClient::from_config(&config);
+        let checks =
+            check_platforms(github_client.http_client()?, &version, source, concurrency).await?;
+
+        println!(
+            "  {:<22} {:<10} Listed in checksums",
+            "Platform", "Available"
+        );
+        for check in &checks {
+            let available = if check.available {
+                "yes".green()
+            } else {
+                "no".red()
+            };
+            let listed = if check.listed_in_checksums {
+                "yes".green()
+            } else {
+                "no".red()
+            };
+            println!("  {:<22} {:<10} {}", check.platform, available, listed);
+        }
+
+        let failed = checks
+            .iter()
+            .filter(|c| !c.available || !c.listed_in_checksums)
+            .count();
+        if failed > 0 {
+            return Err(anyhow!(
+                "{} of {} platform archives failed verification",
+                failed,
+                checks.len()
+            ));
+        }
+
+        ui::success(&format!("All archives of Godot v{} are in place", version));
+        Ok(())
+    }
+
+    /// Resolve a pinned range to the newest installed version in it, or else the newest
+    /// release in it
+    async fn resolve_range(
+        &self,
+        range: &GodotVersionReq,
+        installer: &Installer,
+        github_client: &GitHubClient,
+        is_dotnet: bool,
+    ) -> Result<GodotVersion> {
+        let installed: Vec<GodotVersion> = installer
+            .list_installed()?
+            .into_iter()
+            .filter(|v| v.is_dotnet == is_dotnet)
+            .collect();
+        if let Some(version) = range.resolve(&installed) {
+            return Ok(version);
+        }
+
+        let available: Vec<GodotVersion> = github_client
+            .get_godot_releases(true)
+            .await?
+            .iter()
+            .filter_map(|r| GodotVersion::from_tag(&r.tag_name).ok())
+            .collect();
+        let version = range
+            .resolve(&available)
+            .ok_or_else(|| anyhow!("No Godot release satisfies '{}'", range))?;
+        if self.dry_run_mode() != DryRunMode::Json {
+            ui::info(&format!("Resolved '{}' to Godot v{}", range, version));
+        }
+        Ok(version)
+    }
+
+    fn read_godot_version_file(&self) -> Result<String> {
         let version_file = Path::new(".godot-version");
 
         if !version_file.exists() {
@@ -180,8 +856,632 @@ impl InstallCommand {
             return Err(anyhow!(".godot-version file is empty"));
         }
 
-        ui::info(&format!("Reading version from .godot-version: {}", version));
+        if self.dry_run_mode() != DryRunMode::Json {
+            ui::info(&format!("Reading version from .godot-version: {}", version));
+        }
 
         Ok(version.to_string())
     }
 }
+
+/// What `install --dry-run --json` would do, precise enough for another tool to
+/// download and unpack the archive itself
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct InstallPlan {
+    pub version: String,
+    pub dotnet: bool,
+    pub url: String,
+    pub archive_name: String,
+    /// `None` if neither the release nor the download server reports it
+    pub size_bytes: Option<u64>,
+    /// From the release's SHA512-SUMS.txt, if it has one
+    pub sha512: Option<String>,
+    pub install_path: PathBuf,
+    pub already_installed: bool,
+}
+
+impl InstallPlan {
+    fn new(
+        config: &Config,
+        version: &GodotVersion,
+        url: String,
+        asset: &GitHubAsset,
+        size_bytes: Option<u64>,
+        sha512: Option<String>,
+    ) -> Self {
+        Self {
+            version: version.godot_version_string(),
+            dotnet: version.is_dotnet,
+            url,
+            archive_name: asset.name.clone(),
+            size_bytes,
+            sha512,
+            install_path: config.install_path(version),
+            already_installed: false,
+        }
+    }
+}
+
+/// The outcome of installing several versions
+#[derive(Debug, Default)]
+pub struct InstallSummary {
+    pub installed: Vec<String>,
+    pub failed: Vec<(String, anyhow::Error)>,
+    /// Versions not attempted because an earlier one failed
+    pub skipped: Vec<String>,
+}
+
+impl InstallSummary {
+    /// Print the outcome; a dry run's successes are versions that would be installed
+    pub fn print(&self, dry_run: bool) {
+        println!(
+            "\n📋 {} {}, {} failed, {} skipped",
+            self.installed.len(),
+            if dry_run {
+                "would install"
+            } else {
+                "installed"
+            },
+            self.failed.len(),
+            self.skipped.len()
+        );
+        for version in &self.installed {
+            ui::success(version);
+        }
+        for (version, e) in &self.failed {
+            ui::error(&format!("{}: {}", version, e));
+        }
+        for version in &self.skipped {
+            ui::info(&format!("{}: skipped", version));
+        }
+    }
+}
+
+/// Install each version in turn with `install`. With `keep_going`, failures are
+/// collected and the rest are still installed; otherwise the first failure stops the batch.
+pub async fn install_many<F, Fut>(
+    versions: &[String],
+    keep_going: bool,
+    mut install: F,
+) -> InstallSummary
+where
+    F: FnMut(String) -> Fut,
+    Fut: Future<Output = Result<()>>,
+{
+    let mut summary = InstallSummary::default();
+    for (i, version) in versions.iter().enumerate() {
+        match install(version.clone()).await {
+            Ok(()) => summary.installed.push(version.clone()),
+            Err(e) => {
+                summary.failed.push((version.clone(), e));
+                if !keep_going {
+                    summary.skipped.extend(versions[i + 1..].iter().cloned());
+                    break;
+                }
+            }
+        }
+    }
+    summary
+}
+
+/// Refuse versions whose macOS builds don't run on the host's macOS (`None` elsewhere)
+fn check_macos_compat(version: &GodotVersion, macos: Option<MacosVersion>) -> Result<()> {
+    let (Some(host), Some(minimum)) = (macos, version.minimum_macos_version()) else {
+        return Ok(());
+    };
+    if host < minimum {
+        return Err(anyhow!(
+            "Godot v{} needs macOS {}.{} or newer, but this Mac runs macOS {}.{}. \
+             Install an older Godot, or pass --ignore-os-compat to install it anyway",
+            version,
+            minimum.0,
+            minimum.1,
+            host.0,
+            host.1
+        ));
+    }
+    Ok(())
+}
+
+/// One platform's archive of a release, as checked by `install --verify-all-platforms`
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct PlatformCheck {
+    platform: String,
+    /// A HEAD request for the archive succeeded
+    available: bool,
+    /// The archive is listed in the release's SHA512-SUMS.txt (it isn't downloaded, so
+    /// its digest isn't checked)
+    listed_in_checksums: bool,
+}
+
+/// Check the archive of every platform the version was built for, sending at most
+/// `concurrency` HEAD requests at once
+async fn check_platforms(
+    client: &reqwest::Client,
+    version: &GodotVersion,
+    source: Source,
+    concurrency: usize,
+) -> Result<Vec<PlatformCheck>> {
+    let platforms = version.platform_suffixes();
+    let urls: Vec<String> = platforms
+        .iter()
+        .map(|suffix| version.download_url_for_platform(source, suffix))
+        .collect();
+
+    // The archives of a release all sit next to the same checksum file
+    let sums = match urls.first() {
+        Some(url) => remote::fetch_sha512_sums(client, url).await?,
+        None => None,
+    };
+
+    let available: Vec<bool> = stream::iter(urls.iter().map(|url| remote::url_exists(client, url)))
+        .buffered(concurrency.max(1))
+        .collect()
+        .await;
+
+    Ok(platforms
+        .iter()
+        .zip(available)
+        .map(|(suffix, available)| {
+            let archive_name = version.archive_name_for_platform(suffix);
+            PlatformCheck {
+                platform: suffix.to_string(),
+                available,
+                listed_in_checksums: sums
+                    .as_deref()
+                    .and_then(|sums| remote::find_checksum(sums, &archive_name))
+                    .is_some(),
+            }
+        })
+        .collect())
+}
+
+/// Where to download the archive from on `source`
+fn source_url(source: Source, asset: &GitHubAsset, version: &GodotVersion) -> Result<String> {
+    match source {
+        Source::GitHub => Ok(asset.browser_download_url.clone()),
+        _ => version.download_url_for_source(source),
+    }
+}
+
+/// Print what installing would do, returning whether every prerequisite is met
+async fn dry_run_report(
+    config: &Config,
+    github_client: &GitHubClient,
+    version: &GodotVersion,
+    url: &str,
+    install_path: &Path,
+    force: bool,
+) -> bool {
+    println!("📝 Dry run, nothing will be downloaded or changed");
+    println!("  Archive URL:       {}", url);
+    println!("  Install directory: {}", install_path.display());
+    println!(
+        "  Already installed: {}",
+        match (install_path.exists(), force) {
+            (false, _) => "no",
+            (true, false) => "yes, would skip (use --force to reinstall)",
+            (true, true) => "yes, would reinstall",
+        }
+    );
+
+    let mut would_succeed = true;
+    let client = match github_client.http_client() {
+        Ok(client) => client,
+        Err(e) => {
+            ui::error(&format!("The archive can't be downloaded: {}", e));
+            return false;
+        }
+    };
+    let archive_size = match remote::url_size(client, url).await {
+        Ok(size) => size,
+        Err(e) => {
+            ui::error(&format!("The archive can't be downloaded: {}", e));
+            would_succeed = false;
+            None
+        }
+    };
+
+    match archive_size {
+        Some(archive_size) => {
+            println!("  Download size:     {}", format_size(archive_size));
+            match DiskSpaceChecker::new(version).check(&config.installations_dir, archive_size) {
+                Ok(()) => println!("  Disk space:        enough"),
+                Err(e) => {
+                    ui::error(&e.to_string());
+                    would_succeed = false;
+                }
+            }
+        }
+        None => println!("  Download size:     unknown, can't check disk space"),
+    }
+
+    would_succeed
+}
+
+/// Run the hook for `event`, or in a dry run say which command would run
+fn run_hook(
+    hooks: &GlobalHooks,
+    event: HookEvent,
+    version: &GodotVersion,
+    install_path: &Path,
+    dry_run: DryRunMode,
+) -> Result<()> {
+    if let Some(command) = hooks.get(event) {
+        if dry_run.skip(|| format!("run the hook `{}`", command)) {
+            return Ok(());
+        }
+    }
+    hooks.run(event, version, install_path)
+}
+
+/// The URL of an archive on a mirror laid out like the GitHub releases:
+/// `<mirror>/<tag>/<archive>`
+fn mirror_url(mirror: &str, version: &GodotVersion, archive_name: &str) -> String {
+    format!(
+        "{}/{}/{}",
+        mirror.trim_end_matches('/'),
+        version.to_tag(),
+        archive_name
+    )
+}
+
+/// Try each source (or URL) in order until one succeeds, returning the one that did.
+/// Before switching to the next source, the partial download of `path` is discarded,
+/// since one server's bytes can't be resumed from another. A dry run only says where
+/// it would download from first.
+async fn download_with_fallback<S, F, Fut>(
+    sources: &[S],
+    path: &Path,
+    dry_run: DryRunMode,
+    mut download: F,
+) -> Result<S>
+where
+    S: Clone + fmt::Display,
+    F: FnMut(S) -> Fut,
+    Fut: Future<Output = Result<()>>,
+{
+    if let Some(first) = sources.first() {
+        if dry_run.skip(|| format!("download {} to {}", first, path.display())) {
+            return Ok(first.clone());
+        }
+    }
+
+    let mut last_error = None;
+    for (i, source) in sources.iter().enumerate() {
+        match download(source.clone()).await {
+            Ok(()) => return Ok(source.clone()),
+            Err(e) => {
+                if sources.len() > 1 {
+                    ui::warning(&format!("Download from {} failed: {}", source, e));
+                }
+                if i + 1 < sources.len() {
+                    let _ = fs::remove_file(part_path(path));
+                }
+                last_error = Some(e);
+            }
+        }
+    }
+    Err(last_error.unwrap_or_else(|| anyhow!("No download source to try")))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::installer::fake_install;
+    use std::fs;
+
+    fn install_command(version: &str) -> InstallCommand {
+        InstallCommand {
+            version: Some(version.to_string()),
+            yes: true,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_check_macos_compat() {
+        let v4 = GodotVersion::new("4.2.1", false).unwrap();
+        assert!(check_macos_compat(&v4, None).is_ok());
+        assert!(check_macos_compat(&v4, Some((10, 15))).is_ok());
+        assert!(check_macos_compat(&v4, Some((14, 4))).is_ok());
+
+        let error = check_macos_compat(&v4, Some((10, 14))).unwrap_err();
+        assert!(error.to_string().contains("needs macOS 10.15"));
+
+        let v3 = GodotVersion::new("3.6", false).unwrap();
+        assert!(check_macos_compat(&v3, Some((10, 14))).is_ok());
+    }
+
+    #[test]
+    fn test_install_plan() {
+        let root = tempfile::tempdir().unwrap();
+        let config = Config::with_data_dir(&root.path().join("gdenv"));
+        let version = GodotVersion::new("4.2.1", true).unwrap();
+        let asset = GitHubAsset {
+            name: "Godot_v4.2.1-stable_mono_linux_x86_64.zip".to_string(),
+            browser_download_url: "https://example.com/godot.zip".to_string(),
+            size: 60_000_000,
+        };
+
+        let plan = InstallPlan::new(
+            &config,
+            &version,
+            asset.browser_download_url.clone(),
+            &asset,
+            Some(asset.size),
+            None,
+        );
+        let json: serde_json::Value = serde_json::to_value(&plan).unwrap();
+        assert_eq!(json["version"], "4.2.1");
+        assert_eq!(json["dotnet"], true);
+        assert_eq!(json["url"], "https://example.com/godot.zip");
+        assert_eq!(json["size_bytes"], 60_000_000);
+        assert_eq!(
+            json["install_path"],
+            config.install_path(&version).to_string_lossy().as_ref()
+        );
+    }
+
+    #[tokio::test]
+    async fn test_install_existing_version_skips_network() {
+        let root = tempfile::tempdir().unwrap();
+        let mut config = Config::with_data_dir(root.path());
+        // Nothing listens here, so any request would fail the install
+        config.github_api_url = "http://127.0.0.1:9".to_string();
+
+        let version = GodotVersion::new("4.2.1", false).unwrap();
+        fake_install(&config, &version, "");
+
+        install_command("4.2.1")
+            .run_with(config.clone())
+            .await
+            .unwrap();
+        install_command("4.2.1-stable")
+            .run_with(config.clone())
+            .await
+            .unwrap();
+
+        // A version that isn't installed does go to the network
+        assert!(install_command("4.3").run_with(config).await.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_download_with_fallback() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("Godot.zip");
+        let attempts = std::cell::RefCell::new(Vec::new());
+        let github_fails = |source| {
+            attempts.borrow_mut().push(source);
+            // GitHub leaves a partial download behind, which TuxFamily mustn't resume
+            assert!(!part_path(&path).exists());
+            if source == Source::GitHub {
+                fs::write(part_path(&path), "partial").unwrap();
+            }
+            async move {
+                match source {
+                    Source::GitHub => Err(anyhow!("blocked")),
+                    Source::TuxFamily => Ok(()),
+                }
+            }
+        };
+
+        let used = download_with_fallback(
+            &Source::download_order(Source::GitHub, false),
+            &path,
+            DryRunMode::Off,
+            github_fails,
+        )
+        .await
+        .unwrap();
+        assert_eq!(used, Source::TuxFamily);
+        assert_eq!(*attempts.borrow(), [Source::GitHub, Source::TuxFamily]);
+
+        // With nowhere to fall back to, the partial download is kept to resume later
+        attempts.borrow_mut().clear();
+        let result = download_with_fallback(
+            &Source::download_order(Source::GitHub, true),
+            &path,
+            DryRunMode::Off,
+            github_fails,
+        )
+        .await;
+        assert!(result.is_err());
+        assert_eq!(*attempts.borrow(), [Source::GitHub]);
+        assert!(part_path(&path).exists());
+
+        // A dry run names the first source without downloading from any
+        attempts.borrow_mut().clear();
+        let used = download_with_fallback(
+            &Source::download_order(Source::GitHub, false),
+            &path,
+            DryRunMode::Report,
+            github_fails,
+        )
+        .await
+        .unwrap();
+        assert_eq!(used, Source::GitHub);
+        assert!(attempts.borrow().is_empty());
+    }
+
+    #[test]
+    fn test_download_urls_end_with_mirrors() {
+        let mut config = Config::with_data_dir(Path::new("/gdenv"));
+        config.settings.mirrors = vec!["https://mirror.example/godot/".to_string()];
+        let version = GodotVersion::new("4.3", false).unwrap();
+        let archive = "Godot_v4.3-stable_linux.x86_64.zip";
+        let source_url = |source: Source| Ok(source.to_string());
+
+        let command = install_command("4.3");
+        assert_eq!(
+            command
+                .download_urls(&config, &version, archive, source_url)
+                .unwrap(),
+            [
+                "github",
+                "tuxfamily",
+                "https://mirror.example/godot/4.3-stable/Godot_v4.3-stable_linux.x86_64.zip"
+            ]
+        );
+
+        let command = InstallCommand {
+            only_source: Some(Source::TuxFamily),
+            ..install_command("4.3")
+        };
+        assert_eq!(
+            command
+                .download_urls(&config, &version, archive, source_url)
+                .unwrap(),
+            ["tuxfamily"]
+        );
+    }
+
+    /// Every file and directory under `dir`
+    fn tree(dir: &Path) -> Vec<PathBuf> {
+        let mut paths = Vec::new();
+        for entry in fs::read_dir(dir).unwrap() {
+            let path = entry.unwrap().path();
+            if path.is_dir() {
+                paths.extend(tree(&path));
+            }
+            paths.push(path);
+        }
+        paths.sort();
+        paths
+    }
+
+    /// A config in `root` whose release list is a fresh cache listing 4.3 with this
+    /// platform's archive at `archive_url`, so the (unreachable) API is never asked
+    fn stub_release_list(root: &Path, archive_url: &str) -> Config {
+        let mut config = Config::with_data_dir(root);
+        config.github_api_url = "http://127.0.0.1:9".to_string();
+        let version = GodotVersion::new("4.3", false).unwrap();
+        let release = GitHubRelease {
+            assets: vec![GitHubAsset {
+                name: version.archive_name().unwrap(),
+                browser_download_url: archive_url.to_string(),
+                size: 1024,
+            }],
+            ..crate::github::fake_release("4.3-stable", &[])
+        };
+        fs::create_dir_all(&config.cache_dir).unwrap();
+        fs::write(
+            config.cache_dir.join(crate::github::RELEASES_CACHE_FILE),
+            serde_json::to_vec(&[release]).unwrap(),
+        )
+        .unwrap();
+        config
+    }
+
+    #[tokio::test]
+    async fn test_dry_run_has_no_side_effects() {
+        use tokio::io::{AsyncReadExt, AsyncWriteExt};
+
+        // Answer the dry run's HEAD request for the archive
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let archive_url = format!("http://{}/Godot.zip", listener.local_addr().unwrap());
+        tokio::spawn(async move {
+            let (mut socket, _) = listener.accept().await.unwrap();
+            let mut buf = [0; 4096];
+            let _ = socket.read(&mut buf).await.unwrap();
+            socket
+                .write_all(b"HTTP/1.1 200 OK\r\ncontent-length: 1024\r\nconnection: close\r\n\r\n")
+                .await
+                .unwrap();
+        });
+
+        let root = tempfile::tempdir().unwrap();
+        let config = stub_release_list(root.path(), &archive_url);
+        let before = tree(root.path());
+
+        let mut command = install_command("4.3");
+        command.dry_run = true;
+        command.run_with(config.clone()).await.unwrap();
+
+        assert_eq!(tree(root.path()), before);
+    }
+
+    #[tokio::test]
+    async fn test_dry_run_reports_installed_versions() {
+        // Nothing answers for the archive, which only the dry run report asks about
+        let root = tempfile::tempdir().unwrap();
+        let config = stub_release_list(root.path(), "http://127.0.0.1:9/Godot.zip");
+        fake_install(&config, &GodotVersion::new("4.3", false).unwrap(), "");
+        let before = tree(root.path());
+
+        let command = InstallCommand {
+            dry_run: true,
+            ..install_command("4.3")
+        };
+        assert!(command.run_with(config.clone()).await.is_err());
+        assert_eq!(tree(root.path()), before);
+
+        // Without --dry-run, the installed version is skipped before any request
+        install_command("4.3").run_with(config).await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_dry_run_json_has_no_side_effects() {
+        // The plan only needs the release list, whose asset has a size and no checksum file
+        let root = tempfile::tempdir().unwrap();
+        let config = stub_release_list(root.path(), "https://example.com/Godot.zip");
+        let before = tree(root.path());
+
+        let command = InstallCommand {
+            dry_run: true,
+            json: true,
+            ..install_command("4.3")
+        };
+        command.run_with(config.clone()).await.unwrap();
+
+        assert_eq!(tree(root.path()), before);
+    }
+
+    #[tokio::test]
+    async fn test_requirements_dry_run_installs_nothing() {
+        let root = tempfile::tempdir().unwrap();
+        let config = stub_release_list(root.path(), "https://example.com/Godot.zip");
+        let requirements = root.path().join("requirements.godot.txt");
+        fs::write(&requirements, "godot==4.3\n").unwrap();
+        let before = tree(root.path());
+
+        let command = InstallCommand {
+            dry_run: true,
+            json: true,
+            ..Default::default()
+        };
+        command
+            .install_requirements(config.clone(), &requirements)
+            .await
+            .unwrap();
+
+        assert_eq!(tree(root.path()), before);
+        assert!(!config
+            .install_path(&GodotVersion::new("4.3", false).unwrap())
+            .exists());
+    }
+
+    #[tokio::test]
+    async fn test_install_many() {
+        let versions: Vec<String> = ["4.2.1", "4.9.9", "4.3"].map(String::from).to_vec();
+        let not_found = |version: String| async move {
+            if version == "4.9.9" {
+                Err(anyhow!("Download failed: 404 Not Found"))
+            } else {
+                Ok(())
+            }
+        };
+
+        let summary = install_many(&versions, true, not_found).await;
+        assert_eq!(summary.installed, ["4.2.1", "4.3"]);
+        assert_eq!(summary.failed.len(), 1);
+        assert_eq!(summary.failed[0].0, "4.9.9");
+        assert!(summary.failed[0].1.to_string().contains("404"));
+        assert!(summary.skipped.is_empty());
+
+        let summary = install_many(&versions, false, not_found).await;
+        assert_eq!(summary.installed, ["4.2.1"]);
+        assert_eq!(summary.failed.len(), 1);
+        assert_eq!(summary.skipped, ["4.3"]);
+    }
+}
@@ -1,26 +1,47 @@
 use anyhow::Result;
 use clap::Args;
 use colored::*;
+use serde::Serialize;
 
-use crate::{config::Config, installer::Installer, ui};
+use crate::{config::Config, godot::GodotVersion, installer::Installer, ui};
 
 #[derive(Args)]
 pub struct InstalledCommand {
     /// Show the path to each installation
     #[arg(long, short)]
     pub path: bool,
+
+    /// Print the installed versions as JSON
+    #[arg(long, conflicts_with = "path")]
+    pub json: bool,
+}
+
+/// An installed version, as printed by `installed --json`
+#[derive(Debug, PartialEq, Eq, Serialize)]
+struct InstalledEntry {
+    version: String,
+    is_dotnet: bool,
+    active: bool,
 }
 
 impl InstalledCommand {
     pub async fn run(self) -> Result<()> {
         let config = Config::new()?;
         let installer = Installer::new(config.clone());
-        let installed = installer.list_installed()?;
+        let mut installed = installer.list_installed()?;
+        installed.reverse();
         let active_version = installer.get_active_version()?;
 
+        if self.json {
+            let entries = installed_entries(&installed, active_version.as_ref());
+            println!("{}", serde_json::to_string_pretty(&entries)?);
+            return Ok(());
+        }
+
         println!("📦 Installed Godot versions:");
 
-        if installed.is_empty() {
+        let platform_builds = installer.list_platform_builds()?;
+        if installed.is_empty() && platform_builds.is_empty() {
             ui::warning("No Godot versions installed");
             ui::info("Use 'gdenv install <version>' to install a version");
             return Ok(());
@@ -40,13 +61,30 @@ impl InstalledCommand {
             };
 
             if self.path {
-                let install_path = config.installations_dir.join(version.installation_name());
+                let install_path = config.install_path(version);
                 println!("  {} {} -> {}", marker, version_str, install_path.display());
             } else {
                 println!("  {} {}", marker, version_str);
             }
         }
 
+        if !platform_builds.is_empty() {
+            println!("\n🧳 Builds for other platforms:");
+            for (version, platform) in &platform_builds {
+                if self.path {
+                    let install_path = config.platform_install_path(version, platform);
+                    println!(
+                        "    {} ({}) -> {}",
+                        version,
+                        platform,
+                        install_path.display()
+                    );
+                } else {
+                    println!("    {} ({})", version, platform);
+                }
+            }
+        }
+
         if let Some(active) = active_version {
             println!("\n★ = active version ({})", active.to_string().green());
         } else {
@@ -56,3 +94,42 @@ impl InstalledCommand {
         Ok(())
     }
 }
+
+fn installed_entries(
+    installed: &[GodotVersion],
+    active_version: Option<&GodotVersion>,
+) -> Vec<InstalledEntry> {
+    installed
+        .iter()
+        .map(|version| InstalledEntry {
+            version: version.godot_version_string(),
+            is_dotnet: version.is_dotnet,
+            active: active_version == Some(version),
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_installed_entries() {
+        let active = GodotVersion::new("4.2.1", true).unwrap();
+        let installed = [
+            GodotVersion::new("4.3", false).unwrap(),
+            active.clone(),
+            GodotVersion::new("4.2.1", false).unwrap(),
+        ];
+
+        let json = serde_json::to_value(installed_entries(&installed, Some(&active))).unwrap();
+        assert_eq!(
+            json,
+            serde_json::json!([
+                {"version": "4.3.0", "is_dotnet": false, "active": false},
+                {"version": "4.2.1", "is_dotnet": true, "active": true},
+                {"version": "4.2.1", "is_dotnet": false, "active": false},
+            ])
+        );
+    }
+}
@@ -0,0 +1,313 @@
+use anyhow::{anyhow, Result};
+use clap::Args;
+use colored::*;
+use semver::Version;
+use std::path::{Path, PathBuf};
+
+use crate::{
+    godot::GodotVersion,
+    lockfile::{LockFile, Pin, LOCKFILE_NAME},
+    manifest::{Manifest, MANIFEST_NAME},
+    project::{self, ProjectWalker, PROJECT_FILE},
+};
+
+#[derive(Args)]
+pub struct LintLockfileCommand {
+    /// Path to the Godot project directory
+    #[arg(default_value = ".")]
+    pub path: PathBuf,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Severity {
+    Warning,
+    Error,
+}
+
+#[derive(Debug)]
+struct Finding {
+    severity: Severity,
+    rule: &'static str,
+    message: String,
+    help: String,
+}
+
+impl LintLockfileCommand {
+    pub async fn run(self) -> Result<()> {
+        let findings = lint(&self.path)?;
+
+        for finding in &findings {
+            let label = match finding.severity {
+                Severity::Warning => "warning:".yellow().bold(),
+                Severity::Error => "error:".red().bold(),
+            };
+            println!("{} {} [{}]", label, finding.message, finding.rule);
+            println!("  {} {}", "help:".bold(), finding.help);
+        }
+
+        let errors = findings
+            .iter()
+            .filter(|f| f.severity == Severity::Error)
+            .count();
+        let warnings = findings.len() - errors;
+
+        if findings.is_empty() {
+            println!("No problems found in {}", LOCKFILE_NAME);
+        } else {
+            println!("\n{} error(s), {} warning(s)", errors, warnings);
+        }
+
+        if errors > 0 {
+            std::process::exit(1);
+        }
+
+        Ok(())
+    }
+}
+
+/// Check a project's lockfile (and `gdenv.toml`, if any) for common mistakes
+fn lint(project_dir: &Path) -> Result<Vec<Finding>> {
+    let lockfile_path = LockFile::find(project_dir).ok_or_else(|| {
+        anyhow!(
+            "No {} found in {} or its parents",
+            LOCKFILE_NAME,
+            project_dir.display()
+        )
+    })?;
+    let pin = LockFile::read(&lockfile_path)?.pin;
+    let manifest = Manifest::load(project_dir)?.unwrap_or_default();
+
+    // Projects with export presets are treated as shipping projects
+    let is_shipping = project::count_export_presets(project_dir)? > 0;
+
+    let mut findings = Vec::new();
+
+    // Semver ranges only admit prereleases when one of their bounds is a prerelease
+    let prerelease = match &pin {
+        Pin::Version(version) => version.is_prerelease(),
+        Pin::Range(range) => range.req.comparators.iter().any(|c| !c.pre.is_empty()),
+    };
+    if is_shipping && prerelease {
+        findings.push(Finding {
+            severity: Severity::Warning,
+            rule: "prerelease-in-shipping",
+            message: format!(
+                "{} pins {}{} in a project with export presets",
+                LOCKFILE_NAME,
+                if pin.version().is_some() {
+                    "prerelease "
+                } else {
+                    "a range admitting prereleases, "
+                },
+                pin
+            ),
+            help: "pin a stable release before shipping (see 'gdenv list')".to_string(),
+        });
+    }
+
+    // Ranges don't say whether they're for .NET
+    if let (Some(uses_csharp), Some(locked)) = (uses_csharp(project_dir)?, pin.version()) {
+        if uses_csharp != locked.is_dotnet {
+            let (project_kind, fix) = if uses_csharp {
+                ("uses C#", "add the -dotnet suffix to")
+            } else {
+                ("doesn't use C#", "remove the -dotnet suffix from")
+            };
+            findings.push(Finding {
+                severity: Severity::Error,
+                rule: "mixed-dotnet",
+                message: format!(
+                    "the project {}, but {} pins v{}",
+                    project_kind, LOCKFILE_NAME, locked
+                ),
+                help: format!("{} the version in {}", fix, LOCKFILE_NAME),
+            });
+        }
+    }
+
+    let is_dotnet = pin.version().is_some_and(|v| v.is_dotnet);
+    let known_broken = manifest
+        .lint
+        .known_broken
+        .iter()
+        .filter_map(|v| GodotVersion::new(v, is_dotnet).ok())
+        .find(|broken| pin.allows(broken));
+    if let Some(broken) = known_broken {
+        findings.push(Finding {
+            severity: Severity::Error,
+            rule: "known-broken",
+            message: format!(
+                "v{} {}is listed as broken in {}",
+                broken.godot_version_string(),
+                if pin.version().is_some() {
+                    String::new()
+                } else {
+                    format!("(admitted by {}) ", pin)
+                },
+                MANIFEST_NAME
+            ),
+            help: "pin a different version".to_string(),
+        });
+    }
+
+    if let (Some(range), Some((major, minor))) = (&manifest.godot, pinned_series(&pin)) {
+        let (req, warnings) = GodotVersion::parse_semver_requirement(range)
+            .map_err(|e| anyhow!("Invalid godot range in {}: {}", MANIFEST_NAME, e))?;
+
+        for warning in warnings {
+            findings.push(Finding {
+                severity: Severity::Warning,
+                rule: "major-version-not-pinned",
+                message: format!("godot = \"{}\" in {} {}", range, MANIFEST_NAME, warning),
+                help: format!("pin the major version, e.g. \"^{}.{}\"", major, minor),
+            });
+        }
+
+        // A range that also accepts the next minor release lets the engine change under the project
+        let next_minor = Version::new(major, minor + 1, 0);
+        if is_shipping && req.matches(&next_minor) {
+            findings.push(Finding {
+                severity: Severity::Warning,
+                rule: "broad-version-range",
+                message: format!(
+                    "godot = \"{}\" in {} also accepts v{}",
+                    range, MANIFEST_NAME, next_minor
+                ),
+                help: format!(
+                    "narrow the range to patch releases, e.g. \"~{}.{}\"",
+                    major, minor
+                ),
+            });
+        }
+    }
+
+    findings.retain(|f| !manifest.lint.ignore.iter().any(|rule| rule == f.rule));
+    Ok(findings)
+}
+
+/// The "major.minor" series a pin is for: the pinned version's, or that of a range's
+/// first bound
+fn pinned_series(pin: &Pin) -> Option<(u64, u64)> {
+    match pin {
+        Pin::Version(version) => Some((version.version.major, version.version.minor)),
+        Pin::Range(range) => range
+            .req
+            .comparators
+            .first()
+            .map(|c| (c.major, c.minor.unwrap_or(0))),
+    }
+}
+
+/// Whether the project uses C#, or `None` if there's no `project.godot` to tell
+fn uses_csharp(project_dir: &Path) -> Result<Option<bool>> {
+    let project_file = project_dir.join(PROJECT_FILE);
+    if !project_file.exists() {
+        return Ok(None);
+    }
+
+    let has_feature = project::read_features(&project_file)?
+        .iter()
+        .any(|f| f == "C#");
+    let has_scripts = !ProjectWalker::new(project_dir)
+        .files_with_extension("cs")?
+        .is_empty();
+
+    Ok(Some(has_feature || has_scripts))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+
+    fn rules(findings: &[Finding]) -> Vec<&'static str> {
+        findings.iter().map(|f| f.rule).collect()
+    }
+
+    #[test]
+    fn test_lint_findings() {
+        let dir = tempfile::tempdir().unwrap();
+        let root = dir.path();
+        fs::write(
+            root.join(PROJECT_FILE),
+            "[application]\nconfig/features=PackedStringArray(\"4.3\", \"C#\")\n",
+        )
+        .unwrap();
+        fs::write(
+            root.join("export_presets.cfg"),
+            "[preset.0]\nname=\"Linux\"\n\n[preset.0.options]\n",
+        )
+        .unwrap();
+        fs::write(root.join(LOCKFILE_NAME), "4.3-rc1\n").unwrap();
+        fs::write(
+            root.join(MANIFEST_NAME),
+            "godot = \"^4.3.0-rc1\"\n\n[lint]\nknown_broken = [\"4.3-rc1\"]\n",
+        )
+        .unwrap();
+
+        let findings = lint(root).unwrap();
+        assert_eq!(
+            rules(&findings),
+            [
+                "prerelease-in-shipping",
+                "mixed-dotnet",
+                "known-broken",
+                "broad-version-range"
+            ]
+        );
+
+        fs::write(root.join(LOCKFILE_NAME), "4.3.1-dotnet\n").unwrap();
+        fs::write(
+            root.join(MANIFEST_NAME),
+            "godot = \"~4.3\"\n\n[lint]\nignore = [\"prerelease-in-shipping\"]\n",
+        )
+        .unwrap();
+        assert!(lint(root).unwrap().is_empty());
+
+        fs::write(root.join(MANIFEST_NAME), "godot = \">=4.3\"\n").unwrap();
+        assert_eq!(
+            rules(&lint(root).unwrap()),
+            ["major-version-not-pinned", "broad-version-range"]
+        );
+    }
+
+    #[test]
+    fn test_lint_ignore() {
+        let dir = tempfile::tempdir().unwrap();
+        let root = dir.path();
+        fs::write(root.join("export_presets.cfg"), "[preset.0]\n").unwrap();
+        fs::write(root.join(LOCKFILE_NAME), "4.4-beta1\n").unwrap();
+
+        assert_eq!(rules(&lint(root).unwrap()), ["prerelease-in-shipping"]);
+
+        fs::write(
+            root.join(MANIFEST_NAME),
+            "[lint]\nignore = [\"prerelease-in-shipping\"]\n",
+        )
+        .unwrap();
+        assert!(lint(root).unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_lint_range_pin() {
+        let dir = tempfile::tempdir().unwrap();
+        let root = dir.path();
+        fs::write(root.join("export_presets.cfg"), "[preset.0]\n").unwrap();
+        fs::write(root.join(LOCKFILE_NAME), ">=4.3.0-rc1, <4.4\n").unwrap();
+        fs::write(
+            root.join(MANIFEST_NAME),
+            "godot = \">=4.3\"\n\n[lint]\nknown_broken = [\"4.3.1\"]\n",
+        )
+        .unwrap();
+
+        assert_eq!(
+            rules(&lint(root).unwrap()),
+            [
+                "prerelease-in-shipping",
+                "known-broken",
+                "major-version-not-pinned",
+                "broad-version-range"
+            ]
+        );
+    }
+}
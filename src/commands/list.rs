@@ -1,60 +1,637 @@
-use anyhow::Result;
-use clap::Args;
+use anyhow::{anyhow, Result};
+use clap::{Args, ValueEnum};
 use colored::*;
+use futures_util::{stream, StreamExt};
+use serde::Serialize;
 
-use crate::{config::Config, github::GitHubClient, ui};
+use crate::{
+    catalog::VersionCatalog,
+    config::{ChannelPreference, Config},
+    github::{GitHubClient, GitHubRelease},
+    godot::GodotVersion,
+    installer::{InstallSource, Installer},
+    remote, ui,
+};
 
 #[derive(Args)]
 pub struct ListCommand {
     /// Include pre-release versions (beta, rc, etc.)
     #[arg(long)]
     pub include_prereleases: bool,
+
+    /// Mark which of the available versions are installed
+    #[arg(long)]
+    pub installed_status: bool,
+
+    /// List installed versions with their size on disk instead of available versions
+    #[arg(long, conflicts_with_all = ["include_prereleases", "installed_status"])]
+    pub installed_size: bool,
+
+    /// With --installed-size, sort from largest to smallest
+    #[arg(long, requires = "installed_size")]
+    pub sort_by_size: bool,
+
+    /// With --installed-size, show only the N largest installations
+    #[arg(long, value_name = "N", requires = "installed_size")]
+    pub top: Option<usize>,
+
+    /// List installed versions grouped by where they came from (managed, adopted or Steam)
+    #[arg(long, conflicts_with_all = ["include_prereleases", "installed_status", "installed_size"])]
+    pub by_source: bool,
+
+    /// Only list versions with a build for this platform suffix, e.g. "linux.x86_64",
+    /// which needn't be this platform (install one with `install --platform`)
+    #[arg(long, value_name = "SUFFIX", conflicts_with_all = ["installed_size", "by_source"])]
+    pub available_for: Option<String>,
+
+    /// Print all available versions as JSON
+    #[arg(long)]
+    pub json: bool,
+
+    /// Print the available versions in a format other tools understand
+    #[arg(long, value_enum, conflicts_with_all = ["json", "installed_status", "installed_size", "by_source"])]
+    pub format: Option<ListFormat>,
+
+    /// With --format, also include the .NET build of each version
+    #[arg(long, requires = "format")]
+    pub dotnet: bool,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum ListFormat {
+    /// A Nix file with a `stdenv.mkDerivation` per version, fetching this platform's archive
+    Nix,
+}
+
+/// A release archive for this platform with its SHA-512, as written by `list --format nix`
+struct NixArchive {
+    version: GodotVersion,
+    url: String,
+    sha512: Option<String>,
+}
+
+/// An installed version and its size on disk, as shown by `list --installed-size`
+#[derive(Debug, Serialize)]
+struct InstalledSizeEntry {
+    version: String,
+    dotnet: bool,
+    size_bytes: u64,
+}
+
+/// An installed version and where it came from, as shown by `list --by-source`
+#[derive(Debug, Serialize)]
+struct InstalledSourceEntry {
+    version: String,
+    dotnet: bool,
+    source: InstallSource,
+}
+
+/// A version available for download, as shown by `list`
+#[derive(Debug, Serialize)]
+struct RemoteEntry {
+    version: String,
+    prerelease: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    installed: Option<bool>,
 }
 
 impl ListCommand {
     pub async fn run(self) -> Result<()> {
         let config = Config::new()?;
+        if self.installed_size {
+            return self.list_installed_sizes(&config);
+        }
+        if self.by_source {
+            return self.list_installed_sources(&config);
+        }
         self.list_available_versions(&config).await
     }
 
+    fn list_installed_sizes(&self, config: &Config) -> Result<()> {
+        let installer = Installer::new(config.clone());
+        let entries = installed_size_entries(
+            &installer,
+            self.sort_by_size || self.top.is_some(),
+            self.top,
+        )?;
+
+        if self.json {
+            println!("{}", serde_json::to_string_pretty(&entries)?);
+            return Ok(());
+        }
+
+        println!("📦 Installed Godot versions by size:");
+
+        if entries.is_empty() {
+            ui::warning("No Godot versions installed");
+            return Ok(());
+        }
+
+        for entry in &entries {
+            let dotnet = if entry.dotnet { " (.NET)" } else { "" };
+            println!(
+                "  {:>10}  {}{}",
+                ui::format_size(entry.size_bytes),
+                entry.version,
+                dotnet
+            );
+        }
+
+        let total: u64 = entries.iter().map(|e| e.size_bytes).sum();
+        println!("\n  {:>10}  total", ui::format_size(total));
+
+        Ok(())
+    }
+
+    fn list_installed_sources(&self, config: &Config) -> Result<()> {
+        let entries = installed_source_entries(&Installer::new(config.clone()))?;
+
+        if self.json {
+            println!("{}", serde_json::to_string_pretty(&entries)?);
+            return Ok(());
+        }
+
+        println!("📦 Installed Godot versions by source:");
+
+        if entries.is_empty() {
+            ui::warning("No Godot versions installed");
+            return Ok(());
+        }
+
+        let mut current = None;
+        for entry in &entries {
+            if current != Some(entry.source) {
+                println!("\n  {}:", entry.source.to_string().bold());
+                current = Some(entry.source);
+            }
+            let dotnet = if entry.dotnet { " (.NET)" } else { "" };
+            println!("    {}{}", entry.version, dotnet);
+        }
+
+        if entries.iter().any(|e| e.source != InstallSource::Managed) {
+            ui::info("Only managed installations are upgraded by gdenv");
+        }
+
+        Ok(())
+    }
+
     async fn list_available_versions(&self, config: &Config) -> Result<()> {
-        ui::info("Fetching available Godot versions...");
+        if !self.json {
+            ui::info("Fetching available Godot versions...");
+        }
+
+        let include_prereleases =
+            self.include_prereleases || config.settings.channel == ChannelPreference::Prerelease;
 
-        let github_client = GitHubClient::new(config.github_api_url.clone());
+        let github_client = GitHubClient::from_config(config);
         let releases = github_client
-            .get_godot_releases(self.include_prereleases)
+            .get_godot_releases(include_prereleases)
             .await?;
 
-        println!("\n📋 Available Godot versions:");
+        let releases = match &self.available_for {
+            Some(platform) => releases_for_platform(releases, platform)?,
+            None => releases,
+        };
+
+        if let Some(ListFormat::Nix) = self.format {
+            let archives = nix_archives(&github_client, &releases, self.dotnet, config).await?;
+            print!(
+                "{}",
+                nix_expression(&archives, GodotVersion::get_platform_suffix()?)
+            );
+            return Ok(());
+        }
+
+        let installed = if self.installed_status {
+            Some(Installer::new(config.clone()).list_installed()?)
+        } else {
+            None
+        };
+        let entries = remote_entries(&releases, installed.as_deref());
+
+        if self.json {
+            println!("{}", serde_json::to_string_pretty(&entries)?);
+            return Ok(());
+        }
 
-        if releases.is_empty() {
+        match &self.available_for {
+            Some(platform) => println!("\n📋 Godot versions available for {}:", platform),
+            None => println!("\n📋 Available Godot versions:"),
+        }
+
+        if entries.is_empty() {
             ui::warning("No releases found");
             return Ok(());
         }
 
-        for release in releases.iter().take(20) {
+        for entry in entries.iter().take(20) {
             // Show only latest 20
-            if let Some(version) = release.version() {
-                let status = if release.prerelease {
-                    " (prerelease)".yellow()
-                } else {
-                    " (stable)".green()
-                };
-
-                println!("  • {}{}", version, status);
-            }
+            let status = if entry.prerelease {
+                " (prerelease)".yellow()
+            } else {
+                " (stable)".green()
+            };
+            let installed = match entry.installed {
+                Some(true) => " ✓ installed".green(),
+                Some(false) | None => "".normal(),
+            };
+
+            println!("  • {}{}{}", entry.version, status, installed);
         }
 
-        if releases.len() > 20 {
-            ui::info(&format!("... and {} more versions", releases.len() - 20));
+        if entries.len() > 20 {
+            ui::info(&format!("... and {} more versions", entries.len() - 20));
         }
 
-        if !self.include_prereleases {
+        if !include_prereleases {
             ui::info("Use --include-prereleases to see beta/rc versions");
         }
 
-        ui::info("Use 'gdenv installed' to see installed versions");
+        if !self.installed_status {
+            ui::info("Use --installed-status to mark installed versions");
+        }
 
         Ok(())
     }
 }
+
+/// Keep the releases with a standard build for `platform`
+fn releases_for_platform(
+    releases: Vec<GitHubRelease>,
+    platform: &str,
+) -> Result<Vec<GitHubRelease>> {
+    let known = GodotVersion::all_platform_suffixes()
+        .iter()
+        .chain(GodotVersion::all_legacy_platform_suffixes())
+        .any(|suffix| *suffix == platform);
+    if !known {
+        return Err(anyhow!(
+            "Unknown platform '{}' (available: {}, {})",
+            platform,
+            GodotVersion::all_platform_suffixes().join(", "),
+            GodotVersion::all_legacy_platform_suffixes().join(", ")
+        ));
+    }
+
+    let catalog = VersionCatalog::from_releases(&releases);
+    let available: Vec<&GodotVersion> = catalog
+        .filter_by_platform(platform)
+        .into_iter()
+        .map(|entry| &entry.version)
+        .collect();
+
+    Ok(releases
+        .iter()
+        .filter(|release| {
+            GodotVersion::from_tag(&release.tag_name).is_ok_and(|v| available.contains(&&v))
+        })
+        .cloned()
+        .collect())
+}
+
+/// Find this platform's archive of every release (and its .NET build with `dotnet`),
+/// looking up each release's checksum file a few at a time
+async fn nix_archives(
+    github_client: &GitHubClient,
+    releases: &[GitHubRelease],
+    dotnet: bool,
+    config: &Config,
+) -> Result<Vec<NixArchive>> {
+    let flavors: &[bool] = if dotnet { &[false, true] } else { &[false] };
+    let client = github_client.http_client()?;
+
+    let per_release = stream::iter(releases.iter().map(|release| async move {
+        let Ok(version) = GodotVersion::from_tag(&release.tag_name) else {
+            return Ok(Vec::new());
+        };
+        let assets: Vec<_> = flavors
+            .iter()
+            .filter_map(|&is_dotnet| Some((is_dotnet, release.find_godot_asset(is_dotnet)?)))
+            .collect();
+
+        // Both flavors' archives sit next to the same checksum file
+        let sums = match assets.first() {
+            Some((_, asset)) => {
+                remote::fetch_sha512_sums(client, &asset.browser_download_url).await?
+            }
+            None => None,
+        };
+
+        Ok::<_, anyhow::Error>(
+            assets
+                .into_iter()
+                .map(|(is_dotnet, asset)| NixArchive {
+                    version: GodotVersion {
+                        is_dotnet,
+                        ..version.clone()
+                    },
+                    url: asset.browser_download_url.clone(),
+                    sha512: sums
+                        .as_deref()
+                        .and_then(|sums| remote::find_checksum(sums, &asset.name)),
+                })
+                .collect(),
+        )
+    }))
+    .buffered(config.settings.concurrency.max(1))
+    .collect::<Vec<_>>()
+    .await;
+
+    let mut archives = Vec::new();
+    for release_archives in per_release {
+        archives.extend(release_archives?);
+    }
+    Ok(archives)
+}
+
+/// Write a Nix file with one `stdenv.mkDerivation` per archive, keyed by the canonical
+/// version (e.g. "4.2.1-dotnet"). Archives without a published checksum get
+/// `lib.fakeHash`, so Nix reports the real hash on the first build.
+fn nix_expression(archives: &[NixArchive], platform_suffix: &'static str) -> String {
+    let mut nix = String::from("{ pkgs ? import <nixpkgs> { } }:\n\n{\n");
+
+    for archive in archives {
+        let hash = match &archive.sha512 {
+            Some(sha512) => format!("sha512 = \"{}\";", sha512),
+            None => "hash = pkgs.lib.fakeHash;".to_string(),
+        };
+        nix.push_str(&format!(
+            r#"  "{name}" = pkgs.stdenv.mkDerivation {{
+    pname = "godot{flavor}";
+    version = "{version}";
+    src = pkgs.fetchurl {{
+      url = "{url}";
+      {hash}
+    }};
+    nativeBuildInputs = [ pkgs.unzip ];
+    sourceRoot = ".";
+    installPhase = ''
+      mkdir -p $out/share/godot $out/bin
+      cp -r . $out/share/godot
+      ln -s "$out/share/godot/{executable}" $out/bin/godot
+    '';
+  }};
+"#,
+            name = archive.version.canonical_string(),
+            flavor = if archive.version.is_dotnet {
+                "-dotnet"
+            } else {
+                ""
+            },
+            version = archive.version.godot_version_string(),
+            url = archive.url,
+            hash = hash,
+            executable = archive.version.get_executable_path_for(
+                archive
+                    .version
+                    .platform_suffix_for(platform_suffix)
+                    .unwrap_or(platform_suffix)
+            ),
+        ));
+    }
+
+    nix.push_str("}\n");
+    nix
+}
+
+/// Build the listing of remote releases, marking installed ones when `installed` is given.
+/// A release counts as installed if either its standard or .NET build is.
+fn remote_entries(
+    releases: &[GitHubRelease],
+    installed: Option<&[GodotVersion]>,
+) -> Vec<RemoteEntry> {
+    releases
+        .iter()
+        .filter_map(|release| {
+            let version = release.version()?;
+            let installed = installed.map(|installed| {
+                GodotVersion::new(&version, false)
+                    .is_ok_and(|remote| installed.iter().any(|v| v.version == remote.version))
+            });
+
+            Some(RemoteEntry {
+                version,
+                prerelease: release.prerelease,
+                installed,
+            })
+        })
+        .collect()
+}
+
+/// List installed versions with their sizes, largest first with `sort_by_size`
+/// (which `top` implies)
+fn installed_size_entries(
+    installer: &Installer,
+    sort_by_size: bool,
+    top: Option<usize>,
+) -> Result<Vec<InstalledSizeEntry>> {
+    let mut entries = installer
+        .list_installed()?
+        .into_iter()
+        .map(|version| {
+            Ok(InstalledSizeEntry {
+                size_bytes: installer.install_size_bytes(&version)?,
+                version: version.godot_version_string(),
+                dotnet: version.is_dotnet,
+            })
+        })
+        .collect::<Result<Vec<_>>>()?;
+
+    if sort_by_size {
+        entries.sort_by_key(|e| std::cmp::Reverse(e.size_bytes));
+    }
+    if let Some(top) = top {
+        entries.truncate(top);
+    }
+
+    Ok(entries)
+}
+
+/// List installed versions with their sources, grouped by source
+fn installed_source_entries(installer: &Installer) -> Result<Vec<InstalledSourceEntry>> {
+    let mut installed: Vec<_> = installer
+        .list_installed()?
+        .into_iter()
+        .map(|version| (installer.source_for(&version), version))
+        .collect();
+    installed.sort();
+
+    Ok(installed
+        .into_iter()
+        .map(|(source, version)| InstalledSourceEntry {
+            version: version.godot_version_string(),
+            dotnet: version.is_dotnet,
+            source,
+        })
+        .collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::github::fake_release;
+    use crate::installer::fake_install;
+
+    #[test]
+    fn test_remote_entries_installed_status() {
+        let releases = vec![
+            fake_release("4.3-stable", &[]),
+            fake_release("4.3-rc1", &[]),
+            fake_release("4.2.1-stable", &[]),
+        ];
+        let installed = vec![GodotVersion::new("4.2.1", true).unwrap()];
+
+        let entries = remote_entries(&releases, Some(&installed));
+        let statuses: Vec<_> = entries
+            .iter()
+            .map(|e| (e.version.as_str(), e.installed))
+            .collect();
+        assert_eq!(
+            statuses,
+            [
+                ("4.3", Some(false)),
+                ("4.3-rc1", Some(false)),
+                ("4.2.1", Some(true))
+            ]
+        );
+
+        let json = serde_json::to_value(&entries).unwrap();
+        assert_eq!(json[2]["installed"], true);
+
+        // Without --installed-status the field is left out
+        let entries = remote_entries(&releases, None);
+        assert!(serde_json::to_value(&entries).unwrap()[0]
+            .get("installed")
+            .is_none());
+    }
+
+    #[test]
+    fn test_nix_expression() {
+        let archives = [
+            NixArchive {
+                version: GodotVersion::new("4.2.1", false).unwrap(),
+                url: "https://example.com/Godot_v4.2.1-stable_linux.x86_64.zip".to_string(),
+                sha512: Some("ab12".to_string()),
+            },
+            NixArchive {
+                version: GodotVersion::new("4.2.1", true).unwrap(),
+                url: "https://example.com/Godot_v4.2.1-stable_mono_linux_x86_64.zip".to_string(),
+                sha512: None,
+            },
+        ];
+        let nix = nix_expression(&archives, "linux.x86_64");
+
+        assert!(nix.starts_with("{ pkgs ? import <nixpkgs> { } }:"));
+        assert!(nix.contains("\"4.2.1\" = pkgs.stdenv.mkDerivation {"));
+        assert!(nix.contains("\"4.2.1-dotnet\" = pkgs.stdenv.mkDerivation {"));
+        assert!(nix.contains("pname = \"godot-dotnet\";"));
+        assert!(nix.contains("sha512 = \"ab12\";"));
+        assert!(nix.contains("hash = pkgs.lib.fakeHash;"));
+        assert!(nix.contains(
+            "$out/share/godot/Godot_v4.2.1-stable_mono_linux_x86_64/Godot_v4.2.1-stable_mono_linux.x86_64"
+        ));
+        assert_eq!(nix.matches('{').count(), nix.matches('}').count());
+        assert_eq!(
+            nix_expression(&[], "linux.x86_64"),
+            "{ pkgs ? import <nixpkgs> { } }:\n\n{\n}\n"
+        );
+    }
+
+    #[test]
+    fn test_releases_for_platform() {
+        let releases = vec![
+            fake_release("4.3-stable", &["Godot_v4.3-stable_win64.exe.zip"]),
+            fake_release(
+                "4.2.1-stable",
+                &[
+                    "Godot_v4.2.1-stable_linux.x86_64.zip",
+                    "Godot_v4.2.1-stable_win64.exe.zip",
+                ],
+            ),
+        ];
+
+        let tags = |platform: &str| -> Vec<String> {
+            releases_for_platform(releases.clone(), platform)
+                .unwrap()
+                .into_iter()
+                .map(|r| r.tag_name)
+                .collect()
+        };
+        assert_eq!(tags("linux.x86_64"), ["4.2.1-stable"]);
+        assert_eq!(tags("win64.exe"), ["4.3-stable", "4.2.1-stable"]);
+        assert!(releases_for_platform(releases.clone(), "amiga.m68k").is_err());
+    }
+
+    #[test]
+    fn test_installed_size_entries() {
+        let root = tempfile::tempdir().unwrap();
+        let config = Config::with_data_dir(root.path());
+        let installer = Installer::new(config.clone());
+
+        for (version, is_dotnet, size) in [
+            ("4.1.0", false, 10),
+            ("4.2.1", true, 300),
+            ("4.3", false, 20),
+        ] {
+            let version = GodotVersion::new(version, is_dotnet).unwrap();
+            fake_install(&config, &version, vec![0; size]);
+        }
+
+        let entries = installed_size_entries(&installer, true, Some(2)).unwrap();
+        let sizes: Vec<_> = entries
+            .iter()
+            .map(|e| (e.version.as_str(), e.dotnet, e.size_bytes))
+            .collect();
+        assert_eq!(sizes, [("4.2.1", true, 300), ("4.3.0", false, 20)]);
+
+        let json = serde_json::to_value(&entries).unwrap();
+        assert_eq!(json[0]["size_bytes"], 300);
+
+        assert_eq!(
+            installed_size_entries(&installer, false, None)
+                .unwrap()
+                .len(),
+            3
+        );
+    }
+
+    #[test]
+    fn test_installed_source_entries() {
+        let root = tempfile::tempdir().unwrap();
+        let config = Config::with_data_dir(&root.path().join("gdenv"));
+        let installer = Installer::new(config.clone());
+
+        let managed = GodotVersion::new("4.3", false).unwrap();
+        fake_install(&config, &managed, "");
+
+        let binary = root.path().join("godot");
+        std::fs::write(&binary, "").unwrap();
+        for (version, source) in [
+            ("4.2.2", InstallSource::Steam),
+            ("4.4", InstallSource::Adopted),
+            ("4.1.4", InstallSource::Steam),
+        ] {
+            let version = GodotVersion::new(version, false).unwrap();
+            installer.adopt(&version, &binary, source).unwrap();
+        }
+
+        let entries = installed_source_entries(&installer).unwrap();
+        let sources: Vec<_> = entries
+            .iter()
+            .map(|e| (e.source, e.version.as_str()))
+            .collect();
+        assert_eq!(
+            sources,
+            [
+                (InstallSource::Managed, "4.3.0"),
+                (InstallSource::Adopted, "4.4.0"),
+                (InstallSource::Steam, "4.1.4"),
+                (InstallSource::Steam, "4.2.2"),
+            ]
+        );
+        assert_eq!(
+            serde_json::to_value(&entries).unwrap()[2]["source"],
+            "steam"
+        );
+    }
+}
@@ -0,0 +1,316 @@
+use anyhow::{anyhow, Result};
+use clap::{Args, Subcommand};
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+use crate::{
+    dry_run::DryRunMode,
+    godot::GodotVersion,
+    lockfile::{LockFile, LOCKFILE_NAME},
+    project::PROJECT_FILE,
+    ui,
+};
+
+const PRE_COMMIT_HOOK: &str = "#!/bin/sh\n# Installed by gdenv\nexec gdenv lock check\n";
+
+#[derive(Args)]
+pub struct LockCommand {
+    #[command(subcommand)]
+    pub action: LockAction,
+}
+
+#[derive(Subcommand)]
+pub enum LockAction {
+    /// Check that .godot-version matches the version in project.godot
+    Check {
+        /// Path to the Godot project directory
+        #[arg(default_value = ".")]
+        path: PathBuf,
+
+        /// Update .godot-version to match project.godot instead of failing
+        #[arg(long)]
+        fix: bool,
+
+        /// Install a git pre-commit hook that runs `gdenv lock check`
+        #[arg(long, conflicts_with = "fix")]
+        install_hook: bool,
+    },
+    /// Open .godot-version in $VISUAL or $EDITOR and check it still parses afterwards
+    Edit {
+        /// Directory to look for .godot-version in (or in its parents)
+        #[arg(default_value = ".")]
+        path: PathBuf,
+    },
+}
+
+impl LockCommand {
+    pub async fn run(self) -> Result<()> {
+        match self.action {
+            LockAction::Check {
+                path,
+                fix,
+                install_hook,
+            } => {
+                if install_hook {
+                    let hook_path = install_pre_commit_hook(&path)?;
+                    ui::success(&format!(
+                        "Installed pre-commit hook: {}",
+                        hook_path.display()
+                    ));
+                    return Ok(());
+                }
+
+                check(&path, fix)
+            }
+            LockAction::Edit { path } => {
+                let lockfile_path = LockFile::find(&path).ok_or_else(|| {
+                    anyhow!(
+                        "No {} found in {} or its parents",
+                        LOCKFILE_NAME,
+                        path.display()
+                    )
+                })?;
+                let editor = editor_command(
+                    std::env::var("VISUAL").ok().as_deref(),
+                    std::env::var("EDITOR").ok().as_deref(),
+                );
+
+                let lockfile = edit(&lockfile_path, &editor, || {
+                    ui::confirm("Open the editor again? (no restores the previous version)")
+                })?;
+                ui::success(&format!(
+                    "{} pins Godot {}",
+                    lockfile.path.display(),
+                    lockfile.pin
+                ));
+                Ok(())
+            }
+        }
+    }
+}
+
+fn check(project_dir: &Path, fix: bool) -> Result<()> {
+    let project_file = project_dir.join(PROJECT_FILE);
+    if !project_file.exists() {
+        return Err(anyhow!(
+            "No {} found in {}",
+            PROJECT_FILE,
+            project_dir.display()
+        ));
+    }
+
+    let project_version = GodotVersion::from_project_file(&project_file)?.ok_or_else(|| {
+        anyhow!(
+            "{} doesn't list a Godot version in config/features",
+            project_file.display()
+        )
+    })?;
+
+    let lockfile = match LockFile::find(project_dir) {
+        Some(lockfile_path) => Some(LockFile::read(&lockfile_path)?),
+        None => None,
+    };
+
+    if let Some(lockfile) = &lockfile {
+        if lockfile.matches_project(&project_version) {
+            ui::success(&format!(
+                "{} ({}) matches {} (v{})",
+                LOCKFILE_NAME, lockfile.pin, PROJECT_FILE, project_version
+            ));
+            return Ok(());
+        }
+    }
+
+    if fix {
+        let lockfile_path = lockfile
+            .as_ref()
+            .map(|l| l.path.clone())
+            .unwrap_or_else(|| project_dir.join(LOCKFILE_NAME));
+        LockFile::write(&lockfile_path, &project_version, DryRunMode::Off)?;
+        ui::success(&format!(
+            "Updated {} to v{}",
+            lockfile_path.display(),
+            project_version
+        ));
+        return Ok(());
+    }
+
+    match lockfile {
+        Some(lockfile) => Err(anyhow!(
+            "{} pins {}, but {} targets v{}.{}{}\nUpdate the lockfile or run 'gdenv lock check --fix'",
+            lockfile.path.display(),
+            lockfile.pin,
+            PROJECT_FILE,
+            project_version.version.major,
+            project_version.version.minor,
+            if project_version.is_dotnet { " (.NET)" } else { "" }
+        )),
+        None => Err(anyhow!(
+            "No {} found for this project, which targets v{}\nRun 'gdenv lock check --fix' to create one",
+            LOCKFILE_NAME,
+            project_version
+        )),
+    }
+}
+
+/// The editor to run, split into program and arguments: `$VISUAL`, then `$EDITOR`,
+/// then nano (notepad on Windows)
+fn editor_command(visual: Option<&str>, editor: Option<&str>) -> Vec<String> {
+    let fallback = if cfg!(windows) { "notepad" } else { "nano" };
+    [visual, editor]
+        .into_iter()
+        .flatten()
+        .map(str::trim)
+        .find(|command| !command.is_empty())
+        .unwrap_or(fallback)
+        .split_whitespace()
+        .map(String::from)
+        .collect()
+}
+
+/// Let the user edit a lockfile until it parses, or restore its previous content
+/// when they give up (`retry` returns false)
+fn edit(
+    path: &Path,
+    editor: &[String],
+    mut retry: impl FnMut() -> io::Result<bool>,
+) -> Result<LockFile> {
+    let backup = fs::read(path)?;
+    let (program, args) = editor
+        .split_first()
+        .ok_or_else(|| anyhow!("No editor configured; set $EDITOR"))?;
+
+    loop {
+        let status = Command::new(program)
+            .args(args)
+            .arg(path)
+            .status()
+            .map_err(|e| anyhow!("Could not start the editor '{}': {}", program, e))?;
+
+        let error = if status.success() {
+            match LockFile::read(path) {
+                Ok(lockfile) => return Ok(lockfile),
+                Err(e) => format!("{} is not valid: {}", path.display(), e),
+            }
+        } else {
+            format!("The editor exited with {}", status)
+        };
+
+        ui::error(&error);
+        if !retry()? {
+            fs::write(path, &backup)?;
+            return Err(anyhow!(
+                "{}; restored the previous version of {}",
+                error,
+                path.display()
+            ));
+        }
+    }
+}
+
+/// Write a pre-commit hook into the git repository containing `project_dir`
+fn install_pre_commit_hook(project_dir: &Path) -> Result<PathBuf> {
+    let project_dir = project_dir.canonicalize()?;
+    let git_dir = project_dir
+        .ancestors()
+        .map(|dir| dir.join(".git"))
+        .find(|dir| dir.is_dir())
+        .ok_or_else(|| anyhow!("{} is not inside a git repository", project_dir.display()))?;
+
+    let hook_path = git_dir.join("hooks").join("pre-commit");
+    if hook_path.exists() && fs::read_to_string(&hook_path)? != PRE_COMMIT_HOOK {
+        return Err(anyhow!(
+            "{} already exists; add 'gdenv lock check' to it manually",
+            hook_path.display()
+        ));
+    }
+
+    fs::create_dir_all(hook_path.parent().unwrap())?;
+    fs::write(&hook_path, PRE_COMMIT_HOOK)?;
+
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+        fs::set_permissions(&hook_path, fs::Permissions::from_mode(0o755))?;
+    }
+
+    Ok(hook_path)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::lockfile::Pin;
+
+    #[test]
+    fn test_editor_command() {
+        assert_eq!(editor_command(Some("code -w"), Some("vim")), ["code", "-w"]);
+        assert_eq!(editor_command(Some(" "), Some("vim")), ["vim"]);
+        let fallback = if cfg!(windows) { "notepad" } else { "nano" };
+        assert_eq!(editor_command(None, None), [fallback]);
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_edit_validates_and_restores() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join(LOCKFILE_NAME);
+        fs::write(&path, "4.2.1\n").unwrap();
+        // An "editor" that replaces the file it's given with `content`
+        let editor = |content: &str| {
+            ["sh", "-c", &format!("printf '{}' > \"$0\"", content)].map(String::from)
+        };
+
+        let lockfile = edit(&path, &editor("4.3-dotnet"), || Ok(false)).unwrap();
+        assert_eq!(
+            lockfile.pin,
+            Pin::Version(GodotVersion::new("4.3", true).unwrap())
+        );
+        // A range is a valid pin too
+        let lockfile = edit(&path, &editor("~4.3"), || Ok(false)).unwrap();
+        assert!(matches!(lockfile.pin, Pin::Range(_)));
+
+        let mut attempts = 0;
+        let error = edit(&path, &editor("four"), || {
+            attempts += 1;
+            Ok(attempts < 2)
+        })
+        .unwrap_err();
+        assert_eq!(attempts, 2);
+        assert!(error.to_string().contains("restored"), "{}", error);
+        assert_eq!(fs::read_to_string(&path).unwrap(), "~4.3");
+    }
+
+    #[test]
+    fn test_check_and_fix() {
+        let dir = tempfile::tempdir().unwrap();
+        fs::write(
+            dir.path().join(PROJECT_FILE),
+            "[application]\nconfig/features=PackedStringArray(\"4.3\", \"Forward Plus\")\n",
+        )
+        .unwrap();
+
+        // No lockfile yet
+        assert!(check(dir.path(), false).is_err());
+
+        fs::write(dir.path().join(LOCKFILE_NAME), "4.2.1\n").unwrap();
+        let err = check(dir.path(), false).unwrap_err().to_string();
+        assert!(err.contains("4.2.1"));
+        assert!(err.contains("4.3"));
+
+        check(dir.path(), true).unwrap();
+        check(dir.path(), false).unwrap();
+        assert_eq!(
+            LockFile::read(&dir.path().join(LOCKFILE_NAME)).unwrap().pin,
+            Pin::Version(GodotVersion::new("4.3", false).unwrap())
+        );
+
+        // A range matches if it admits some 4.3 patch release
+        fs::write(dir.path().join(LOCKFILE_NAME), ">=4.2, <4.4\n").unwrap();
+        check(dir.path(), false).unwrap();
+        fs::write(dir.path().join(LOCKFILE_NAME), "~4.2\n").unwrap();
+        assert!(check(dir.path(), false).is_err());
+    }
+}
@@ -0,0 +1,251 @@
+use anyhow::{anyhow, Result};
+use clap::Args;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+use crate::{
+    config::Config,
+    disk::{self, DiskSpaceError},
+    installer::{Installer, ADOPTED_FILE},
+    ui,
+};
+
+#[derive(Args)]
+pub struct MigrateInstallsCommand {
+    /// The directory to use as the new gdenv root
+    #[arg(long, value_name = "NEW_ROOT")]
+    pub to: PathBuf,
+}
+
+impl MigrateInstallsCommand {
+    pub async fn run(self) -> Result<()> {
+        let config = Config::new()?;
+        let old_bin_dir = config.bin_dir.clone();
+        let new_config = self.run_with(config)?;
+
+        ui::success(&format!("Moved gdenv to {}", new_config.root_dir.display()));
+        println!("\nTo use the new location, set GDENV_ROOT in your shell profile:");
+        println!("  export GDENV_ROOT=\"{}\"", new_config.root_dir.display());
+        println!(
+            "and replace {} with {} in your PATH",
+            old_bin_dir.display(),
+            new_config.bin_dir.display()
+        );
+
+        Ok(())
+    }
+
+    /// Move everything under the root of `config` to the new root, returning its configuration
+    fn run_with(self, config: Config) -> Result<Config> {
+        let old_root = config.root_dir.canonicalize()?;
+        fs::create_dir_all(&self.to)?;
+        let new_root = self.to.canonicalize()?;
+
+        if new_root.starts_with(&old_root) || old_root.starts_with(&new_root) {
+            return Err(anyhow!(
+                "{} and {} overlap; pick a directory outside the current root",
+                new_root.display(),
+                old_root.display()
+            ));
+        }
+        if fs::read_dir(&new_root)?.next().is_some() {
+            return Err(anyhow!("{} is not empty", new_root.display()));
+        }
+
+        let active_version = Installer::new(config.clone()).get_active_version()?;
+
+        // The link to the active version is recreated rather than moved
+        let entries: Vec<PathBuf> = fs::read_dir(&old_root)?
+            .map(|entry| entry.map(|e| e.path()))
+            .collect::<io::Result<Vec<_>>>()?
+            .into_iter()
+            .filter(|path| path.file_name() != config.active_symlink.file_name())
+            .collect();
+
+        // Renames within a filesystem need no extra space
+        if !disk::same_filesystem(&old_root, &new_root)? {
+            let required = entries
+                .iter()
+                .map(|path| disk::dir_size(path))
+                .sum::<io::Result<u64>>()?;
+            let available = disk::available_space(&new_root)?;
+            if available < required {
+                return Err(DiskSpaceError {
+                    required,
+                    available,
+                }
+                .into());
+            }
+        }
+
+        for entry in &entries {
+            let name = entry.file_name().unwrap();
+            ui::info(&format!("Moving {}", name.to_string_lossy()));
+            move_entry(entry, &new_root.join(name))?;
+        }
+
+        let mut new_config = Config {
+            github_api_url: config.github_api_url.clone(),
+            settings: config.settings.clone(),
+            ..Config::with_data_dir(&new_root)
+        };
+        if !config.config_dir.starts_with(&config.root_dir) {
+            new_config.config_dir = config.config_dir.clone();
+        }
+
+        rewrite_adopted_paths(&new_config.installations_dir, &old_root, &new_root)?;
+        remove_links_into(&new_config.bin_dir, &old_root)?;
+
+        if config.active_symlink.is_symlink() {
+            fs::remove_file(&config.active_symlink)?;
+        }
+        if let Some(version) = active_version {
+            fs::create_dir_all(&new_config.bin_dir)?;
+            Installer::new(new_config.clone()).set_active_version_with_message(&version, false)?;
+        }
+
+        // Only succeeds if nothing else was left behind
+        let _ = fs::remove_dir(&old_root);
+
+        Ok(new_config)
+    }
+}
+
+/// Move a file or directory, renaming it if possible and otherwise copying it next to
+/// its destination first, so `dest` never holds a partial copy
+fn move_entry(source: &Path, dest: &Path) -> Result<()> {
+    if fs::rename(source, dest).is_ok() {
+        return Ok(());
+    }
+
+    let staging = dest.with_file_name(format!(
+        ".{}.migrating",
+        dest.file_name().unwrap().to_string_lossy()
+    ));
+    copy_tree(source, &staging)?;
+    fs::rename(&staging, dest)?;
+
+    if fs::symlink_metadata(source)?.is_dir() {
+        fs::remove_dir_all(source)?;
+    } else {
+        fs::remove_file(source)?;
+    }
+    Ok(())
+}
+
+fn copy_tree(source: &Path, dest: &Path) -> io::Result<()> {
+    let metadata = fs::symlink_metadata(source)?;
+
+    if metadata.is_symlink() {
+        let target = fs::read_link(source)?;
+        #[cfg(unix)]
+        std::os::unix::fs::symlink(&target, dest)?;
+        #[cfg(windows)]
+        if source.is_dir() {
+            std::os::windows::fs::symlink_dir(&target, dest)?;
+        } else {
+            std::os::windows::fs::symlink_file(&target, dest)?;
+        }
+    } else if metadata.is_dir() {
+        fs::create_dir_all(dest)?;
+        for entry in fs::read_dir(source)? {
+            let entry = entry?;
+            copy_tree(&entry.path(), &dest.join(entry.file_name()))?;
+        }
+    } else {
+        fs::copy(source, dest)?;
+    }
+
+    Ok(())
+}
+
+/// Point adopted installations whose binary lived under the old root to its new location
+fn rewrite_adopted_paths(installations_dir: &Path, old_root: &Path, new_root: &Path) -> Result<()> {
+    let Ok(entries) = fs::read_dir(installations_dir) else {
+        return Ok(());
+    };
+
+    for entry in entries {
+        let adopted_file = entry?.path().join(ADOPTED_FILE);
+        if !adopted_file.is_file() {
+            continue;
+        }
+
+        let executable = PathBuf::from(fs::read_to_string(&adopted_file)?.trim());
+        if let Ok(relative) = executable.strip_prefix(old_root) {
+            fs::write(
+                &adopted_file,
+                new_root.join(relative).to_string_lossy().as_bytes(),
+            )?;
+        }
+    }
+
+    Ok(())
+}
+
+/// Remove symlinks in `dir` that point under `old_root`, which no longer resolve
+fn remove_links_into(dir: &Path, old_root: &Path) -> Result<()> {
+    let Ok(entries) = fs::read_dir(dir) else {
+        return Ok(());
+    };
+
+    for entry in entries {
+        let path = entry?.path();
+        if path.is_symlink() && fs::read_link(&path)?.starts_with(old_root) {
+            fs::remove_file(&path)?;
+        }
+    }
+
+    Ok(())
+}
+
+#[cfg(all(test, unix))]
+mod tests {
+    use super::*;
+    use crate::godot::GodotVersion;
+    use crate::installer::fake_install;
+
+    #[test]
+    fn test_migrate_installs() {
+        let old = tempfile::tempdir().unwrap();
+        let config = Config::with_data_dir(&old.path().canonicalize().unwrap());
+        let installer = Installer::new(config.clone());
+
+        let versions = [
+            GodotVersion::new("4.2.1", false).unwrap(),
+            GodotVersion::new("4.3", true).unwrap(),
+        ];
+        for version in &versions {
+            fake_install(&config, version, "");
+        }
+        fs::create_dir_all(&config.bin_dir).unwrap();
+        fs::create_dir_all(&config.cache_dir).unwrap();
+        fs::write(config.cache_dir.join("archive.zip"), "zip").unwrap();
+        installer.set_active_version(&versions[0]).unwrap();
+
+        let new = tempfile::tempdir().unwrap();
+        let new_config = MigrateInstallsCommand {
+            to: new.path().join("gdenv"),
+        }
+        .run_with(config.clone())
+        .unwrap();
+
+        let new_installer = Installer::new(new_config.clone());
+        let mut installed = new_installer.list_installed().unwrap();
+        installed.sort();
+        assert_eq!(installed, versions);
+        assert_eq!(
+            new_installer.get_active_version().unwrap(),
+            Some(versions[0].clone())
+        );
+        assert!(new_config
+            .active_symlink
+            .canonicalize()
+            .unwrap()
+            .starts_with(&new_config.root_dir));
+        assert!(new_config.bin_dir.join("godot").is_file());
+        assert!(new_config.cache_dir.join("archive.zip").is_file());
+        assert!(!config.root_dir.exists());
+    }
+}
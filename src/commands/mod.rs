@@ -1,8 +1,38 @@
+pub mod adopt;
+pub mod available;
+pub mod build_id;
 pub mod cache;
+pub mod catalog;
+pub mod checksum;
+pub mod complete;
+pub mod completions;
+pub mod config;
 pub mod current;
+pub mod doctor;
+pub mod dotnet_sdk_version;
+pub mod env;
+pub mod hook;
+pub mod import;
+pub mod import_from_steam;
+pub mod info;
+pub mod inspect_project;
 pub mod install;
 pub mod installed;
+pub mod lint_lockfile;
 pub mod list;
+pub mod lock;
+pub mod migrate_installs;
+pub mod project;
+pub mod run;
+pub mod source;
+pub mod status;
+pub mod sync;
 pub mod uninstall;
 pub mod update;
+pub mod upgrade_tooling;
+pub mod url;
 pub mod use_cmd;
+pub mod validate_pin;
+pub mod verify;
+pub mod watch;
+pub mod which;
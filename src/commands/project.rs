@@ -0,0 +1,570 @@
+use anyhow::{anyhow, Result};
+use clap::{Args, Subcommand};
+use std::fs;
+use std::io::{self, IsTerminal};
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+use crate::{
+    breaking_changes,
+    commands::install::InstallCommand,
+    config::Config,
+    github::GitHubClient,
+    godot::GodotVersion,
+    installer::Installer,
+    lockfile::{LockFile, Pin, LOCKFILE_NAME},
+    project::{self, ExportPreset, EXPORT_PRESETS_FILE, PROJECT_FILE},
+    ui,
+};
+
+#[derive(Args)]
+pub struct ProjectCommand {
+    #[command(subcommand)]
+    pub action: ProjectAction,
+}
+
+#[derive(Subcommand)]
+pub enum ProjectAction {
+    /// Export a project with one of its presets, installing Godot and its export templates if needed
+    Export {
+        /// Name of the export preset, as listed in export_presets.cfg
+        #[arg(required_unless_present = "preset_list")]
+        preset: Option<String>,
+
+        /// Godot version to export with (default: the project's .godot-version, then the active version)
+        #[arg(long = "version", value_name = "VERSION")]
+        godot_version: Option<String>,
+
+        /// File to export to, or a directory for the file named by the preset's export path
+        #[arg(long, short, required_unless_present = "preset_list")]
+        output: Option<PathBuf>,
+
+        /// Path to the Godot project directory
+        #[arg(long, default_value = ".")]
+        path: PathBuf,
+
+        /// Export a debug build instead of a release build
+        #[arg(long)]
+        debug: bool,
+
+        /// Print the names of the project's export presets instead of exporting
+        #[arg(long)]
+        preset_list: bool,
+    },
+
+    /// Show the Godot version a project needs, according to each place gdenv looks for it
+    Detect {
+        /// Path to the Godot project directory
+        #[arg(default_value = ".")]
+        path: PathBuf,
+    },
+
+    /// Generate a sed script that rewrites GDScript for a newer Godot version, marking
+    /// what it can't migrate with "# TODO: verify"
+    UpgradeScript {
+        /// The Godot version the project uses now, e.g. 3.5.3
+        from: String,
+
+        /// The Godot version to migrate to, e.g. 4.2.1
+        to: String,
+
+        /// File to write the script to (default: stdout)
+        #[arg(long, short)]
+        output: Option<PathBuf>,
+    },
+}
+
+impl ProjectCommand {
+    pub async fn run(self) -> Result<()> {
+        match self.action {
+            ProjectAction::Export {
+                preset,
+                godot_version,
+                output,
+                path,
+                debug,
+                preset_list,
+            } => {
+                if !path.join(PROJECT_FILE).exists() {
+                    return Err(anyhow!("No {} found in {}", PROJECT_FILE, path.display()));
+                }
+
+                let presets = project::export_presets(&path)?;
+                if preset_list {
+                    for preset in &presets {
+                        println!("{}", preset.name);
+                    }
+                    return Ok(());
+                }
+
+                // Both are required by clap unless --preset-list is given
+                let (preset, output) = (preset.unwrap(), output.unwrap());
+                let preset = presets.iter().find(|p| p.name == preset).ok_or_else(|| {
+                    anyhow!(
+                        "No export preset named '{}' in {} (available: {})",
+                        preset,
+                        EXPORT_PRESETS_FILE,
+                        presets
+                            .iter()
+                            .map(|p| p.name.as_str())
+                            .collect::<Vec<_>>()
+                            .join(", ")
+                    )
+                })?;
+
+                export(&path, preset, godot_version.as_deref(), &output, debug).await
+            }
+            ProjectAction::Detect { path } => detect(&path),
+            ProjectAction::UpgradeScript { from, to, output } => {
+                upgrade_script(&from.parse()?, &to.parse()?, output.as_deref())
+            }
+        }
+    }
+}
+
+fn detect(project_dir: &Path) -> Result<()> {
+    println!("🔎 Godot version of {}:", project_dir.display());
+
+    let config = Config::new()?;
+    // IDE settings come with the repository, so only run their editor if it's one gdenv
+    // installed or the user agrees
+    let confirm = |editor: &Path| {
+        io::stdin().is_terminal()
+            && ui::confirm(&format!(
+                "Run {} to detect its Godot version?",
+                editor.display()
+            ))
+            .unwrap_or(false)
+    };
+
+    let mut detected = None;
+    for (source, result) in detection_heuristics(project_dir, &config, confirm) {
+        match result {
+            Ok(Some(version)) => {
+                println!("  {:<22} {}", source, version);
+                detected.get_or_insert((version, source));
+            }
+            Ok(None) => println!("  {:<22} not set", source),
+            Err(e) => ui::warning(&format!("{}: {}", source, e)),
+        }
+    }
+
+    let (version, source) = detected.ok_or_else(|| {
+        anyhow!(
+            "Could not detect the Godot version of {}; pin one with 'gdenv lock'",
+            project_dir.display()
+        )
+    })?;
+    ui::success(&format!("Detected Godot v{} (from {})", version, source));
+    Ok(())
+}
+
+fn upgrade_script(from: &GodotVersion, to: &GodotVersion, output: Option<&Path>) -> Result<()> {
+    if to.version <= from.version {
+        return Err(anyhow!(
+            "Godot v{} isn't newer than v{}",
+            to.godot_version_string(),
+            from.godot_version_string()
+        ));
+    }
+
+    let changes = breaking_changes::known_breaking_changes_from(from, to);
+    let script = breaking_changes::sed_script(from, to, &changes);
+
+    match output {
+        Some(path) => {
+            fs::write(path, &script)?;
+            ui::success(&format!(
+                "Wrote {} migrations to {}",
+                changes.len(),
+                path.display()
+            ));
+        }
+        None => print!("{}", script),
+    }
+    if changes.is_empty() {
+        ui::info("No known GDScript changes between these versions");
+    }
+    Ok(())
+}
+
+/// Every place a project's Godot version can be read from, most authoritative first.
+/// Editors configured by IDE settings are run through [`ide_editor_version`].
+fn detection_heuristics(
+    project_dir: &Path,
+    config: &Config,
+    confirm: impl Fn(&Path) -> bool,
+) -> Vec<(&'static str, Result<Option<GodotVersion>>)> {
+    let installer = Installer::new(config.clone());
+    let ide_editor = |path: Result<Option<PathBuf>>| {
+        path?
+            .map(|editor| ide_editor_version(&editor, &config.installations_dir, &confirm))
+            .transpose()
+    };
+    let project_file = project_dir.join(PROJECT_FILE);
+    vec![
+        (
+            LOCKFILE_NAME,
+            LockFile::find(project_dir)
+                .map(|path| lockfile_version(&path, &installer))
+                .transpose(),
+        ),
+        (
+            PROJECT_FILE,
+            if project_file.exists() {
+                GodotVersion::from_project_file(&project_file)
+            } else {
+                Ok(None)
+            },
+        ),
+        (
+            "VS Code settings",
+            ide_editor(GodotVersion::vscode_editor_path(project_dir)),
+        ),
+        (
+            "JetBrains godot.xml",
+            ide_editor(GodotVersion::jetbrains_editor_path(project_dir)),
+        ),
+    ]
+}
+
+/// The version a lockfile pins, or for a range the newest installed version in it
+fn lockfile_version(lockfile_path: &Path, installer: &Installer) -> Result<GodotVersion> {
+    let pin = LockFile::read_pin(lockfile_path)?;
+    match pin {
+        Pin::Version(version) => Ok(version),
+        Pin::Range(_) => installer.resolve_pin(&pin)?.ok_or_else(|| {
+            anyhow!(
+                "pins {}, which no installed version satisfies; run 'gdenv install'",
+                pin
+            )
+        }),
+    }
+}
+
+/// Detect the version of an editor an IDE is configured with by running it. The path
+/// comes from the repository, so an editor outside the gdenv installations only runs if
+/// `confirm` allows it; otherwise the error reports the path without running it.
+fn ide_editor_version(
+    editor: &Path,
+    installations_dir: &Path,
+    confirm: impl Fn(&Path) -> bool,
+) -> Result<GodotVersion> {
+    let resolved = editor.canonicalize().map_err(|e| {
+        anyhow!(
+            "The configured editor {} can't be found: {}",
+            editor.display(),
+            e
+        )
+    })?;
+    let managed = installations_dir
+        .canonicalize()
+        .is_ok_and(|installations| resolved.starts_with(installations));
+
+    if !managed && !confirm(&resolved) {
+        return Err(anyhow!(
+            "configures the editor {}, which gdenv didn't install, so it wasn't run to check its version",
+            resolved.display()
+        ));
+    }
+    GodotVersion::try_from_path(&resolved)
+}
+
+async fn export(
+    project_dir: &Path,
+    preset: &ExportPreset,
+    godot_version: Option<&str>,
+    output: &Path,
+    debug: bool,
+) -> Result<()> {
+    let config = Config::new()?;
+    let installer = Installer::new(config.clone());
+
+    let version = match godot_version {
+        Some(version) => version.parse()?,
+        None => match LockFile::find(project_dir) {
+            Some(lockfile_path) => match LockFile::read_pin(&lockfile_path)? {
+                Pin::Version(version) => version,
+                // A range can't be installed, so it has to be satisfied already
+                pin @ Pin::Range(_) => installer.resolve_pin(&pin)?.ok_or_else(|| {
+                    anyhow!(
+                        "{} pins {}, which no installed version satisfies\nRun 'gdenv install' or pass --version",
+                        lockfile_path.display(),
+                        pin
+                    )
+                })?,
+            },
+            None => installer.get_active_version()?.ok_or_else(|| {
+                anyhow!("No Godot version pinned or active; pass --version or add a .godot-version")
+            })?,
+        },
+    };
+
+    if !installer.is_installed(&version) {
+        InstallCommand::for_version(&version)
+            .run_with(config.clone())
+            .await?;
+    }
+
+    let templates_dir = Installer::export_templates_dir(&version)
+        .ok_or_else(|| anyhow!("Could not determine the export templates directory"))?;
+    if templates_dir.exists() {
+        ui::info(&format!(
+            "Using export templates in {}",
+            templates_dir.display()
+        ));
+    } else {
+        let archive = config
+            .cache_dir
+            .join(format!("{}.tpz", version.export_templates_name()));
+        if !archive.exists() {
+            GitHubClient::from_config(&config)
+                .download_with_progress(
+                    &version.export_templates_url(),
+                    "export templates",
+                    0,
+                    &archive,
+                )
+                .await?;
+        }
+        let templates_dir = installer.install_export_templates(&version, &archive)?;
+        ui::success(&format!(
+            "Installed export templates to {}",
+            templates_dir.display()
+        ));
+    }
+
+    let output = std::env::current_dir()?.join(output_file(output, preset)?);
+    if let Some(parent) = output.parent() {
+        fs::create_dir_all(parent)?;
+    }
+
+    ui::info(&format!(
+        "Exporting '{}' with Godot v{} to {}",
+        preset.name,
+        version,
+        output.display()
+    ));
+
+    let godot = installer.get_executable(&version)?;
+    let status =
+        export_command(&godot, &version, project_dir, &preset.name, &output, debug).status()?;
+
+    if !status.success() || !output.exists() {
+        ui::error("Export failed, see the Godot output above");
+        std::process::exit(status.code().filter(|&code| code != 0).unwrap_or(1));
+    }
+
+    ui::success(&format!("Exported {}", output.display()));
+    Ok(())
+}
+
+/// Godot needs a file to export to, so a directory gets the file name of the preset's export path
+fn output_file(output: &Path, preset: &ExportPreset) -> Result<PathBuf> {
+    if !output.is_dir() && output.extension().is_some() {
+        return Ok(output.to_path_buf());
+    }
+
+    let file_name = Path::new(&preset.export_path).file_name().ok_or_else(|| {
+        anyhow!(
+            "Preset '{}' has no export path to take a file name from; pass a file to --output",
+            preset.name
+        )
+    })?;
+    Ok(output.join(file_name))
+}
+
+/// Build the command that exports a project headlessly with an export preset
+fn export_command(
+    godot: &Path,
+    version: &GodotVersion,
+    project_dir: &Path,
+    preset: &str,
+    output: &Path,
+    debug: bool,
+) -> Command {
+    // Godot 3 has no --headless and a single flag for release exports
+    let (headless, export_flag) = match (version.version.major >= 4, debug) {
+        (true, false) => ("--headless", "--export-release"),
+        (true, true) => ("--headless", "--export-debug"),
+        (false, false) => ("--no-window", "--export"),
+        (false, true) => ("--no-window", "--export-debug"),
+    };
+
+    let mut command = Command::new(godot);
+    command
+        .arg(headless)
+        .arg("--path")
+        .arg(project_dir)
+        .arg(export_flag)
+        .arg(preset)
+        .arg(output);
+    command
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::ffi::OsStr;
+
+    #[cfg(unix)]
+    #[test]
+    fn test_ide_editor_version_only_runs_trusted_editors() {
+        use std::os::unix::fs::PermissionsExt;
+
+        let root = tempfile::tempdir().unwrap();
+        let config = Config::with_data_dir(&root.path().join("gdenv"));
+        let installations = config.installations_dir.clone();
+        let workspace = root.path().join("game");
+        let marker = root.path().join("ran");
+        let write_editor = |path: &Path| {
+            fs::create_dir_all(path.parent().unwrap()).unwrap();
+            fs::write(
+                path,
+                format!(
+                    "#!/bin/sh\ntouch {}\necho 4.3.stable.mono.official.77dcf97d8\n",
+                    marker.display()
+                ),
+            )
+            .unwrap();
+            fs::set_permissions(path, fs::Permissions::from_mode(0o755)).unwrap();
+        };
+
+        let repo_editor = workspace.join("bin/godot");
+        write_editor(&repo_editor);
+        fs::create_dir_all(workspace.join(".vscode")).unwrap();
+        fs::write(
+            workspace.join(".vscode/settings.json"),
+            "{\"godotTools.editorPath\": \"bin/godot\"}",
+        )
+        .unwrap();
+
+        let vscode = |confirm: fn(&Path) -> bool| {
+            detection_heuristics(&workspace, &config, confirm)
+                .into_iter()
+                .find(|(source, _)| *source == "VS Code settings")
+                .unwrap()
+                .1
+        };
+
+        let error = vscode(|_| false).unwrap_err();
+        assert!(error.to_string().contains("wasn't run"), "{}", error);
+        assert!(!marker.exists());
+
+        let expected = GodotVersion::new("4.3", true).unwrap();
+        assert_eq!(vscode(|_| true).unwrap(), Some(expected.clone()));
+        assert!(marker.exists());
+
+        // An editor in a gdenv installation runs without asking
+        let managed_editor = installations.join("godot-4.3.0-dotnet/godot");
+        write_editor(&managed_editor);
+        assert_eq!(
+            ide_editor_version(&managed_editor, &installations, |_| false).unwrap(),
+            expected
+        );
+
+        assert!(ide_editor_version(&workspace.join("missing"), &installations, |_| true).is_err());
+    }
+
+    #[test]
+    fn test_export_command_args() {
+        let v4 = GodotVersion::new("4.2.1", false).unwrap();
+        let command = export_command(
+            Path::new("godot"),
+            &v4,
+            Path::new("/work/game"),
+            "Android",
+            Path::new("/work/dist/game.apk"),
+            false,
+        );
+        assert_eq!(
+            command.get_args().collect::<Vec<_>>(),
+            [
+                "--headless",
+                "--path",
+                "/work/game",
+                "--export-release",
+                "Android",
+                "/work/dist/game.apk"
+            ]
+            .map(OsStr::new)
+        );
+
+        let v3 = GodotVersion::new("3.6", false).unwrap();
+        let args: Vec<_> = export_command(
+            Path::new("godot"),
+            &v3,
+            Path::new("."),
+            "Linux/X11",
+            Path::new("game.x86_64"),
+            false,
+        )
+        .get_args()
+        .map(|a| a.to_string_lossy().into_owned())
+        .collect();
+        assert_eq!(args[0], "--no-window");
+        assert_eq!(args[3], "--export");
+    }
+
+    #[test]
+    fn test_output_file() {
+        let dir = tempfile::tempdir().unwrap();
+        let preset = ExportPreset {
+            name: "Android".to_string(),
+            platform: "Android".to_string(),
+            export_path: "build/game.apk".to_string(),
+        };
+
+        assert_eq!(
+            output_file(dir.path(), &preset).unwrap(),
+            dir.path().join("game.apk")
+        );
+        assert_eq!(
+            output_file(Path::new("dist/android"), &preset).unwrap(),
+            Path::new("dist/android/game.apk")
+        );
+        assert_eq!(
+            output_file(Path::new("dist/my.apk"), &preset).unwrap(),
+            Path::new("dist/my.apk")
+        );
+
+        let no_path = ExportPreset {
+            export_path: String::new(),
+            ..preset
+        };
+        assert!(output_file(dir.path(), &no_path).is_err());
+    }
+
+    #[test]
+    fn test_detection_heuristics() {
+        let dir = tempfile::tempdir().unwrap();
+        fs::write(
+            dir.path().join(PROJECT_FILE),
+            "[application]\nconfig/features=PackedStringArray(\"4.3\", \"Forward Plus\")\n",
+        )
+        .unwrap();
+
+        let detected = |dir: &Path| -> Vec<Option<GodotVersion>> {
+            let config = Config::with_data_dir(&dir.join("gdenv"));
+            detection_heuristics(dir, &config, |_| false)
+                .into_iter()
+                .map(|(_, result)| result.unwrap())
+                .collect()
+        };
+        let v = |version: &str| Some(GodotVersion::new(version, false).unwrap());
+
+        assert_eq!(detected(dir.path()), [None, v("4.3"), None, None]);
+
+        fs::write(dir.path().join(LOCKFILE_NAME), "4.3.1\n").unwrap();
+        assert_eq!(detected(dir.path()), [v("4.3.1"), v("4.3"), None, None]);
+
+        // A range resolves to an installed version, and is an error when none is
+        fs::write(dir.path().join(LOCKFILE_NAME), "~4.3\n").unwrap();
+        let config = Config::with_data_dir(&dir.path().join("gdenv"));
+        let (_, lockfile) = detection_heuristics(dir.path(), &config, |_| false).remove(0);
+        assert!(lockfile
+            .unwrap_err()
+            .to_string()
+            .contains("no installed version"));
+    }
+}
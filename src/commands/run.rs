@@ -0,0 +1,813 @@
+use anyhow::{anyhow, Result};
+use clap::Args;
+use reqwest::Url;
+use std::ffi::OsString;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::process::{Child, Command, ExitStatus, Stdio};
+use std::time::{Duration, Instant};
+
+use crate::{
+    config::Config,
+    godot::{GodotVersion, MemProfiler, PhysicsBackend, RenderingMethod},
+    installer::Installer,
+    project::{self, PROJECT_FILE},
+    ui,
+};
+
+#[derive(Args)]
+pub struct RunCommand {
+    /// Launch an exported game from this directory instead of the editor
+    #[arg(long, value_name = "DIR")]
+    pub exported_game: Option<PathBuf>,
+
+    /// Open the project even if the active version is older than the one it was made with
+    #[arg(long)]
+    pub force_open: bool,
+
+    /// Rendering method to start Godot 4 with
+    #[arg(long, value_enum)]
+    pub rendering_method: Option<RenderingMethod>,
+
+    /// Open the hosted web editor for the active version in a browser
+    #[arg(long, conflicts_with_all = ["exported_game", "rendering_method"])]
+    pub web: bool,
+
+    /// URL of a zipped project for the web editor to open
+    #[arg(long, value_name = "ZIP_URL", requires = "web")]
+    pub open_project: Option<String>,
+
+    /// Only let Godot write to the project directory and the temp directory, and read
+    /// its installation and system libraries (bubblewrap on Linux, sandbox-exec on macOS)
+    #[arg(long, conflicts_with = "web")]
+    pub sandboxed: bool,
+
+    /// Run Godot under a memory profiler, which must be on your PATH
+    /// (heaptrack, or valgrind for massif and dhat)
+    #[arg(long, value_enum, value_name = "PROFILER", conflicts_with_all = ["web", "sandboxed"])]
+    pub memory_profiler: Option<MemProfiler>,
+
+    /// Trace Godot's system calls with strace (Linux only), writing the log to the temp
+    /// directory
+    #[arg(long, conflicts_with_all = ["web", "sandboxed", "memory_profiler"])]
+    pub strace: bool,
+
+    /// Comma-separated system calls to trace with --strace, or "all" (default: file
+    /// access calls, see `GodotVersion::strace_suggested_filter`)
+    #[arg(long, value_name = "SYSCALLS", requires = "strace")]
+    pub strace_filter: Option<String>,
+
+    /// Start a virtual display (Xvfb on Linux) for Godot to run on, for tests that need
+    /// a real display server on headless CI
+    #[arg(long, conflicts_with_all = ["web", "sandboxed"])]
+    pub gui_server: bool,
+
+    /// Xvfb binary to start for --gui-server (default: `Xvfb` on PATH)
+    #[arg(long, value_name = "PATH", requires = "gui_server")]
+    pub xvfb_binary: Option<PathBuf>,
+
+    /// Physics engine to run the project's 3D physics with, selected through an
+    /// override.cfg in the project directory that is removed again afterwards
+    #[arg(long, value_enum, value_name = "BACKEND", conflicts_with_all = ["web", "exported_game"])]
+    pub physics: Option<PhysicsBackend>,
+
+    /// Scale the UI by this factor (Godot 4), e.g. 2.0 to test HiDPI rendering
+    #[arg(long, value_name = "FACTOR", value_parser = parse_display_scale, conflicts_with = "web")]
+    pub display_scale: Option<f32>,
+
+    /// Arguments passed through to Godot
+    #[arg(last = true)]
+    pub args: Vec<String>,
+}
+
+impl RunCommand {
+    pub async fn run(self) -> Result<()> {
+        let config = Config::new()?;
+        let installer = Installer::new(config.clone());
+
+        let version = installer.resolve_version_for(&self.project_dir())?;
+
+        if self.web {
+            return open_web_editor(&version, self.open_project.as_deref());
+        }
+
+        if self.exported_game.is_none() {
+            self.check_project_compatibility(&version)?;
+        }
+
+        let program = match &self.exported_game {
+            Some(dir) => find_exported_binary(dir, &version)?,
+            None => installer.get_executable(&version)?,
+        };
+
+        ui::info(&format!("Running {}", program.display()));
+
+        let mut args = Vec::new();
+        if let Some(method) = self.rendering_method {
+            if version.preferred_rendering_method().is_some() {
+                args.push("--rendering-method".to_string());
+                args.push(method.to_string());
+            } else {
+                ui::warning(&format!(
+                    "Godot v{} has no rendering method option, ignoring --rendering-method",
+                    version
+                ));
+            }
+        }
+        if let Some(factor) = self.display_scale {
+            let scale_args = version.display_scale_arg(factor);
+            if scale_args.is_empty() {
+                ui::warning(&format!(
+                    "Godot v{} has no display scale option, ignoring --display-scale",
+                    version
+                ));
+            }
+            args.extend(scale_args);
+        }
+        args.extend(self.args.iter().cloned());
+
+        let profile = match self.memory_profiler {
+            Some(profiler) => Some((profiler, profiled_output(profiler, &version)?)),
+            None => None,
+        };
+
+        let strace_log = self
+            .strace
+            .then(|| std::env::temp_dir().join(format!("gdenv_strace_{}.log", std::process::id())));
+
+        let mut command = if let Some((profiler, output)) = &profile {
+            if program.extension().is_some_and(|ext| ext == "app") {
+                return Err(anyhow!(
+                    "App bundles can't be profiled; run the binary in {}/Contents/MacOS instead",
+                    program.display()
+                ));
+            }
+            let binary = which::which(profiler.binary()).map_err(|_| {
+                anyhow!(
+                    "{} isn't on your PATH; install it to use --memory-profiler",
+                    profiler.binary()
+                )
+            })?;
+            let mut command = Command::new(binary);
+            command.args(profiler.args(output)).arg(&program);
+            command
+        } else if let Some(log) = &strace_log {
+            if std::env::consts::OS != "linux" {
+                return Err(anyhow!("--strace is only available on Linux"));
+            }
+            let binary = which::which("strace")
+                .map_err(|_| anyhow!("strace isn't on your PATH; install it to use --strace"))?;
+            let filter = self
+                .strace_filter
+                .as_deref()
+                .unwrap_or_else(|| version.strace_suggested_filter());
+            let mut command = Command::new(binary);
+            command.args(strace_args(log, filter)).arg(&program);
+            command
+        } else if self.sandboxed {
+            let readable = match &self.exported_game {
+                Some(dir) => dir.clone(),
+                None => config.install_path(&version),
+            };
+            let sandbox = SandboxConfig::for_project(&self.project_dir(), &readable, &program)?;
+            ui::info(&format!(
+                "Sandboxed: writable {}",
+                sandbox
+                    .allowed_rw
+                    .iter()
+                    .map(|p| p.display().to_string())
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            ));
+            sandbox.command(&program)?
+        } else if program.extension().is_some_and(|ext| ext == "app") {
+            // macOS app bundles are directories and must be launched through `open`
+            let mut command = Command::new("open");
+            command.arg("-W").arg(&program);
+            if !args.is_empty() {
+                command.arg("--args");
+            }
+            command
+        } else {
+            Command::new(&program)
+        };
+
+        let virtual_display = if self.gui_server {
+            start_gui_server(self.xvfb_binary.as_deref())?
+        } else {
+            None
+        };
+        if virtual_display.is_some() {
+            command.env("DISPLAY", XVFB_DISPLAY);
+        }
+
+        let physics_override = match self.physics {
+            Some(backend) => Some(PhysicsOverride::apply(
+                &self.project_dir(),
+                &version,
+                backend,
+            )?),
+            None => None,
+        };
+        let status = wait_ignoring_interrupts(command.args(&args));
+        drop(virtual_display);
+        drop(physics_override);
+        let status = status?;
+
+        if let Some((profiler, output)) = &profile {
+            if output.exists() {
+                ui::success(&format!("Memory profile written to {}", output.display()));
+                ui::info(&format!(
+                    "Analyze it with: {}",
+                    profiler.analyze_command(output)
+                ));
+            } else {
+                ui::warning(&format!(
+                    "{} wrote no profile to {}",
+                    profiler.binary(),
+                    output.display()
+                ));
+            }
+        }
+
+        if let Some(log) = &strace_log {
+            ui::success(&format!("strace log written to {}", log.display()));
+        }
+
+        if !status.success() {
+            std::process::exit(status.code().unwrap_or(1));
+        }
+
+        Ok(())
+    }
+
+    /// Refuse to open a project made with a newer Godot than `version`, which
+    /// Godot can't load (or would need to upgrade)
+    fn check_project_compatibility(&self, version: &GodotVersion) -> Result<()> {
+        let project_file = self.project_dir().join(PROJECT_FILE);
+        if !project_file.exists() {
+            return Ok(());
+        }
+
+        let Some(project_version) = GodotVersion::from_project_file(&project_file)? else {
+            return Ok(());
+        };
+
+        if version.version.major > project_version.version.major {
+            ui::warning(&format!(
+                "This project was made with Godot {}.{}; Godot v{} will convert it when opened",
+                project_version.version.major, project_version.version.minor, version
+            ));
+        } else if !version.can_open_project(&project_version) {
+            let message = format!(
+                "This project requires Godot {}.{}, but the active version is v{}",
+                project_version.version.major, project_version.version.minor, version
+            );
+            if !self.force_open {
+                return Err(anyhow!(
+                    "{}\nUse 'gdenv use' to switch versions, or --force-open to open it anyway",
+                    message
+                ));
+            }
+            ui::warning(&message);
+        }
+
+        Ok(())
+    }
+
+    /// Godot opens the project in `--path <dir>` or the current directory
+    fn project_dir(&self) -> PathBuf {
+        self.args
+            .iter()
+            .position(|arg| arg == "--path")
+            .and_then(|i| self.args.get(i + 1))
+            .map(PathBuf::from)
+            .unwrap_or_else(|| PathBuf::from("."))
+    }
+}
+
+/// Parse a `--display-scale` factor, which must be a finite number above zero
+fn parse_display_scale(factor: &str) -> Result<f32, String> {
+    match factor.parse::<f32>() {
+        Ok(factor) if factor.is_finite() && factor > 0.0 => Ok(factor),
+        _ => Err(format!("'{}' isn't a positive scale factor", factor)),
+    }
+}
+
+/// The strace arguments that trace `filter` ("all" for every call) in Godot and the
+/// processes it starts into `log`, with timestamps
+fn strace_args(log: &Path, filter: &str) -> Vec<OsString> {
+    let mut args: Vec<OsString> = vec!["-o".into(), log.into(), "-f".into(), "-tt".into()];
+    if filter != "all" {
+        args.push("-e".into());
+        args.push(format!("trace={}", filter).into());
+    }
+    args
+}
+
+/// The X display and screen (width x height x depth) of the Xvfb server for `--gui-server`
+const XVFB_DISPLAY: &str = ":99";
+const XVFB_SCREEN: &str = "1920x1080x24";
+
+/// How long Xvfb gets to open its display before gdenv gives up
+const XVFB_STARTUP_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// Start a virtual display for Godot, or `None` where every session already has one
+fn start_gui_server(xvfb_binary: Option<&Path>) -> Result<Option<VirtualDisplay>> {
+    match std::env::consts::OS {
+        "macos" => Err(anyhow!(
+            "There's no Xvfb on macOS; run Godot without a display instead: gdenv run -- --headless"
+        )),
+        "windows" => {
+            ui::info("Windows sessions always have a desktop, running Godot on it");
+            Ok(None)
+        }
+        _ => VirtualDisplay::start(xvfb_binary).map(Some),
+    }
+}
+
+/// An Xvfb server started for one run, stopped when dropped
+struct VirtualDisplay {
+    server: Child,
+}
+
+impl VirtualDisplay {
+    fn start(xvfb_binary: Option<&Path>) -> Result<Self> {
+        let binary = match xvfb_binary {
+            Some(path) => path.to_path_buf(),
+            None => which::which("Xvfb").map_err(|_| {
+                anyhow!("Xvfb isn't on your PATH; install it (usually the xvfb package) or pass --xvfb-binary")
+            })?,
+        };
+
+        // X servers create a socket per display, so an existing one means it's taken
+        let socket = Path::new("/tmp/.X11-unix").join(format!("X{}", &XVFB_DISPLAY[1..]));
+        if socket.exists() {
+            return Err(anyhow!(
+                "Display {} is already in use by another X server",
+                XVFB_DISPLAY
+            ));
+        }
+
+        let mut server = Command::new(&binary)
+            .args([XVFB_DISPLAY, "-screen", "0", XVFB_SCREEN])
+            .stdout(Stdio::null())
+            .stderr(Stdio::null())
+            .spawn()
+            .map_err(|e| anyhow!("Could not start {}: {}", binary.display(), e))?;
+
+        let start = Instant::now();
+        while !socket.exists() {
+            if let Some(status) = server.try_wait()? {
+                return Err(anyhow!(
+                    "{} exited with {} before opening display {}",
+                    binary.display(),
+                    status,
+                    XVFB_DISPLAY
+                ));
+            }
+            if start.elapsed() > XVFB_STARTUP_TIMEOUT {
+                let _ = server.kill();
+                return Err(anyhow!(
+                    "{} didn't open display {} within {}s",
+                    binary.display(),
+                    XVFB_DISPLAY,
+                    XVFB_STARTUP_TIMEOUT.as_secs()
+                ));
+            }
+            std::thread::sleep(Duration::from_millis(50));
+        }
+
+        ui::info(&format!(
+            "Started Xvfb on display {} ({})",
+            XVFB_DISPLAY, XVFB_SCREEN
+        ));
+        Ok(Self { server })
+    }
+}
+
+impl Drop for VirtualDisplay {
+    fn drop(&mut self) {
+        let _ = self.server.kill();
+        let _ = self.server.wait();
+    }
+}
+
+/// The file Godot reads project setting overrides from, next to `project.godot`
+const OVERRIDE_FILE: &str = "override.cfg";
+
+/// An `override.cfg` selecting a physics engine for one run, undone when dropped
+struct PhysicsOverride {
+    path: PathBuf,
+    original: Option<String>,
+}
+
+impl PhysicsOverride {
+    fn apply(project_dir: &Path, version: &GodotVersion, backend: PhysicsBackend) -> Result<Self> {
+        let engine = version.physics_engine_setting(backend).ok_or_else(|| {
+            anyhow!(
+                "Godot v{} can't use {} physics (its default is {})",
+                version,
+                backend,
+                version.default_physics_engine()
+            )
+        })?;
+        if backend == PhysicsBackend::Jolt
+            && engine == "JoltPhysics3D"
+            && !project::uses_jolt_addon(project_dir)
+        {
+            ui::warning(&format!(
+                "Jolt needs the godot-jolt addon in {} before Godot 4.4",
+                project::JOLT_ADDON_DIR
+            ));
+        }
+
+        let path = project_dir.join(OVERRIDE_FILE);
+        let original = fs::read_to_string(&path).ok();
+        fs::write(&path, with_physics_engine(original.as_deref(), engine))?;
+        ui::info(&format!("Using {} physics", backend));
+
+        Ok(Self { path, original })
+    }
+}
+
+impl Drop for PhysicsOverride {
+    fn drop(&mut self) {
+        let restored = match &self.original {
+            Some(content) => fs::write(&self.path, content),
+            None => fs::remove_file(&self.path),
+        };
+        if let Err(e) = restored {
+            ui::warning(&format!("Could not restore {}: {}", self.path.display(), e));
+        }
+    }
+}
+
+/// Run Godot and wait for it to exit. Ctrl+C reaches Godot too, so on Unix gdenv ignores
+/// SIGINT meanwhile to outlive it and clean up (the physics override, the virtual display).
+/// Godot is started first because ignored signals stay ignored across `exec`.
+fn wait_ignoring_interrupts(command: &mut Command) -> io::Result<ExitStatus> {
+    let mut child = command.spawn()?;
+
+    #[cfg(unix)]
+    {
+        let previous = unsafe { libc::signal(libc::SIGINT, libc::SIG_IGN) };
+        let status = child.wait();
+        unsafe { libc::signal(libc::SIGINT, previous) };
+        status
+    }
+
+    #[cfg(not(unix))]
+    child.wait()
+}
+
+/// Add a setting for the 3D physics engine to an override file; Godot takes the last
+/// value of a setting that appears more than once
+fn with_physics_engine(original: Option<&str>, engine: &str) -> String {
+    let mut content = original.unwrap_or_default().to_string();
+    if !content.is_empty() && !content.ends_with('\n') {
+        content.push('\n');
+    }
+    content.push_str(&format!("[physics]\n3d/physics_engine=\"{}\"\n", engine));
+    content
+}
+
+/// The absolute path of the profile `profiler` writes for `version`
+fn profiled_output(profiler: MemProfiler, version: &GodotVersion) -> Result<PathBuf> {
+    Ok(std::env::current_dir()?.join(version.memory_profile_output_path(profiler)))
+}
+
+/// System directories Godot needs to read to start (shared libraries, fonts, GPU drivers)
+#[cfg(not(target_os = "macos"))]
+const SYSTEM_READ_DIRS: &[&str] = &[
+    "/usr", "/lib", "/lib32", "/lib64", "/bin", "/etc", "/sys", "/opt",
+];
+#[cfg(target_os = "macos")]
+const SYSTEM_READ_DIRS: &[&str] = &[
+    "/System",
+    "/Library",
+    "/usr",
+    "/bin",
+    "/private/etc",
+    "/dev",
+];
+
+/// Godot's own user config and data directories (editor settings, `user://` data),
+/// which a sandboxed Godot still writes to
+fn godot_user_dirs() -> Vec<PathBuf> {
+    let godot_dir = if cfg!(target_os = "linux") {
+        "godot"
+    } else {
+        "Godot"
+    };
+    [dirs::config_dir(), dirs::data_dir()]
+        .into_iter()
+        .flatten()
+        .map(|dir| dir.join(godot_dir))
+        .collect()
+}
+
+/// The paths a sandboxed Godot may access; everything else is hidden or denied
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SandboxConfig {
+    pub allowed_rw: Vec<PathBuf>,
+    pub allowed_ro: Vec<PathBuf>,
+}
+
+impl SandboxConfig {
+    /// Allow writing to the project, the temp directory, the session's runtime directory
+    /// (Wayland, PulseAudio and D-Bus sockets) and Godot's user directories, and reading
+    /// `install_dir`, the directory of `program` (adopted binaries live outside their
+    /// installation), `/run` and the system directories Godot loads libraries from
+    pub fn for_project(project_dir: &Path, install_dir: &Path, program: &Path) -> Result<Self> {
+        let mut allowed_rw = vec![
+            project_dir.canonicalize()?,
+            std::env::temp_dir().canonicalize()?,
+        ];
+        if let Some(runtime_dir) = std::env::var_os("XDG_RUNTIME_DIR") {
+            let runtime_dir = PathBuf::from(runtime_dir);
+            if runtime_dir.is_dir() {
+                allowed_rw.push(runtime_dir);
+            }
+        }
+        // Godot creates these on its first run anyway, and they must exist to be bound
+        for dir in godot_user_dirs() {
+            fs::create_dir_all(&dir)?;
+            allowed_rw.push(dir);
+        }
+
+        let mut allowed_ro = vec![install_dir.canonicalize()?];
+        if let Some(program_dir) = program.canonicalize()?.parent() {
+            if !allowed_ro.iter().any(|dir| program_dir.starts_with(dir)) {
+                allowed_ro.push(program_dir.to_path_buf());
+            }
+        }
+        allowed_ro.extend(
+            SYSTEM_READ_DIRS
+                .iter()
+                .chain(&["/run"])
+                .map(PathBuf::from)
+                .filter(|dir| dir.exists()),
+        );
+
+        Ok(Self {
+            allowed_rw,
+            allowed_ro,
+        })
+    }
+
+    /// Build the command that runs `program` inside the sandbox
+    pub fn command(&self, program: &Path) -> Result<Command> {
+        if program.extension().is_some_and(|ext| ext == "app") {
+            return Err(anyhow!(
+                "App bundles can't be sandboxed; run the binary in {}/Contents/MacOS instead",
+                program.display()
+            ));
+        }
+
+        if cfg!(target_os = "linux") {
+            let bwrap = which::which("bwrap").map_err(|_| {
+                anyhow!("--sandboxed needs bubblewrap; install the 'bubblewrap' package")
+            })?;
+            let mut command = Command::new(bwrap);
+            command.args(self.bwrap_args()).arg("--").arg(program);
+            Ok(command)
+        } else if cfg!(target_os = "macos") {
+            let mut command = Command::new("sandbox-exec");
+            command.arg("-p").arg(self.macos_profile()).arg(program);
+            Ok(command)
+        } else {
+            Err(anyhow!(
+                "--sandboxed is not implemented on this platform yet; run without it"
+            ))
+        }
+    }
+
+    /// bubblewrap arguments that mount only the allowed paths into an empty root
+    fn bwrap_args(&self) -> Vec<OsString> {
+        let mut args: Vec<OsString> = ["--die-with-parent", "--dev", "/dev", "--proc", "/proc"]
+            .map(OsString::from)
+            .to_vec();
+
+        for (flag, paths) in [
+            ("--ro-bind", &self.allowed_ro),
+            ("--bind", &self.allowed_rw),
+        ] {
+            for path in paths {
+                args.push(flag.into());
+                args.push(path.into());
+                args.push(path.into());
+            }
+        }
+
+        args
+    }
+
+    /// A sandbox-exec profile that denies file access outside the allowed paths
+    fn macos_profile(&self) -> String {
+        let subpaths = |paths: &[PathBuf]| {
+            paths
+                .iter()
+                .map(|path| {
+                    format!(
+                        "(subpath \"{}\")",
+                        path.display().to_string().replace('"', "\\\"")
+                    )
+                })
+                .collect::<Vec<_>>()
+                .join(" ")
+        };
+
+        format!(
+            "(version 1)\n\
+             (deny default)\n\
+             (allow process* signal sysctl-read mach* ipc* iokit* network* file-read-metadata)\n\
+             (allow file-read* {} {})\n\
+             (allow file-write* {})\n",
+            subpaths(&self.allowed_ro),
+            subpaths(&self.allowed_rw),
+            subpaths(&self.allowed_rw)
+        )
+    }
+}
+
+fn open_web_editor(version: &GodotVersion, project_url: Option<&str>) -> Result<()> {
+    let url = web_editor_launch_url(version, project_url)?;
+    ui::info(&format!("Opening {}", url));
+    opener::open(url.as_str())?;
+    Ok(())
+}
+
+/// The web editor URL, asking it to load `project_url` if given
+fn web_editor_launch_url(version: &GodotVersion, project_url: Option<&str>) -> Result<Url> {
+    let editor_url = version.web_editor_url().ok_or_else(|| {
+        anyhow!(
+            "There is no web editor for Godot v{} (only Godot 4 without .NET)",
+            version
+        )
+    })?;
+
+    Ok(match project_url {
+        Some(project_url) => Url::parse_with_params(&editor_url, [("project", project_url)])?,
+        None => Url::parse(&editor_url)?,
+    })
+}
+
+/// Find the game binary Godot exported for the current platform in `dir`
+fn find_exported_binary(dir: &Path, version: &GodotVersion) -> Result<PathBuf> {
+    let suffix = version.export_binary_suffix(GodotVersion::get_platform_suffix()?);
+
+    for entry in fs::read_dir(dir)? {
+        let path = entry?.path();
+        if let Some(name) = path.file_name().and_then(|n| n.to_str()) {
+            if name.ends_with(suffix) && name.len() > suffix.len() {
+                return Ok(path);
+            }
+        }
+    }
+
+    Err(anyhow!(
+        "No exported binary ending in '{}' found in {}",
+        suffix,
+        dir.display()
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_web_editor_launch_url() {
+        let version = GodotVersion::new("4.2.1", false).unwrap();
+        assert_eq!(
+            web_editor_launch_url(&version, Some("https://example.com/my game.zip"))
+                .unwrap()
+                .as_str(),
+            "https://editor.godotengine.org/releases/4.2.1.stable/?project=https%3A%2F%2Fexample.com%2Fmy+game.zip"
+        );
+
+        let old = GodotVersion::new("3.6", false).unwrap();
+        assert!(web_editor_launch_url(&old, None).is_err());
+    }
+
+    #[test]
+    fn test_sandbox_config() {
+        let project = tempfile::tempdir().unwrap();
+        let install = tempfile::tempdir().unwrap();
+        let program = install.path().join("godot");
+        fs::write(&program, "").unwrap();
+
+        let sandbox = SandboxConfig::for_project(project.path(), install.path(), &program).unwrap();
+        let project = project.path().canonicalize().unwrap();
+        let install = install.path().canonicalize().unwrap();
+        assert_eq!(
+            sandbox.allowed_rw[..2],
+            [
+                project.clone(),
+                std::env::temp_dir().canonicalize().unwrap()
+            ]
+        );
+        for dir in godot_user_dirs() {
+            assert!(sandbox.allowed_rw.contains(&dir), "{}", dir.display());
+        }
+        assert_eq!(sandbox.allowed_ro[0], install);
+        // The program is inside the installation, so its directory isn't listed twice
+        assert!(!sandbox.allowed_ro[1..].contains(&install));
+
+        let args: Vec<_> = sandbox
+            .bwrap_args()
+            .into_iter()
+            .map(|arg| arg.to_string_lossy().into_owned())
+            .collect();
+        let install = install.to_string_lossy().into_owned();
+        let project = project.to_string_lossy().into_owned();
+        assert!(args
+            .windows(3)
+            .any(|w| w == ["--ro-bind", install.as_str(), install.as_str()]));
+        assert!(args
+            .windows(3)
+            .any(|w| w == ["--bind", project.as_str(), project.as_str()]));
+
+        let profile = sandbox.macos_profile();
+        assert!(profile.starts_with("(version 1)\n(deny default)"));
+        assert!(profile.contains(&format!("(allow file-write* (subpath \"{}\")", project)));
+    }
+
+    #[test]
+    fn test_strace_args() {
+        let log = Path::new("/tmp/gdenv_strace_42.log");
+        assert_eq!(
+            strace_args(log, "openat,statx"),
+            [
+                "-o",
+                "/tmp/gdenv_strace_42.log",
+                "-f",
+                "-tt",
+                "-e",
+                "trace=openat,statx"
+            ]
+        );
+        assert_eq!(strace_args(log, "all").len(), 4);
+    }
+
+    #[test]
+    fn test_parse_display_scale() {
+        assert_eq!(parse_display_scale("2"), Ok(2.0));
+        assert_eq!(parse_display_scale("1.25"), Ok(1.25));
+        for factor in ["0", "-1", "NaN", "inf", "big"] {
+            assert!(parse_display_scale(factor).is_err(), "{}", factor);
+        }
+    }
+
+    #[test]
+    fn test_physics_override() {
+        let dir = tempfile::tempdir().unwrap();
+        let override_file = dir.path().join(OVERRIDE_FILE);
+        let v4 = GodotVersion::new("4.2.1", false).unwrap();
+
+        let physics_override =
+            PhysicsOverride::apply(dir.path(), &v4, PhysicsBackend::GodotPhysics).unwrap();
+        assert_eq!(
+            fs::read_to_string(&override_file).unwrap(),
+            "[physics]\n3d/physics_engine=\"GodotPhysics3D\"\n"
+        );
+        drop(physics_override);
+        assert!(!override_file.exists());
+
+        // An existing override file keeps its settings and gets them back afterwards
+        fs::write(&override_file, "[display]\nwindow/size/mode=2").unwrap();
+        let physics_override =
+            PhysicsOverride::apply(dir.path(), &v4, PhysicsBackend::Jolt).unwrap();
+        assert_eq!(
+            fs::read_to_string(&override_file).unwrap(),
+            "[display]\nwindow/size/mode=2\n[physics]\n3d/physics_engine=\"JoltPhysics3D\"\n"
+        );
+        drop(physics_override);
+        assert_eq!(
+            fs::read_to_string(&override_file).unwrap(),
+            "[display]\nwindow/size/mode=2"
+        );
+
+        assert!(PhysicsOverride::apply(dir.path(), &v4, PhysicsBackend::Bullet).is_err());
+    }
+
+    #[cfg(all(unix, not(target_os = "macos")))]
+    #[test]
+    fn test_virtual_display_start_failure() {
+        use std::os::unix::fs::PermissionsExt;
+
+        assert!(VirtualDisplay::start(Some(Path::new("/nonexistent/Xvfb"))).is_err());
+
+        let dir = tempfile::tempdir().unwrap();
+        let xvfb = dir.path().join("Xvfb");
+        fs::write(&xvfb, "#!/bin/sh\nexit 1\n").unwrap();
+        fs::set_permissions(&xvfb, fs::Permissions::from_mode(0o755)).unwrap();
+        let error = VirtualDisplay::start(Some(&xvfb))
+            .err()
+            .unwrap()
+            .to_string();
+        assert!(error.contains("exited"), "{}", error);
+    }
+}
@@ -0,0 +1,52 @@
+use anyhow::{anyhow, Result};
+use clap::Args;
+use std::path::PathBuf;
+
+use crate::{config::Config, github::GitHubClient, godot::GodotVersion, ui};
+
+#[derive(Args)]
+pub struct SourceCommand {
+    /// The Godot version whose source to download, e.g. 4.2.1
+    pub version: String,
+
+    /// File to write the tarball to (default: GitHub's name for it, e.g.
+    /// godot-4.2.1-stable.tar.gz, in the current directory)
+    #[arg(long, short)]
+    pub output: Option<PathBuf>,
+}
+
+impl SourceCommand {
+    pub async fn run(self) -> Result<()> {
+        let config = Config::new()?;
+        let github_client = GitHubClient::from_config(&config);
+        let version: GodotVersion = self.version.parse()?;
+
+        let output = self
+            .output
+            .unwrap_or_else(|| PathBuf::from(version.source_code_file_name()));
+        if output.exists() {
+            return Err(anyhow!("{} already exists", output.display()));
+        }
+
+        let downloaded = github_client
+            .download_with_progress(
+                &version.source_code_url(),
+                &format!("Godot v{} source", version.godot_version_string()),
+                0,
+                &output,
+            )
+            .await;
+        if let Err(e) = downloaded {
+            // Don't leave a partial tarball behind
+            let _ = std::fs::remove_file(&output);
+            return Err(e);
+        }
+
+        ui::success(&format!(
+            "Downloaded the source of Godot v{} to {}",
+            version.godot_version_string(),
+            output.display()
+        ));
+        Ok(())
+    }
+}
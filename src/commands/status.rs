@@ -0,0 +1,57 @@
+use anyhow::Result;
+use clap::Args;
+
+use crate::{config::Config, installer::Installer, lockfile::LockFile, ui};
+
+#[derive(Args)]
+pub struct StatusCommand {
+    /// Print a single line, and only when the directory pins a version (for shell hooks)
+    #[arg(long, short)]
+    pub quiet: bool,
+}
+
+impl StatusCommand {
+    pub async fn run(self) -> Result<()> {
+        let config = Config::new()?;
+        let installer = Installer::new(config);
+        let current_dir = std::env::current_dir()?;
+
+        let lockfile = match LockFile::find(&current_dir) {
+            Some(path) => Some(LockFile::read(&path)?),
+            None => None,
+        };
+
+        if self.quiet {
+            if let Some(lockfile) = lockfile {
+                let suffix = if installer.resolve_pin(&lockfile.pin)?.is_some() {
+                    ""
+                } else {
+                    " (not installed, run 'gdenv install')"
+                };
+                println!("🤖 Godot {}{}", lockfile.pin, suffix);
+            }
+            return Ok(());
+        }
+
+        match &lockfile {
+            Some(lockfile) => {
+                ui::info(&format!(
+                    "Pinned version: {} (from {})",
+                    lockfile.pin,
+                    lockfile.path.display()
+                ));
+                if installer.resolve_pin(&lockfile.pin)?.is_none() {
+                    ui::warning("The pinned version is not installed. Run 'gdenv install'.");
+                }
+            }
+            None => ui::info("No .godot-version found in this directory or its parents"),
+        }
+
+        match installer.get_active_version()? {
+            Some(version) => ui::info(&format!("Active version: {}", version)),
+            None => ui::info("No active version set"),
+        }
+
+        Ok(())
+    }
+}
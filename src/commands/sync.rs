@@ -0,0 +1,80 @@
+use anyhow::{anyhow, Result};
+use clap::Args;
+
+use crate::{
+    commands::install::{install_many, InstallCommand},
+    config::Config,
+    godot::GodotVersion,
+};
+
+#[derive(Args)]
+pub struct SyncCommand {
+    /// The Godot versions to install (a "-dotnet" suffix selects the .NET build)
+    #[arg(required = true)]
+    pub versions: Vec<String>,
+
+    /// Install the .NET builds of all versions
+    #[arg(long)]
+    pub dotnet: bool,
+
+    /// Use the standard build even when the default_dotnet setting is on
+    #[arg(long, conflicts_with = "dotnet")]
+    pub no_dotnet: bool,
+
+    /// Keep installing after a version fails and report all failures at the end (the default)
+    #[arg(long, overrides_with = "fail_fast")]
+    pub keep_going: bool,
+
+    /// Stop at the first version that fails to install
+    #[arg(long, overrides_with = "keep_going")]
+    pub fail_fast: bool,
+
+    /// Show what would be installed without changing anything
+    #[arg(long)]
+    pub dry_run: bool,
+
+    /// With --dry-run, print each version's install plan as a line of JSON
+    #[arg(long, requires = "dry_run")]
+    pub json: bool,
+}
+
+impl SyncCommand {
+    pub async fn run(self) -> Result<()> {
+        let mut config = Config::new()?;
+        let dotnet = config
+            .settings
+            .apply_dotnet_flags(self.dotnet, self.no_dotnet);
+
+        let (dry_run, json) = (self.dry_run, self.json);
+        let summary = install_many(&self.versions, !self.fail_fast, |version| {
+            let config = config.clone();
+            async move {
+                let mut version: GodotVersion = version.parse()?;
+                version.is_dotnet |= dotnet;
+                InstallCommand {
+                    dry_run,
+                    json,
+                    ..InstallCommand::for_version(&version)
+                }
+                .run_with(config)
+                .await
+            }
+        })
+        .await;
+
+        // Keep stdout to the plans; failures still surface as the error below
+        if !json {
+            summary.print(dry_run);
+        }
+
+        if !summary.failed.is_empty() {
+            return Err(anyhow!(
+                "{} of {} versions failed to install",
+                summary.failed.len(),
+                self.versions.len()
+            ));
+        }
+
+        Ok(())
+    }
+}
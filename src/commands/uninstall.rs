@@ -1,30 +1,95 @@
-use anyhow::Result;
+use anyhow::{anyhow, Result};
 use clap::Args;
 use std::io::{self, Write};
+use std::path::{Path, PathBuf};
 
-use crate::{config::Config, godot::GodotVersion, installer::Installer, ui};
+use crate::{
+    catalog::CatalogFile,
+    config::Config,
+    godot::GodotVersion,
+    hooks::{GlobalHooks, HookEvent},
+    installer::{self, Installer},
+    lockfile::LockFile,
+    project, ui,
+};
 
-#[derive(Args)]
+#[derive(Args, Default)]
 pub struct UninstallCommand {
     /// The Godot version to uninstall
-    pub version: String,
+    #[arg(required_unless_present = "unused", conflicts_with = "unused")]
+    pub version: Option<String>,
 
     /// Uninstall the .NET version
     #[arg(long)]
     pub dotnet: bool,
 
+    /// Use the standard build even when the default_dotnet setting is on
+    #[arg(long, conflicts_with = "dotnet")]
+    pub no_dotnet: bool,
+
     /// Skip confirmation prompt
     #[arg(long, short)]
     pub yes: bool,
+
+    /// Uninstall even if the editor appears to be running
+    #[arg(long, short)]
+    pub force: bool,
+
+    /// Uninstall every version that no project pins, except the active version
+    #[arg(long)]
+    pub unused: bool,
+
+    /// Directory to look for projects in with --unused
+    #[arg(long, value_name = "DIR", default_value = ".", requires = "unused")]
+    pub scan: PathBuf,
+
+    /// List the versions --unused would remove without removing them
+    #[arg(long, requires = "unused")]
+    pub dry_run: bool,
+
+    /// Uninstall the build for another platform installed with `install --platform`
+    #[arg(long, value_name = "SUFFIX", conflicts_with = "unused")]
+    pub platform: Option<String>,
 }
 
 impl UninstallCommand {
+    /// An uninstall of exactly `version` that doesn't ask for confirmation, for commands
+    /// that remove a version on the user's behalf
+    pub fn for_version(version: &GodotVersion) -> Self {
+        Self {
+            version: Some(version.godot_version_string()),
+            dotnet: version.is_dotnet,
+            no_dotnet: !version.is_dotnet,
+            yes: true,
+            ..Default::default()
+        }
+    }
+
     pub async fn run(self) -> Result<()> {
-        let config = Config::new()?;
-        let installer = Installer::new(config);
+        self.run_with(Config::new()?, installer::running_executables)
+            .await
+    }
+
+    pub async fn run_with(
+        self,
+        mut config: Config,
+        running_executables: impl FnOnce() -> Vec<PathBuf>,
+    ) -> Result<()> {
+        if self.unused {
+            return self.uninstall_unused(config, running_executables());
+        }
+
+        let is_dotnet = config
+            .settings
+            .apply_dotnet_flags(self.dotnet, self.no_dotnet);
+        let installer = Installer::new(config.clone());
 
-        let is_dotnet = self.dotnet;
-        let target_version = GodotVersion::new(&self.version, is_dotnet)?;
+        // Required by clap unless --unused is given
+        let target_version = GodotVersion::new(self.version.as_deref().unwrap(), is_dotnet)?;
+
+        if let Some(platform) = &self.platform {
+            return self.uninstall_platform_build(&config, &target_version, platform);
+        }
 
         // Check if the version is installed
         let installed_versions = installer.list_installed()?;
@@ -33,6 +98,12 @@ impl UninstallCommand {
             return Ok(());
         }
 
+        if !self.force {
+            installer
+                .ensure_not_running(&target_version, &running_executables())
+                .map_err(|e| anyhow!("{}\nUse --force to uninstall anyway", e))?;
+        }
+
         // Check if it's the active version
         let active_version = installer.get_active_version()?;
         let is_active = active_version.as_ref() == Some(&target_version);
@@ -62,8 +133,7 @@ impl UninstallCommand {
             }
         }
 
-        // Uninstall the version
-        installer.uninstall_version(&target_version)?;
+        uninstall(&config, &installer, &target_version)?;
 
         // If it was the active version, suggest setting a new one
         if is_active {
@@ -84,4 +154,249 @@ impl UninstallCommand {
 
         Ok(())
     }
+
+    /// Remove a build for another platform. Such builds never run here, are never active
+    /// and don't run hooks, so this only needs to delete the directory.
+    fn uninstall_platform_build(
+        &self,
+        config: &Config,
+        version: &GodotVersion,
+        platform: &str,
+    ) -> Result<()> {
+        let install_path = config.platform_install_path(version, platform);
+        if !install_path.exists() {
+            ui::warning(&format!(
+                "Godot v{} for {} is not installed",
+                version, platform
+            ));
+            return Ok(());
+        }
+
+        if !self.yes {
+            print!(
+                "Are you sure you want to uninstall Godot v{} for {}? [y/N]: ",
+                version, platform
+            );
+            io::stdout().flush()?;
+
+            let mut input = String::new();
+            io::stdin().read_line(&mut input)?;
+
+            let confirmed = input.trim().to_lowercase();
+            if confirmed != "y" && confirmed != "yes" {
+                ui::info("Uninstall cancelled");
+                return Ok(());
+            }
+        }
+
+        std::fs::remove_dir_all(&install_path)?;
+        ui::success(&format!("Uninstalled Godot v{} for {}", version, platform));
+        Ok(())
+    }
+
+    fn uninstall_unused(self, config: Config, running_executables: Vec<PathBuf>) -> Result<()> {
+        let installer = Installer::new(config.clone());
+        let unused = unused_versions(&installer, &self.scan, &CatalogFile::load(&config)?)?;
+
+        if unused.is_empty() {
+            ui::success(&format!(
+                "Every installed version is pinned by a project in {} or active",
+                self.scan.display()
+            ));
+            return Ok(());
+        }
+
+        let verb = if self.dry_run {
+            "Would uninstall"
+        } else {
+            "Uninstalling"
+        };
+        ui::info(&format!(
+            "{} {} version(s) no project in {} pins:",
+            verb,
+            unused.len(),
+            self.scan.display()
+        ));
+        for version in &unused {
+            println!("  • {}", version);
+        }
+
+        if self.dry_run {
+            return Ok(());
+        }
+
+        if !self.yes {
+            print!("Uninstall these versions? [y/N]: ");
+            io::stdout().flush()?;
+
+            let mut input = String::new();
+            io::stdin().read_line(&mut input)?;
+
+            let confirmed = input.trim().to_lowercase();
+            if confirmed != "y" && confirmed != "yes" {
+                ui::info("Uninstall cancelled");
+                return Ok(());
+            }
+        }
+
+        for version in &unused {
+            if !self.force {
+                if let Err(e) = installer.ensure_not_running(version, &running_executables) {
+                    ui::warning(&format!("Skipping Godot v{}: {}", version, e));
+                    continue;
+                }
+            }
+            uninstall(&config, &installer, version)?;
+        }
+
+        Ok(())
+    }
+}
+
+/// Remove an installation and run the post-uninstall hooks
+fn uninstall(config: &Config, installer: &Installer, version: &GodotVersion) -> Result<()> {
+    installer.uninstall_version(version)?;
+
+    let install_path = config.install_path(version);
+    GlobalHooks::load(config)?.run(HookEvent::PostUninstall, version, &install_path)
+}
+
+/// The installed versions that no project under `scan_root` pins, that aren't active
+/// and that the catalog doesn't retain
+fn unused_versions(
+    installer: &Installer,
+    scan_root: &Path,
+    catalog_file: &CatalogFile,
+) -> Result<Vec<GodotVersion>> {
+    let mut pins = Vec::new();
+    for project_dir in project::scan(scan_root)? {
+        if let Some(lockfile_path) = LockFile::find(&project_dir) {
+            pins.push(LockFile::read_pin(&lockfile_path)?);
+        }
+    }
+    let active = installer.get_active_version()?;
+
+    let mut unused: Vec<GodotVersion> = installer
+        .list_installed()?
+        .into_iter()
+        .filter(|version| !pins.iter().any(|pin| pin.allows(version)))
+        .filter(|version| active.as_ref() != Some(version))
+        .filter(|version| !catalog_file.retains(version))
+        .collect();
+    unused.sort();
+    Ok(unused)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::installer::fake_install;
+    use std::fs;
+
+    #[tokio::test]
+    async fn test_uninstall_refuses_running_editor() {
+        let root = tempfile::tempdir().unwrap();
+        let config = Config::with_data_dir(root.path());
+
+        let version = GodotVersion::new("4.2.1", false).unwrap();
+        let executable = fake_install(&config, &version, "");
+        let running = executable.canonicalize().unwrap();
+
+        let command = UninstallCommand::for_version(&version);
+        let err = command
+            .run_with(config.clone(), || vec![running])
+            .await
+            .unwrap_err();
+
+        assert!(err.to_string().contains("currently open"));
+        assert!(executable.exists());
+    }
+
+    #[tokio::test]
+    async fn test_uninstall_platform_build() {
+        let root = tempfile::tempdir().unwrap();
+        let config = Config::with_data_dir(root.path());
+        let installer = Installer::new(config.clone());
+
+        let version = GodotVersion::new("4.2.1", false).unwrap();
+        let platform_path = config.platform_install_path(&version, "linux.x86_64");
+        fs::create_dir_all(&platform_path).unwrap();
+        fs::create_dir_all(config.install_path(&version)).unwrap();
+
+        let command = UninstallCommand {
+            platform: Some("linux.x86_64".to_string()),
+            ..UninstallCommand::for_version(&version)
+        };
+        command.run_with(config.clone(), Vec::new).await.unwrap();
+
+        assert!(!platform_path.exists());
+        assert!(installer.list_platform_builds().unwrap().is_empty());
+        // The build for this platform stays
+        assert!(config.install_path(&version).exists());
+    }
+
+    #[tokio::test]
+    async fn test_uninstall_unused() {
+        let root = tempfile::tempdir().unwrap();
+        let config = Config::with_data_dir(root.path());
+        let installer = Installer::new(config.clone());
+
+        let versions = ["4.2.1", "4.1.0", "4.3"].map(|v| GodotVersion::new(v, false).unwrap());
+        for version in &versions {
+            fake_install(&config, version, "");
+        }
+        fs::create_dir_all(&config.bin_dir).unwrap();
+        installer.set_active_version(&versions[2]).unwrap();
+
+        let projects = tempfile::tempdir().unwrap();
+        for name in ["game", "jam/entry"] {
+            let dir = projects.path().join(name);
+            fs::create_dir_all(&dir).unwrap();
+            fs::write(dir.join(project::PROJECT_FILE), "").unwrap();
+            LockFile::write(
+                &dir.join(crate::lockfile::LOCKFILE_NAME),
+                &versions[0],
+                crate::dry_run::DryRunMode::Off,
+            )
+            .unwrap();
+        }
+
+        assert_eq!(
+            unused_versions(&installer, projects.path(), &CatalogFile::default()).unwrap(),
+            [versions[1].clone()]
+        );
+
+        // Pinned versions stay, unless pruning.keep_pinned is off
+        let mut catalog_file = CatalogFile::default();
+        catalog_file.pin(&versions[1]);
+        assert!(unused_versions(&installer, projects.path(), &catalog_file)
+            .unwrap()
+            .is_empty());
+        catalog_file.pruning.keep_pinned = false;
+        assert_eq!(
+            unused_versions(&installer, projects.path(), &catalog_file).unwrap(),
+            [versions[1].clone()]
+        );
+
+        let command = |dry_run| UninstallCommand {
+            yes: true,
+            unused: true,
+            scan: projects.path().to_path_buf(),
+            dry_run,
+            ..Default::default()
+        };
+        command(true)
+            .run_with(config.clone(), Vec::new)
+            .await
+            .unwrap();
+        assert!(installer.is_installed(&versions[1]));
+
+        command(false)
+            .run_with(config.clone(), Vec::new)
+            .await
+            .unwrap();
+        let mut installed = installer.list_installed().unwrap();
+        installed.sort();
+        assert_eq!(installed, [versions[0].clone(), versions[2].clone()]);
+    }
 }
@@ -13,26 +13,30 @@ pub struct UpdateCommand {
 impl UpdateCommand {
     pub async fn run(self) -> Result<()> {
         let config = Config::new()?;
-        let github_client = GitHubClient::new(config.github_api_url);
+        let github_client = GitHubClient::from_config(&config);
 
         ui::info("Updating available Godot versions...");
 
-        // Fetch releases from GitHub
-        let releases = github_client.get_godot_releases(true).await?;
+        // Fetch releases from GitHub, replacing the cached list
+        let releases = github_client.refresh_godot_releases().await?;
 
         ui::success(&format!("Found {} Godot releases", releases.len()));
 
         // Show latest stable and prerelease versions
-        let stable_releases: Vec<_> = releases.iter().filter(|r| !r.prerelease).collect();
-        let prerelease_releases: Vec<_> = releases.iter().filter(|r| r.prerelease).collect();
-
-        if let Some(latest_stable) = stable_releases.first() {
+        let latest = |prerelease: bool| {
+            releases
+                .iter()
+                .filter(|r| r.prerelease == prerelease)
+                .max_by_key(|r| r.published_at)
+        };
+
+        if let Some(latest_stable) = latest(false) {
             if let Some(version) = latest_stable.version() {
                 ui::info(&format!("Latest stable: {}", version));
             }
         }
 
-        if let Some(latest_prerelease) = prerelease_releases.first() {
+        if let Some(latest_prerelease) = latest(true) {
             if let Some(version) = latest_prerelease.version() {
                 ui::info(&format!("Latest prerelease: {}", version));
             }
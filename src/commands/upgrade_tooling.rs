@@ -0,0 +1,356 @@
+use anyhow::{anyhow, Result};
+use clap::Args;
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use crate::{
+    commands::{install::InstallCommand, uninstall::UninstallCommand},
+    config::Config,
+    github::GitHubClient,
+    godot::GodotVersion,
+    installer::{self, Installer},
+    ui,
+};
+
+/// Progress of an upgrade, kept in the gdenv root so an interrupted upgrade can resume
+const STATE_FILE: &str = ".gdenv_upgrade_state";
+
+#[derive(Args)]
+pub struct UpgradeToolingCommand {
+    /// Don't check for a newer gdenv
+    #[arg(long)]
+    pub skip_self_update: bool,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+struct UpgradeStep {
+    from: GodotVersion,
+    to: GodotVersion,
+    /// The new version has been installed
+    installed: bool,
+    /// The old version has been uninstalled
+    removed: bool,
+}
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct UpgradeState {
+    steps: Vec<UpgradeStep>,
+}
+
+impl UpgradeState {
+    fn path(config: &Config) -> PathBuf {
+        config.root_dir.join(STATE_FILE)
+    }
+
+    fn load(path: &Path) -> Result<Option<Self>> {
+        if !path.exists() {
+            return Ok(None);
+        }
+        let content = fs::read_to_string(path)?;
+        Ok(Some(serde_json::from_str(&content)?))
+    }
+
+    fn save(&self, path: &Path) -> Result<()> {
+        fs::write(path, serde_json::to_string_pretty(self)?)?;
+        Ok(())
+    }
+}
+
+impl UpgradeToolingCommand {
+    pub async fn run(self) -> Result<()> {
+        let config = Config::new()?;
+        let github_client = GitHubClient::from_config(&config);
+        let installer = Installer::new(config.clone());
+
+        if !self.skip_self_update {
+            if let Err(e) = self_update(&github_client).await {
+                ui::warning(&format!("Could not update gdenv: {}", e));
+            }
+        }
+
+        let state_path = UpgradeState::path(&config);
+        let mut state = match UpgradeState::load(&state_path)? {
+            Some(state) => {
+                ui::info("Resuming an interrupted upgrade");
+                state
+            }
+            None => {
+                let available: Vec<GodotVersion> = github_client
+                    .get_godot_releases(false)
+                    .await?
+                    .iter()
+                    .filter_map(|r| r.version())
+                    .filter_map(|v| GodotVersion::new(&v, false).ok())
+                    .collect();
+                UpgradeState {
+                    steps: plan_upgrades(&installer.list_installed()?, &available),
+                }
+            }
+        };
+
+        if state.steps.is_empty() {
+            ui::success("All installed Godot versions are up to date");
+            return Ok(());
+        }
+
+        state.save(&state_path)?;
+        let active_version = installer.get_active_version()?;
+
+        // Install every new version first, so a failure leaves the old ones untouched
+        for i in 0..state.steps.len() {
+            if state.steps[i].installed {
+                continue;
+            }
+
+            let to = state.steps[i].to.clone();
+            let install = InstallCommand {
+                check_disk_space: true,
+                ..InstallCommand::for_version(&to)
+            };
+
+            if let Err(e) = install.run_with(config.clone()).await {
+                ui::error(&format!("Failed to install Godot v{}, rolling back", to));
+                rollback(&installer, &state);
+                fs::remove_file(&state_path)?;
+                return Err(e);
+            }
+
+            state.steps[i].installed = true;
+            state.save(&state_path)?;
+        }
+
+        for i in 0..state.steps.len() {
+            if state.steps[i].removed {
+                continue;
+            }
+
+            let UpgradeStep { from, to, .. } = state.steps[i].clone();
+            if active_version.as_ref() == Some(&from) {
+                installer.set_active_version(&to)?;
+            }
+
+            UninstallCommand::for_version(&from)
+                .run_with(config.clone(), installer::running_executables)
+                .await?;
+
+            state.steps[i].removed = true;
+            state.save(&state_path)?;
+        }
+
+        fs::remove_file(&state_path)?;
+
+        println!("\n📋 Upgraded Godot versions:");
+        println!("  {:<24} After", "Before");
+        for step in &state.steps {
+            println!("  {:<24} {}", step.from.to_string(), step.to);
+        }
+
+        ui::success("Upgrade complete");
+        Ok(())
+    }
+}
+
+/// Pair each installed stable version with the newest stable patch release of its minor series
+fn plan_upgrades(installed: &[GodotVersion], available: &[GodotVersion]) -> Vec<UpgradeStep> {
+    installed
+        .iter()
+        .filter(|from| !from.is_prerelease())
+        .filter_map(|from| {
+            let newest = available
+                .iter()
+                .filter(|v| !v.is_prerelease())
+                .filter(|v| {
+                    v.version.major == from.version.major && v.version.minor == from.version.minor
+                })
+                .max_by(|a, b| a.version.cmp(&b.version))?;
+
+            let to = GodotVersion::new(&newest.godot_version_string(), from.is_dotnet).ok()?;
+            let already_installed = installed.contains(&to);
+            (to.version > from.version && !already_installed).then(|| UpgradeStep {
+                from: from.clone(),
+                to,
+                installed: false,
+                removed: false,
+            })
+        })
+        .collect()
+}
+
+/// Uninstall the versions this upgrade installed
+fn rollback(installer: &Installer, state: &UpgradeState) {
+    for step in state.steps.iter().filter(|s| s.installed && !s.removed) {
+        if let Err(e) = installer.uninstall_version(&step.to) {
+            ui::warning(&format!("Failed to remove Godot v{}: {}", step.to, e));
+        }
+    }
+}
+
+/// Replace the running gdenv with the binary from its latest release, if that's newer
+async fn self_update(github_client: &GitHubClient) -> Result<()> {
+    let release = github_client.get_latest_gdenv_release().await?;
+    let tag = release.tag_name.trim_start_matches('v');
+    let latest = semver::Version::parse(tag)?;
+    let current = semver::Version::parse(env!("CARGO_PKG_VERSION"))?;
+
+    if latest <= current {
+        ui::success(&format!("gdenv {} is up to date", current));
+        return Ok(());
+    }
+
+    let asset_name = release_asset_name(std::env::consts::OS, std::env::consts::ARCH);
+    let asset = release
+        .assets
+        .iter()
+        .find(|asset| asset.name == asset_name)
+        .ok_or_else(|| {
+            anyhow!(
+                "gdenv {} has no {} build; download it from https://github.com/bytemeadow/gdenv/releases",
+                latest,
+                asset_name
+            )
+        })?;
+
+    ui::info(&format!("Updating gdenv {} -> {}", current, latest));
+    // Download next to the executable, so the rename that replaces it stays on one filesystem
+    let executable = std::env::current_exe()?;
+    let downloaded = executable.with_file_name(format!(".{}.new", asset.name));
+    let result = async {
+        github_client
+            .download_with_progress(
+                &asset.browser_download_url,
+                &asset.name,
+                asset.size,
+                &downloaded,
+            )
+            .await?;
+        replace_executable(&downloaded, &executable)
+    }
+    .await;
+    if result.is_err() {
+        let _ = fs::remove_file(&downloaded);
+    }
+    result?;
+
+    ui::success(&format!("Updated gdenv to {}", latest));
+    Ok(())
+}
+
+/// The name of the release asset with the gdenv binary for an OS and architecture (as in
+/// `std::env::consts`), as the release workflow names them, e.g. "gdenv-linux-x86_64"
+fn release_asset_name(os: &str, arch: &str) -> String {
+    let extension = if os == "windows" { ".exe" } else { "" };
+    format!("gdenv-{}-{}{}", os, arch, extension)
+}
+
+/// Move a downloaded gdenv over the running executable. Windows won't let a running
+/// executable be overwritten, but does let it be renamed out of the way first.
+fn replace_executable(downloaded: &Path, executable: &Path) -> Result<()> {
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+        fs::set_permissions(downloaded, fs::Permissions::from_mode(0o755))?;
+    }
+
+    #[cfg(windows)]
+    {
+        let old = executable.with_extension("old.exe");
+        let _ = fs::remove_file(&old);
+        fs::rename(executable, &old)?;
+    }
+
+    fs::rename(downloaded, executable)?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn v(version: &str, is_dotnet: bool) -> GodotVersion {
+        GodotVersion::new(version, is_dotnet).unwrap()
+    }
+
+    #[test]
+    fn test_plan_upgrades() {
+        let installed = vec![
+            v("4.1.1", false),
+            v("4.2.1", true),
+            v("4.3", false),
+            v("4.4-beta1", false),
+        ];
+        let available = vec![
+            v("4.1.4", false),
+            v("4.1.3", false),
+            v("4.2.2", false),
+            v("4.2.3-rc1", false),
+            v("4.3", false),
+            v("4.4", false),
+        ];
+
+        let plan: Vec<_> = plan_upgrades(&installed, &available)
+            .into_iter()
+            .map(|s| (s.from, s.to))
+            .collect();
+        assert_eq!(
+            plan,
+            [
+                (v("4.1.1", false), v("4.1.4", false)),
+                (v("4.2.1", true), v("4.2.2", true)),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_release_asset_name() {
+        assert_eq!(release_asset_name("linux", "x86_64"), "gdenv-linux-x86_64");
+        assert_eq!(
+            release_asset_name("macos", "aarch64"),
+            "gdenv-macos-aarch64"
+        );
+        assert_eq!(
+            release_asset_name("windows", "x86_64"),
+            "gdenv-windows-x86_64.exe"
+        );
+    }
+
+    #[test]
+    fn test_replace_executable() {
+        let dir = tempfile::tempdir().unwrap();
+        let executable = dir.path().join("gdenv");
+        let downloaded = dir.path().join(".gdenv-linux-x86_64.new");
+        fs::write(&executable, "old").unwrap();
+        fs::write(&downloaded, "new").unwrap();
+
+        replace_executable(&downloaded, &executable).unwrap();
+        assert_eq!(fs::read_to_string(&executable).unwrap(), "new");
+        assert!(!downloaded.exists());
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::PermissionsExt;
+            let mode = fs::metadata(&executable).unwrap().permissions().mode();
+            assert_eq!(mode & 0o111, 0o111);
+        }
+    }
+
+    #[test]
+    fn test_state_round_trip() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join(STATE_FILE);
+        assert!(UpgradeState::load(&path).unwrap().is_none());
+
+        let state = UpgradeState {
+            steps: vec![UpgradeStep {
+                from: v("4.2.1", false),
+                to: v("4.2.2", false),
+                installed: true,
+                removed: false,
+            }],
+        };
+        state.save(&path).unwrap();
+        assert_eq!(
+            UpgradeState::load(&path).unwrap().unwrap().steps,
+            state.steps
+        );
+    }
+}
@@ -0,0 +1,137 @@
+use anyhow::{anyhow, Result};
+use clap::Args;
+
+use crate::{
+    config::Config,
+    godot::{GodotVersion, Source},
+};
+
+/// The `--platform` value that lists every platform
+const ALL_PLATFORMS: &str = "all";
+
+#[derive(Args)]
+pub struct UrlCommand {
+    /// The Godot version, e.g. 4.2.1 (a "-dotnet" suffix selects the .NET build)
+    pub version: String,
+
+    /// Use the .NET build of the version
+    #[arg(long)]
+    pub dotnet: bool,
+
+    /// Use the standard build even when the default_dotnet setting is on
+    #[arg(long, conflicts_with = "dotnet")]
+    pub no_dotnet: bool,
+
+    /// Platform suffix to print the URL for, e.g. "linux.x86_64", or "all" to list the
+    /// archive name and URL of every platform, standard and .NET (default: this platform)
+    #[arg(long)]
+    pub platform: Option<String>,
+
+    /// Download source to build the URL for (default: the prefer_source setting)
+    #[arg(long, value_enum)]
+    pub source: Option<Source>,
+}
+
+impl UrlCommand {
+    pub async fn run(self) -> Result<()> {
+        let mut config = Config::new()?;
+        let source = self.source.unwrap_or(config.settings.prefer_source);
+        let version: GodotVersion = self.version.parse()?;
+        let version = GodotVersion {
+            is_dotnet: version.is_dotnet
+                || config
+                    .settings
+                    .apply_dotnet_flags(self.dotnet, self.no_dotnet),
+            ..version
+        };
+
+        match self.platform.as_deref() {
+            Some(ALL_PLATFORMS) => {
+                for line in platform_lines(&version, source) {
+                    println!("{}", line);
+                }
+            }
+            Some(platform) => {
+                if !version.platform_suffixes().contains(&platform) {
+                    return Err(anyhow!(
+                        "Godot v{} has no '{}' builds (available: {}, or '{}')",
+                        version,
+                        platform,
+                        version.platform_suffixes().join(", "),
+                        ALL_PLATFORMS
+                    ));
+                }
+                println!("{}", version.download_url_for_platform(source, platform));
+            }
+            None => println!("{}", version.download_url_for_source(source)?),
+        }
+
+        Ok(())
+    }
+}
+
+/// A tab-separated "<platform> <archive name> <url>" line for the standard and the .NET
+/// build of every platform the version is published for
+fn platform_lines(version: &GodotVersion, source: Source) -> Vec<String> {
+    let builds = [false, true].map(|is_dotnet| GodotVersion {
+        is_dotnet,
+        ..version.clone()
+    });
+
+    version
+        .platform_suffixes()
+        .iter()
+        .flat_map(|platform| {
+            builds.iter().map(move |build| {
+                let platform_label = if build.is_dotnet {
+                    format!("{}-dotnet", platform)
+                } else {
+                    platform.to_string()
+                };
+                format!(
+                    "{}\t{}\t{}",
+                    platform_label,
+                    build.archive_name_for_platform(platform),
+                    build.download_url_for_platform(source, platform)
+                )
+            })
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_platform_lines() {
+        let version = GodotVersion::new("4.2.1", false).unwrap();
+        let lines = platform_lines(&version, Source::GitHub);
+        assert_eq!(lines.len(), version.platform_suffixes().len() * 2);
+        assert!(!lines.iter().any(|line| line.contains("windows_arm64")));
+
+        let platforms: Vec<&str> = lines
+            .iter()
+            .map(|line| line.split('\t').next().unwrap())
+            .collect();
+        assert_eq!(platforms[..2], ["win64.exe", "win64.exe-dotnet"]);
+        assert!(platforms.contains(&"linux.arm64"));
+        assert!(lines.contains(
+            &"linux.x86_64-dotnet\tGodot_v4.2.1-stable_mono_linux_x86_64.zip\t\
+              https://github.com/godotengine/godot-builds/releases/download/4.2.1-stable/Godot_v4.2.1-stable_mono_linux_x86_64.zip"
+                .to_string()
+        ));
+
+        let with_windows_arm = GodotVersion::new("4.3", false).unwrap();
+        assert_eq!(
+            platform_lines(&with_windows_arm, Source::GitHub).len(),
+            GodotVersion::all_platform_suffixes().len() * 2
+        );
+
+        let legacy = GodotVersion::new("3.5.3", false).unwrap();
+        assert_eq!(
+            platform_lines(&legacy, Source::GitHub).len(),
+            GodotVersion::all_legacy_platform_suffixes().len() * 2
+        );
+    }
+}
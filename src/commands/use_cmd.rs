@@ -1,23 +1,47 @@
-use anyhow::Result;
+use anyhow::{anyhow, Result};
 use clap::Args;
-
-use crate::{config::Config, godot::GodotVersion, installer::Installer, ui};
+use std::path::{Path, PathBuf};
+
+use crate::{
+    config::Config,
+    dry_run::DryRunMode,
+    godot::GodotVersion,
+    installer::Installer,
+    lockfile::{LockFile, LOCKFILE_NAME},
+    project::PROJECT_FILE,
+    ui,
+};
 
 #[derive(Args)]
 pub struct UseCommand {
-    /// The Godot version to switch to
+    /// The Godot version to switch to, e.g. 4.2.1, or 4.2 for the newest installed 4.2.x
     /// If not provided, reads from .godot-version file
     pub version: Option<String>,
 
     /// Use the .NET version
     #[arg(long)]
     pub dotnet: bool,
+
+    /// Use the standard build even when the default_dotnet setting is on
+    #[arg(long, conflicts_with = "dotnet")]
+    pub no_dotnet: bool,
+
+    /// Also set the version in the project.godot and .godot-version of the current directory
+    #[arg(long)]
+    pub update_project: bool,
+
+    /// Print the installed version and executable the version resolves to without switching
+    #[arg(long, conflicts_with = "update_project")]
+    pub resolve: bool,
 }
 
 impl UseCommand {
     pub async fn run(self) -> Result<()> {
-        let config = Config::new()?;
-        let installer = Installer::new(config);
+        let mut config = Config::new()?;
+        let is_dotnet = config
+            .settings
+            .apply_dotnet_flags(self.dotnet, self.no_dotnet);
+        let installer = Installer::new(config.clone());
 
         // Get the version to use
         let version_string = match self.version {
@@ -28,13 +52,23 @@ impl UseCommand {
             }
         };
 
-        let is_dotnet = self.dotnet;
-        let target_version = GodotVersion::new(&version_string, is_dotnet)?;
+        let resolved = installer.resolve_installed(&version_string, is_dotnet)?;
+
+        if self.resolve {
+            let (version, executable) = preview(&installer, &version_string, is_dotnet)?;
+            // The version parses back for `gdenv use`, the path is for running it directly
+            println!("{}", version.canonical_string());
+            println!("{}", executable.display());
+            return Ok(());
+        }
 
         // Check if the version is installed
-        let installed_versions = installer.list_installed()?;
-        if !installed_versions.contains(&target_version) {
-            ui::error(&format!("Godot v{} is not installed", target_version));
+        let Some(target_version) = resolved else {
+            let installed_versions = installer.list_installed()?;
+            match GodotVersion::new(&version_string, is_dotnet) {
+                Ok(version) => ui::error(&format!("Godot v{} is not installed", version)),
+                Err(_) => ui::error(&format!("No installed Godot matches '{}'", version_string)),
+            }
             ui::info("Available installed versions:");
 
             for version in &installed_versions {
@@ -48,18 +82,20 @@ impl UseCommand {
             }
 
             return Ok(());
-        }
+        };
 
         // Switch to the version
         installer.set_active_version(&target_version)?;
 
+        if self.update_project {
+            update_project(Path::new("."), &target_version)?;
+        }
+
         Ok(())
     }
 
     fn read_godot_version_file(&self) -> Result<String> {
-        use anyhow::anyhow;
         use std::fs;
-        use std::path::Path;
 
         let version_file = Path::new(".godot-version");
 
@@ -82,3 +118,102 @@ impl UseCommand {
         Ok(version.to_string())
     }
 }
+
+/// The installed version and executable `gdenv use <spec>` would switch to
+fn preview(installer: &Installer, spec: &str, is_dotnet: bool) -> Result<(GodotVersion, PathBuf)> {
+    let version = installer
+        .resolve_installed(spec, is_dotnet)?
+        .ok_or_else(|| anyhow!("No installed Godot matches '{}'", spec))?;
+    let executable = installer.get_executable(&version)?;
+    Ok((version, executable))
+}
+
+/// Point a project's `project.godot` and `.godot-version` at `version`
+fn update_project(project_dir: &Path, version: &GodotVersion) -> Result<()> {
+    let project_file = project_dir.join(PROJECT_FILE);
+    if !project_file.exists() {
+        return Err(anyhow!(
+            "No {} found in {}",
+            PROJECT_FILE,
+            project_dir.display()
+        ));
+    }
+
+    version.write_to_project_file(&project_file)?;
+    ui::success(&format!(
+        "Set {} to Godot {}",
+        project_file.display(),
+        version.as_project_feature_string()
+    ));
+
+    let lockfile_path = project_dir.join(LOCKFILE_NAME);
+    LockFile::write(&lockfile_path, version, DryRunMode::Off)?;
+    ui::success(&format!(
+        "Pinned Godot v{} in {}",
+        version,
+        lockfile_path.display()
+    ));
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::installer::fake_install;
+    use std::fs;
+
+    #[test]
+    fn test_preview_resolves_partial_versions() {
+        let dir = tempfile::tempdir().unwrap();
+        let config = Config::with_data_dir(dir.path());
+        let installer = Installer::new(config.clone());
+        for version in ["4.1.4", "4.2", "4.2.1", "4.2.2-rc1", "4.3"] {
+            let version = GodotVersion::new(version, false).unwrap();
+            fake_install(&config, &version, "");
+        }
+        let active = GodotVersion::new("4.1.4", false).unwrap();
+        fs::create_dir_all(&config.bin_dir).unwrap();
+        installer.set_active_version(&active).unwrap();
+
+        // The newest stable patch, even though 4.2.0 itself is installed
+        let (version, executable) = preview(&installer, "4.2", false).unwrap();
+        assert_eq!(version, GodotVersion::new("4.2.1", false).unwrap());
+        assert!(executable.is_file());
+        assert_eq!(
+            preview(&installer, "4", false).unwrap().0,
+            GodotVersion::new("4.3", false).unwrap()
+        );
+        assert_eq!(
+            preview(&installer, "4.2.2-rc1", false).unwrap().0,
+            GodotVersion::new("4.2.2-rc1", false).unwrap()
+        );
+        assert!(preview(&installer, "4.2", true).is_err());
+        assert!(preview(&installer, "5", false).is_err());
+
+        // Previewing switches nothing
+        assert_eq!(installer.get_active_version().unwrap(), Some(active));
+        assert!(LockFile::find(dir.path()).is_none());
+    }
+
+    #[test]
+    fn test_update_project() {
+        let dir = tempfile::tempdir().unwrap();
+        let version = GodotVersion::new("4.3.1", false).unwrap();
+        assert!(update_project(dir.path(), &version).is_err());
+
+        fs::write(
+            dir.path().join(PROJECT_FILE),
+            "[application]\nconfig/features=PackedStringArray(\"4.2\", \"Forward Plus\")\n",
+        )
+        .unwrap();
+        update_project(dir.path(), &version).unwrap();
+
+        let lockfile = LockFile::read(&dir.path().join(LOCKFILE_NAME)).unwrap();
+        assert_eq!(lockfile.pin, crate::lockfile::Pin::Version(version));
+        let project_version = GodotVersion::from_project_file(&dir.path().join(PROJECT_FILE))
+            .unwrap()
+            .unwrap();
+        assert!(lockfile.matches_project(&project_version));
+    }
+}
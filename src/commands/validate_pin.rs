@@ -0,0 +1,225 @@
+use anyhow::{anyhow, Result};
+use clap::Args;
+use std::path::{Path, PathBuf};
+
+use crate::{
+    config::Config,
+    github::GitHubClient,
+    godot::GodotVersion,
+    installer::Installer,
+    lockfile::{LockFile, Pin, LOCKFILE_NAME},
+    ui,
+};
+
+#[derive(Args)]
+pub struct ValidatePinCommand {
+    /// Directory to look for .godot-version in (or in its parents)
+    #[arg(default_value = ".")]
+    pub path: PathBuf,
+
+    /// Also fail if the pinned version isn't installed
+    #[arg(long)]
+    pub require_installed: bool,
+
+    /// Don't check that the release exists, only that the pin parses (the default
+    /// when the offline setting is on)
+    #[arg(long)]
+    pub offline: bool,
+}
+
+impl ValidatePinCommand {
+    pub async fn run(self) -> Result<()> {
+        let config = Config::new()?;
+        let offline = self.offline || config.settings.offline;
+
+        let lockfile_path = LockFile::find(&self.path).ok_or_else(|| {
+            anyhow!(
+                "No {} found in {} or its parents",
+                LOCKFILE_NAME,
+                self.path.display()
+            )
+        })?;
+
+        let available = if offline {
+            None
+        } else {
+            let releases = GitHubClient::from_config(&config)
+                .get_godot_releases(true)
+                .await
+                .map_err(|e| {
+                    anyhow!(
+                        "Could not fetch Godot releases: {}\nUse --offline to only check the pin locally",
+                        e
+                    )
+                })?;
+            Some(
+                releases
+                    .iter()
+                    .filter_map(|r| GodotVersion::from_tag(&r.tag_name).ok())
+                    .collect::<Vec<_>>(),
+            )
+        };
+
+        let installer = Installer::new(config);
+        let pin = validate(
+            &lockfile_path,
+            &installer,
+            available.as_deref(),
+            self.require_installed,
+        )?;
+
+        ui::success(&format!(
+            "{} pins Godot {}{}",
+            lockfile_path.display(),
+            pin,
+            if available.is_some() {
+                ", a released version"
+            } else {
+                ""
+            }
+        ));
+        if installer.resolve_pin(&pin)?.is_none() {
+            ui::info(&format!(
+                "Godot {} is not installed; run 'gdenv install' to install it",
+                pin
+            ));
+        }
+        Ok(())
+    }
+}
+
+/// Check that a lockfile parses, names one of the `available` releases (or, for a range,
+/// admits one) when they're known and, with `require_installed`, is installed
+fn validate(
+    lockfile_path: &Path,
+    installer: &Installer,
+    available: Option<&[GodotVersion]>,
+    require_installed: bool,
+) -> Result<Pin> {
+    let pin = LockFile::read_pin(lockfile_path)
+        .map_err(|e| anyhow!("{} has an invalid pin: {}", lockfile_path.display(), e))?;
+
+    if let Some(available) = available {
+        match &pin {
+            Pin::Version(version) if !available.iter().any(|v| v.version == version.version) => {
+                let suggestion = match nearest_version(version, available) {
+                    Some(nearest) => format!(
+                        "; the nearest release is {}",
+                        nearest.godot_version_string()
+                    ),
+                    None => String::new(),
+                };
+                return Err(anyhow!(
+                    "{} pins Godot {}, which was never released{}",
+                    lockfile_path.display(),
+                    pin,
+                    suggestion
+                ));
+            }
+            Pin::Range(range) if range.resolve(available).is_none() => {
+                return Err(anyhow!(
+                    "{} pins {}, which no Godot release satisfies",
+                    lockfile_path.display(),
+                    pin
+                ));
+            }
+            _ => {}
+        }
+    }
+
+    if require_installed && installer.resolve_pin(&pin)?.is_none() {
+        return Err(anyhow!(
+            "{} pins Godot {}, which is not installed\nRun 'gdenv install' to install it",
+            lockfile_path.display(),
+            pin
+        ));
+    }
+
+    Ok(pin)
+}
+
+/// The newest release not newer than `version`, or else the oldest release
+fn nearest_version<'a>(
+    version: &GodotVersion,
+    available: &'a [GodotVersion],
+) -> Option<&'a GodotVersion> {
+    available
+        .iter()
+        .filter(|v| v.version <= version.version)
+        .max()
+        .or_else(|| available.iter().min())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::installer::fake_install;
+    use std::fs;
+
+    fn v(version: &str) -> GodotVersion {
+        GodotVersion::new(version, false).unwrap()
+    }
+
+    #[test]
+    fn test_validate() {
+        let dir = tempfile::tempdir().unwrap();
+        let config = Config::with_data_dir(&dir.path().join("gdenv"));
+        let installer = Installer::new(config.clone());
+        let lockfile = dir.path().join(LOCKFILE_NAME);
+        let available = [v("4.1.4"), v("4.2.1"), v("4.2.2"), v("4.3")];
+
+        // Malformed pin
+        fs::write(&lockfile, "four point two\n").unwrap();
+        let error = validate(&lockfile, &installer, None, false).unwrap_err();
+        assert!(error.to_string().contains("invalid pin"), "{}", error);
+
+        // Never released, with the nearest release suggested
+        fs::write(&lockfile, "4.2.7\n").unwrap();
+        let error = validate(&lockfile, &installer, Some(&available), false).unwrap_err();
+        assert!(
+            error.to_string().contains("nearest release is 4.2.2"),
+            "{}",
+            error
+        );
+        // Offline, only the syntax is checked
+        assert_eq!(
+            validate(&lockfile, &installer, None, false).unwrap(),
+            Pin::Version(v("4.2.7"))
+        );
+
+        // Valid but not installed
+        fs::write(&lockfile, "4.2.1-dotnet\n").unwrap();
+        let pinned = GodotVersion::new("4.2.1", true).unwrap();
+        assert_eq!(
+            validate(&lockfile, &installer, Some(&available), false).unwrap(),
+            Pin::Version(pinned.clone())
+        );
+        let error = validate(&lockfile, &installer, Some(&available), true).unwrap_err();
+        assert!(error.to_string().contains("not installed"), "{}", error);
+
+        // Valid and installed
+        fake_install(&config, &pinned, "");
+        assert_eq!(
+            validate(&lockfile, &installer, Some(&available), true).unwrap(),
+            Pin::Version(pinned)
+        );
+
+        // Ranges have to admit a release, and an install with --require-installed
+        fs::write(&lockfile, ">=4.4\n").unwrap();
+        let error = validate(&lockfile, &installer, Some(&available), false).unwrap_err();
+        assert!(error.to_string().contains("no Godot release"), "{}", error);
+        fs::write(&lockfile, "~4.2\n").unwrap();
+        assert!(validate(&lockfile, &installer, Some(&available), false).is_ok());
+        let error = validate(&lockfile, &installer, Some(&available), true).unwrap_err();
+        assert!(error.to_string().contains("not installed"), "{}", error);
+    }
+
+    #[test]
+    fn test_nearest_version() {
+        let available = [v("3.6"), v("4.2.2"), v("4.3")];
+        assert_eq!(nearest_version(&v("4.2.9"), &available), Some(&v("4.2.2")));
+        assert_eq!(nearest_version(&v("4.9"), &available), Some(&v("4.3")));
+        assert_eq!(nearest_version(&v("2.1"), &available), Some(&v("3.6")));
+        assert_eq!(nearest_version(&v("4.2"), &[]), None);
+    }
+}
@@ -0,0 +1,61 @@
+use anyhow::{anyhow, Result};
+use clap::Args;
+
+use crate::{config::Config, godot::GodotVersion, installer::Installer, ui};
+
+#[derive(Args)]
+pub struct VerifyCommand {
+    /// The Godot version to check (default: every installed version)
+    pub version: Option<String>,
+
+    /// Use the .NET build of the version
+    #[arg(long)]
+    pub dotnet: bool,
+
+    /// Use the standard build even when the default_dotnet setting is on
+    #[arg(long, conflicts_with = "dotnet")]
+    pub no_dotnet: bool,
+}
+
+impl VerifyCommand {
+    pub async fn run(self) -> Result<()> {
+        let mut config = Config::new()?;
+        let is_dotnet = config
+            .settings
+            .apply_dotnet_flags(self.dotnet, self.no_dotnet);
+        let installer = Installer::new(config);
+
+        let versions = match &self.version {
+            Some(version) => vec![GodotVersion::new(version, is_dotnet)?],
+            None => installer.list_installed()?,
+        };
+        if versions.is_empty() {
+            ui::info("No Godot versions installed");
+            return Ok(());
+        }
+
+        let mut problems = 0;
+        for version in &versions {
+            match installer.executable_size_warning(version) {
+                Ok(None) => ui::success(&format!("Godot v{} looks intact", version)),
+                Ok(Some(warning)) => {
+                    ui::warning(&format!("Godot v{}: {}", version, warning));
+                    problems += 1;
+                }
+                Err(e) => {
+                    ui::error(&format!("Godot v{}: {}", version, e));
+                    problems += 1;
+                }
+            }
+        }
+
+        if problems > 0 {
+            return Err(anyhow!(
+                "{} of {} installations look damaged",
+                problems,
+                versions.len()
+            ));
+        }
+        Ok(())
+    }
+}
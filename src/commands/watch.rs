@@ -0,0 +1,177 @@
+use anyhow::{anyhow, Result};
+use clap::Args;
+use glob::Pattern;
+use notify::{EventKind, RecursiveMode, Watcher};
+use std::collections::BTreeSet;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+use std::time::Duration;
+use tokio::sync::mpsc;
+
+use super::import::import_command;
+use crate::{config::Config, installer::Installer, project::PROJECT_FILE, ui};
+
+/// How long the project must be quiet before changes are processed
+const DEBOUNCE: Duration = Duration::from_millis(500);
+
+/// Paths Godot itself writes while importing, which must not trigger another import
+const GENERATED_DIRS: &[&str] = &[".godot", ".import", ".git"];
+
+#[derive(Args)]
+pub struct WatchCommand {
+    /// Path to the Godot project directory
+    #[arg(default_value = ".")]
+    pub path: PathBuf,
+
+    /// Ignore changes to files matching this glob (can be repeated)
+    #[arg(long, value_name = "GLOB")]
+    pub ignore_pattern: Vec<String>,
+
+    /// Run this shell command on changes instead of reimporting
+    #[arg(long, value_name = "CMD")]
+    pub on_change: Option<String>,
+}
+
+impl WatchCommand {
+    pub async fn run(self) -> Result<()> {
+        if !self.path.join(PROJECT_FILE).exists() {
+            return Err(anyhow!(
+                "No {} found in {}",
+                PROJECT_FILE,
+                self.path.display()
+            ));
+        }
+
+        let project_dir = self.path.canonicalize()?;
+        let ignore_patterns = self
+            .ignore_pattern
+            .iter()
+            .map(|p| Pattern::new(p).map_err(|e| anyhow!("Invalid pattern '{}': {}", p, e)))
+            .collect::<Result<Vec<_>>>()?;
+
+        // Resolve the editor up front so a missing install fails fast
+        let godot = match self.on_change {
+            Some(_) => None,
+            None => {
+                let installer = Installer::new(Config::new()?);
+                let version = installer.resolve_version_for(&project_dir)?;
+                ui::info(&format!("Reimporting with Godot v{}", version));
+                Some(installer.get_executable(&version)?)
+            }
+        };
+
+        // notify calls back on its own thread; forward events so the loop can await them
+        let (tx, mut rx) = mpsc::unbounded_channel();
+        let mut watcher = notify::recommended_watcher(move |event| {
+            let _ = tx.send(event);
+        })?;
+        watcher.watch(&project_dir, RecursiveMode::Recursive)?;
+
+        println!("👀 Watching {} (Ctrl+C to stop)", project_dir.display());
+
+        loop {
+            // Wait for the first change, then gather everything until things settle
+            let mut changed = BTreeSet::new();
+            let Some(mut event) = rx.recv().await else {
+                return Ok(());
+            };
+            loop {
+                if let Ok(event) = event {
+                    if !matches!(event.kind, EventKind::Access(_)) {
+                        changed.extend(event.paths.iter().filter_map(|path| {
+                            let relative = path.strip_prefix(&project_dir).ok()?;
+                            is_relevant(relative, &ignore_patterns).then(|| relative.to_path_buf())
+                        }));
+                    }
+                }
+
+                match tokio::time::timeout(DEBOUNCE, rx.recv()).await {
+                    Ok(Some(next)) => event = next,
+                    Ok(None) => return Ok(()),
+                    Err(_) => break,
+                }
+            }
+
+            if changed.is_empty() {
+                continue;
+            }
+
+            let timestamp = chrono::Local::now().format("%H:%M:%S");
+            println!("\n[{}] {} file(s) changed:", timestamp, changed.len());
+            for path in changed.iter().take(10) {
+                println!("  • {}", path.display());
+            }
+            if changed.len() > 10 {
+                println!("  … and {} more", changed.len() - 10);
+            }
+
+            let command = match (&self.on_change, &godot) {
+                (Some(command), _) => {
+                    let mut shell = shell_command(command);
+                    shell.current_dir(&project_dir);
+                    shell
+                }
+                (None, Some(godot)) => import_command(godot, &project_dir),
+                (None, None) => unreachable!("editor is resolved when no command is given"),
+            };
+            let status = tokio::process::Command::from(command).status().await;
+
+            match status {
+                Ok(status) if status.success() => ui::success("Done"),
+                Ok(status) => ui::error(&format!("Command failed with {}", status)),
+                Err(e) => ui::error(&format!("Failed to run command: {}", e)),
+            }
+        }
+    }
+}
+
+/// Whether a change to `relative` (a path inside the project) should trigger a reimport
+fn is_relevant(relative: &Path, ignore_patterns: &[Pattern]) -> bool {
+    let in_generated_dir = relative
+        .components()
+        .any(|c| GENERATED_DIRS.iter().any(|dir| c.as_os_str() == *dir));
+    let is_import_file = relative.extension().is_some_and(|ext| ext == "import");
+    let is_ignored = ignore_patterns.iter().any(|pattern| {
+        pattern.matches_path(relative)
+            || relative
+                .file_name()
+                .is_some_and(|name| pattern.matches(&name.to_string_lossy()))
+    });
+
+    !in_generated_dir && !is_import_file && !is_ignored
+}
+
+fn shell_command(command: &str) -> Command {
+    if cfg!(windows) {
+        let mut shell = Command::new("cmd");
+        shell.args(["/C", command]);
+        shell
+    } else {
+        let mut shell = Command::new("sh");
+        shell.args(["-c", command]);
+        shell
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_is_relevant() {
+        let patterns = vec![
+            Pattern::new("*.tmp").unwrap(),
+            Pattern::new("art/**").unwrap(),
+        ];
+
+        assert!(is_relevant(Path::new("scenes/main.tscn"), &patterns));
+        assert!(is_relevant(Path::new("icon.png"), &patterns));
+        assert!(!is_relevant(Path::new("icon.png.import"), &patterns));
+        assert!(!is_relevant(
+            Path::new(".godot/imported/icon.ctex"),
+            &patterns
+        ));
+        assert!(!is_relevant(Path::new("scenes/backup.tmp"), &patterns));
+        assert!(!is_relevant(Path::new("art/source/hero.blend"), &patterns));
+    }
+}
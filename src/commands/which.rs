@@ -0,0 +1,136 @@
+use anyhow::{anyhow, Result};
+use clap::Args;
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+
+use crate::{config::Config, godot::GodotVersion, installer::Installer};
+
+#[derive(Args)]
+pub struct WhichCommand {
+    /// The Godot version (defaults to the project's pinned or the active version)
+    pub version: Option<String>,
+
+    /// Use the .NET build of the version
+    #[arg(long)]
+    pub dotnet: bool,
+
+    /// Use the standard build even when the default_dotnet setting is on
+    #[arg(long, conflicts_with = "dotnet")]
+    pub no_dotnet: bool,
+
+    /// Print only the absolute path and a newline, or nothing and exit with 1 if
+    /// there's no executable (for tools embedding gdenv)
+    #[arg(long)]
+    pub print_path_only: bool,
+
+    /// List every binary that could provide the version and which one is used
+    #[arg(long, conflicts_with = "print_path_only")]
+    pub all: bool,
+
+    /// Print the console executable, which keeps stdout attached on Windows (for CI)
+    #[arg(long, conflicts_with = "all")]
+    pub console: bool,
+}
+
+impl WhichCommand {
+    pub async fn run(self) -> Result<()> {
+        if self.print_path_only {
+            let executable = self.resolve().ok();
+            if !write_path_only(&mut io::stdout().lock(), executable.as_deref())? {
+                std::process::exit(1);
+            }
+            return Ok(());
+        }
+
+        if self.all {
+            return self.print_candidates();
+        }
+
+        println!("{}", self.resolve()?.display());
+
+        Ok(())
+    }
+
+    fn print_candidates(&self) -> Result<()> {
+        let (installer, version) = self.version()?;
+        let candidates = installer.resolve_binary_candidates(&version);
+
+        if candidates.is_empty() {
+            return Err(anyhow!("No Godot binary found for v{}", version));
+        }
+
+        println!("Candidates for Godot v{}:", version);
+        for candidate in &candidates {
+            let marker = if candidate.selected { "→" } else { " " };
+            println!(
+                "  {} {:<8} {} ({})",
+                marker,
+                candidate.source,
+                candidate.path.display(),
+                candidate.reason
+            );
+        }
+
+        Ok(())
+    }
+
+    fn resolve(&self) -> Result<PathBuf> {
+        let (installer, version) = self.version()?;
+        let executable = if self.console {
+            installer.get_console_executable(&version)?
+        } else {
+            installer.get_executable(&version)?
+        };
+        Ok(std::path::absolute(executable)?)
+    }
+
+    /// The requested version, or the project's pinned or active version
+    fn version(&self) -> Result<(Installer, GodotVersion)> {
+        let mut config = Config::new()?;
+        let is_dotnet = config
+            .settings
+            .apply_dotnet_flags(self.dotnet, self.no_dotnet);
+        let installer = Installer::new(config);
+
+        let version = match &self.version {
+            Some(v) => GodotVersion::new(v, is_dotnet)?,
+            None => installer.resolve_version_for(Path::new("."))?,
+        };
+
+        Ok((installer, version))
+    }
+}
+
+/// Write exactly `executable` and one newline, or nothing when there's no executable.
+/// Returns whether a path was written.
+fn write_path_only(out: &mut impl Write, executable: Option<&Path>) -> io::Result<bool> {
+    let Some(executable) = executable else {
+        return Ok(false);
+    };
+
+    out.write_all(executable.as_os_str().as_encoded_bytes())?;
+    out.write_all(b"\n")?;
+    out.flush()?;
+    Ok(true)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_write_path_only() {
+        let mut out = Vec::new();
+        let path =
+            Path::new("/opt/gdenv/installations/godot-4.2.1/Godot_v4.2.1-stable_linux.x86_64");
+        assert!(write_path_only(&mut out, Some(path)).unwrap());
+        assert_eq!(
+            out,
+            b"/opt/gdenv/installations/godot-4.2.1/Godot_v4.2.1-stable_linux.x86_64\n"
+        );
+
+        let mut out = Vec::new();
+        assert!(!write_path_only(&mut out, None).unwrap());
+        assert!(out.is_empty());
+    }
+}
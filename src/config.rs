@@ -1,8 +1,18 @@
-use anyhow::Result;
-use std::path::PathBuf;
+use anyhow::{anyhow, Result};
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+
+use crate::godot::{GodotVersion, Source};
+use crate::install_name::InstallNameTemplate;
 
 #[derive(Debug, Clone)]
 pub struct Config {
+    /// Root directory of all gdenv data (overridable with `GDENV_ROOT`)
+    pub root_dir: PathBuf,
+
     /// Directory where Godot installations are stored
     pub installations_dir: PathBuf,
 
@@ -15,29 +25,54 @@ pub struct Config {
     /// Directory for executable symlinks (to be added to PATH)
     pub bin_dir: PathBuf,
 
+    /// Directory for user configuration files (hooks, settings)
+    pub config_dir: PathBuf,
+
     /// GitHub API base URL
     pub github_api_url: String,
+
+    /// User settings from `config.toml` and `GDENV_*` environment variables
+    pub settings: Settings,
 }
 
 impl Default for Config {
     fn default() -> Self {
+        if let Some(root) = std::env::var_os("GDENV_ROOT") {
+            return Self::with_data_dir(Path::new(&root));
+        }
+
         let data_dir = dirs::data_dir()
             .unwrap_or_else(|| dirs::home_dir().unwrap_or_default().join(".local/share"))
             .join("gdenv");
 
+        let config_dir = dirs::config_dir()
+            .unwrap_or_else(|| dirs::home_dir().unwrap_or_default().join(".config"))
+            .join("gdenv");
+
         Self {
+            config_dir,
+            ..Self::with_data_dir(&data_dir)
+        }
+    }
+}
+
+impl Config {
+    /// Build a configuration rooted at `data_dir` instead of the default location
+    pub fn with_data_dir(data_dir: &Path) -> Self {
+        Self {
+            root_dir: data_dir.to_path_buf(),
             installations_dir: data_dir.join("installations"),
             cache_dir: data_dir.join("cache"),
             active_symlink: data_dir.join("current"),
             bin_dir: data_dir.join("bin"),
+            config_dir: data_dir.join("config"),
             github_api_url: "https://api.github.com".to_string(),
+            settings: Settings::default(),
         }
     }
-}
 
-impl Config {
     pub fn new() -> Result<Self> {
-        let config = Self::default();
+        let config = load()?;
 
         // Ensure directories exist
         std::fs::create_dir_all(&config.installations_dir)?;
@@ -46,4 +81,314 @@ impl Config {
 
         Ok(config)
     }
+
+    /// Path of the `config.toml` settings file
+    pub fn settings_path(&self) -> PathBuf {
+        self.root_dir.join("config.toml")
+    }
+
+    /// The template installation directories are named with
+    pub fn install_name_template(&self) -> &InstallNameTemplate {
+        self.settings
+            .install_dir_template
+            .as_ref()
+            .unwrap_or_else(|| InstallNameTemplate::default_ref())
+    }
+
+    /// Directory a version is installed in
+    pub fn install_path(&self, version: &GodotVersion) -> PathBuf {
+        self.installations_dir
+            .join(self.install_name_template().format(version))
+    }
+
+    /// Get the directory for a build of `version` for another platform, installed with
+    /// `install --platform`. Its name doesn't parse as an installation, so gdenv never
+    /// runs it; `Installer::list_platform_builds` finds it instead.
+    pub fn platform_install_path(&self, version: &GodotVersion, platform_suffix: &str) -> PathBuf {
+        self.installations_dir.join(format!(
+            "{}@{}",
+            self.install_name_template().format(version),
+            platform_suffix
+        ))
+    }
+}
+
+/// Load the configuration, merging `config.toml` with `GDENV_*` environment variables
+/// Command-line flags are applied on top of this by each command.
+pub fn load() -> Result<Config> {
+    let mut config = Config::default();
+    config.settings = Settings::load_file(&config.settings_path())?;
+    config.settings.apply_env(|name| std::env::var(name).ok())?;
+    Ok(config)
+}
+
+/// Save the settings of `config` to its `config.toml`
+pub fn save(config: &Config) -> Result<()> {
+    config.settings.save_file(&config.settings_path())
+}
+
+/// Which release channel to prefer when no version is given explicitly
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ChannelPreference {
+    #[default]
+    Stable,
+    Prerelease,
+}
+
+impl FromStr for ChannelPreference {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s {
+            "stable" => Ok(Self::Stable),
+            "prerelease" => Ok(Self::Prerelease),
+            _ => Err(anyhow!(
+                "Invalid channel '{}' (expected 'stable' or 'prerelease')",
+                s
+            )),
+        }
+    }
+}
+
+impl fmt::Display for ChannelPreference {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Stable => write!(f, "stable"),
+            Self::Prerelease => write!(f, "prerelease"),
+        }
+    }
+}
+
+/// User settings stored in `config.toml`
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Settings {
+    /// Extra download mirrors laid out like the GitHub releases (`<mirror>/<tag>/<archive>`),
+    /// tried in order after the download sources
+    pub mirrors: Vec<String>,
+
+    /// HTTP(S) proxy URL for all requests
+    pub proxy: Option<String>,
+
+    /// Install/use .NET builds unless told otherwise with `--no-dotnet`
+    pub default_dotnet: bool,
+
+    /// Preferred release channel for listings
+    pub channel: ChannelPreference,
+
+    /// Maximum number of parallel downloads
+    pub concurrency: usize,
+
+    /// How often to retry a failed request
+    pub retries: u32,
+
+    /// How long the cached list of Godot releases stays fresh, in hours (0 disables it)
+    pub cache_ttl_hours: u64,
+
+    /// Never touch the network
+    pub offline: bool,
+
+    /// Download source to try first
+    pub prefer_source: Source,
+
+    /// How to name installation directories, e.g. "{version}{dotnet}"
+    /// (default: "godot-{version}{dotnet}")
+    pub install_dir_template: Option<InstallNameTemplate>,
+}
+
+impl Default for Settings {
+    fn default() -> Self {
+        Self {
+            mirrors: Vec::new(),
+            proxy: None,
+            default_dotnet: false,
+            channel: ChannelPreference::Stable,
+            concurrency: 4,
+            retries: 3,
+            cache_ttl_hours: 24,
+            offline: false,
+            prefer_source: Source::GitHub,
+            install_dir_template: None,
+        }
+    }
+}
+
+impl Settings {
+    /// All setting keys, in display order
+    pub const KEYS: &'static [&'static str] = &[
+        "mirrors",
+        "proxy",
+        "default_dotnet",
+        "channel",
+        "concurrency",
+        "retries",
+        "cache_ttl_hours",
+        "offline",
+        "prefer_source",
+        "install_dir_template",
+    ];
+
+    /// Read settings from a file, using defaults if it doesn't exist
+    pub fn load_file(path: &Path) -> Result<Self> {
+        if !path.exists() {
+            return Ok(Self::default());
+        }
+
+        let content = fs::read_to_string(path)?;
+        toml::from_str(&content).map_err(|e| anyhow!("Failed to parse {}: {}", path.display(), e))
+    }
+
+    /// Apply a command's `--dotnet` or `--no-dotnet` flag over `default_dotnet`, so that
+    /// ranges resolve to the selected build too, and return whether .NET builds are used
+    pub fn apply_dotnet_flags(&mut self, dotnet: bool, no_dotnet: bool) -> bool {
+        if dotnet || no_dotnet {
+            self.default_dotnet = dotnet;
+        }
+        self.default_dotnet
+    }
+
+    pub fn save_file(&self, path: &Path) -> Result<()> {
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        fs::write(path, toml::to_string(self)?)?;
+        Ok(())
+    }
+
+    /// Override settings from `GDENV_<KEY>` variables (e.g. `GDENV_DEFAULT_DOTNET=true`)
+    pub fn apply_env(&mut self, lookup: impl Fn(&str) -> Option<String>) -> Result<()> {
+        for key in Self::KEYS {
+            let name = format!("GDENV_{}", key.to_uppercase());
+            if let Some(value) = lookup(&name) {
+                self.set(key, &value)
+                    .map_err(|e| anyhow!("Invalid {}: {}", name, e))?;
+            }
+        }
+        Ok(())
+    }
+
+    pub fn get(&self, key: &str) -> Result<String> {
+        Ok(match key {
+            "mirrors" => self.mirrors.join(","),
+            "proxy" => self.proxy.clone().unwrap_or_default(),
+            "default_dotnet" => self.default_dotnet.to_string(),
+            "channel" => self.channel.to_string(),
+            "concurrency" => self.concurrency.to_string(),
+            "retries" => self.retries.to_string(),
+            "cache_ttl_hours" => self.cache_ttl_hours.to_string(),
+            "offline" => self.offline.to_string(),
+            "prefer_source" => self.prefer_source.to_string(),
+            "install_dir_template" => self
+                .install_dir_template
+                .as_ref()
+                .map(ToString::to_string)
+                .unwrap_or_default(),
+            _ => return Err(unknown_key(key)),
+        })
+    }
+
+    /// Set a setting from its string form. Lists are comma-separated and an
+    /// empty value clears optional settings.
+    pub fn set(&mut self, key: &str, value: &str) -> Result<()> {
+        let value = value.trim();
+        match key {
+            "mirrors" => {
+                self.mirrors = value
+                    .split(',')
+                    .map(str::trim)
+                    .filter(|m| !m.is_empty())
+                    .map(String::from)
+                    .collect()
+            }
+            "proxy" => self.proxy = (!value.is_empty()).then(|| value.to_string()),
+            "default_dotnet" => self.default_dotnet = value.parse()?,
+            "channel" => self.channel = value.parse()?,
+            "concurrency" => self.concurrency = value.parse()?,
+            "retries" => self.retries = value.parse()?,
+            "cache_ttl_hours" => self.cache_ttl_hours = value.parse()?,
+            "offline" => self.offline = value.parse()?,
+            "prefer_source" => self.prefer_source = value.parse()?,
+            "install_dir_template" => {
+                self.install_dir_template = (!value.is_empty())
+                    .then(|| InstallNameTemplate::new(value))
+                    .transpose()?
+            }
+            _ => return Err(unknown_key(key)),
+        }
+        Ok(())
+    }
+}
+
+fn unknown_key(key: &str) -> anyhow::Error {
+    anyhow!(
+        "Unknown setting '{}' (available: {})",
+        key,
+        Settings::KEYS.join(", ")
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_settings_precedence() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("config.toml");
+        fs::write(
+            &path,
+            "retries = 5\nconcurrency = 2\nchannel = \"prerelease\"\ndefault_dotnet = false\n",
+        )
+        .unwrap();
+
+        // File overrides defaults
+        let mut settings = Settings::load_file(&path).unwrap();
+        assert_eq!(settings.retries, 5);
+        assert_eq!(settings.concurrency, 2);
+        assert_eq!(settings.cache_ttl_hours, 24);
+
+        // Environment overrides the file
+        settings
+            .apply_env(|name| match name {
+                "GDENV_RETRIES" => Some("7".to_string()),
+                "GDENV_DEFAULT_DOTNET" => Some("true".to_string()),
+                _ => None,
+            })
+            .unwrap();
+        assert_eq!(settings.retries, 7);
+        assert_eq!(settings.concurrency, 2);
+        assert!(settings.default_dotnet);
+        assert_eq!(settings.channel, ChannelPreference::Prerelease);
+
+        // Flags override the environment, and no flag keeps it
+        assert!(settings.clone().apply_dotnet_flags(false, false));
+        assert!(!settings.clone().apply_dotnet_flags(false, true));
+        settings.default_dotnet = false;
+        assert!(settings.apply_dotnet_flags(true, false));
+    }
+
+    #[test]
+    fn test_settings_set_round_trip() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("config.toml");
+
+        let mut settings = Settings::load_file(&path).unwrap();
+        settings
+            .set("mirrors", "https://a.example, https://b.example")
+            .unwrap();
+        settings.set("default_dotnet", "true").unwrap();
+        settings.save_file(&path).unwrap();
+
+        let reloaded = Settings::load_file(&path).unwrap();
+        assert_eq!(reloaded, settings);
+        assert_eq!(
+            reloaded.get("mirrors").unwrap(),
+            "https://a.example,https://b.example"
+        );
+        assert_eq!(reloaded.get("default_dotnet").unwrap(), "true");
+
+        assert!(settings.set("nope", "1").is_err());
+        assert!(settings.set("concurrency", "many").is_err());
+    }
 }
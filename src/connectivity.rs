@@ -0,0 +1,184 @@
+use futures_util::future::join_all;
+use reqwest::{Client, StatusCode};
+use std::error::Error as _;
+use std::fmt;
+use std::time::{Duration, Instant};
+
+use crate::config::Config;
+
+/// Where official release archives are downloaded from
+const GITHUB_DOWNLOADS_URL: &str = "https://github.com";
+
+/// How long to wait for each source before giving up
+pub const PROBE_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// The outcome of probing one source
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ProbeStatus {
+    Reachable(StatusCode),
+    HttpError(StatusCode),
+    DnsFailure,
+    TlsFailure,
+    Timeout,
+    ConnectionFailed(String),
+}
+
+#[derive(Debug, Clone)]
+pub struct ProbeResult {
+    pub url: String,
+    pub status: ProbeStatus,
+    pub latency: Duration,
+}
+
+impl ProbeStatus {
+    pub fn is_reachable(&self) -> bool {
+        matches!(self, ProbeStatus::Reachable(_))
+    }
+}
+
+impl fmt::Display for ProbeStatus {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ProbeStatus::Reachable(status) => write!(f, "reachable ({})", status),
+            ProbeStatus::HttpError(status) => write!(f, "HTTP error ({})", status),
+            ProbeStatus::DnsFailure => write!(f, "DNS lookup failed"),
+            ProbeStatus::TlsFailure => write!(f, "TLS handshake failed"),
+            ProbeStatus::Timeout => write!(f, "timed out"),
+            ProbeStatus::ConnectionFailed(reason) => write!(f, "connection failed: {}", reason),
+        }
+    }
+}
+
+/// The GitHub API, GitHub downloads and every configured mirror
+pub fn sources(config: &Config) -> Vec<String> {
+    let mut sources = vec![
+        config.github_api_url.clone(),
+        GITHUB_DOWNLOADS_URL.to_string(),
+    ];
+    sources.extend(config.settings.mirrors.iter().cloned());
+    sources
+}
+
+/// The proxy requests go through: the `proxy` setting, or else the usual environment variables
+pub fn active_proxy(config: &Config) -> Option<String> {
+    config.settings.proxy.clone().or_else(|| {
+        ["HTTPS_PROXY", "https_proxy", "ALL_PROXY", "all_proxy"]
+            .iter()
+            .find_map(|name| std::env::var(name).ok().filter(|v| !v.is_empty()))
+    })
+}
+
+/// Send a HEAD request to every source in parallel
+pub async fn probe_all(client: &Client, urls: &[String], timeout: Duration) -> Vec<ProbeResult> {
+    join_all(urls.iter().map(|url| probe(client, url, timeout))).await
+}
+
+async fn probe(client: &Client, url: &str, timeout: Duration) -> ProbeResult {
+    let start = Instant::now();
+    let status = match client.head(url).timeout(timeout).send().await {
+        Ok(response) if response.status().is_success() || response.status().is_redirection() => {
+            ProbeStatus::Reachable(response.status())
+        }
+        Ok(response) => ProbeStatus::HttpError(response.status()),
+        Err(e) => classify_error(&e),
+    };
+
+    ProbeResult {
+        url: url.to_string(),
+        status,
+        latency: start.elapsed(),
+    }
+}
+
+/// reqwest doesn't expose DNS or TLS failures directly, so look through the error chain
+fn classify_error(error: &reqwest::Error) -> ProbeStatus {
+    if error.is_timeout() {
+        return ProbeStatus::Timeout;
+    }
+
+    let mut chain = Vec::new();
+    let mut source = error.source();
+    while let Some(e) = source {
+        chain.push(e.to_string());
+        source = e.source();
+    }
+    let details = chain.join(": ").to_lowercase();
+
+    if details.contains("dns error") || details.contains("failed to lookup address") {
+        ProbeStatus::DnsFailure
+    } else if ["ssl", "tls", "certificate", "handshake"]
+        .iter()
+        .any(|word| details.contains(word))
+    {
+        ProbeStatus::TlsFailure
+    } else {
+        ProbeStatus::ConnectionFailed(chain.last().cloned().unwrap_or_else(|| error.to_string()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tokio::io::{AsyncReadExt, AsyncWriteExt};
+    use tokio::net::TcpListener;
+
+    /// Serve every request on a local port with the given status line
+    async fn mock_server(status_line: &'static str) -> String {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        tokio::spawn(async move {
+            while let Ok((mut socket, _)) = listener.accept().await {
+                let mut buf = [0; 1024];
+                let _ = socket.read(&mut buf).await;
+                let response = format!("HTTP/1.1 {}\r\ncontent-length: 0\r\n\r\n", status_line);
+                let _ = socket.write_all(response.as_bytes()).await;
+            }
+        });
+        addr.to_string()
+    }
+
+    #[tokio::test]
+    async fn test_probe_all() {
+        let ok = mock_server("200 OK").await;
+        let broken = mock_server("503 Service Unavailable").await;
+        let urls = vec![
+            format!("http://{}", ok),
+            format!("http://{}", broken),
+            // A plain HTTP server can't complete a TLS handshake
+            format!("https://{}", ok),
+            "http://gdenv-probe.invalid".to_string(),
+        ];
+
+        let client = Client::new();
+        let results = probe_all(&client, &urls, Duration::from_secs(5)).await;
+        let statuses: Vec<_> = results.iter().map(|r| r.status.clone()).collect();
+
+        assert_eq!(
+            statuses,
+            [
+                ProbeStatus::Reachable(StatusCode::OK),
+                ProbeStatus::HttpError(StatusCode::SERVICE_UNAVAILABLE),
+                ProbeStatus::TlsFailure,
+                ProbeStatus::DnsFailure,
+            ]
+        );
+        assert_eq!(results[0].url, urls[0]);
+        assert!(results[0].status.is_reachable());
+    }
+
+    #[test]
+    fn test_sources_include_mirrors() {
+        let dir = tempfile::tempdir().unwrap();
+        let mut config = Config::with_data_dir(dir.path());
+        config.settings.mirrors = vec!["https://mirror.example".to_string()];
+
+        assert_eq!(
+            sources(&config),
+            [
+                "https://api.github.com",
+                GITHUB_DOWNLOADS_URL,
+                "https://mirror.example"
+            ]
+        );
+    }
+}
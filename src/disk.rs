@@ -0,0 +1,180 @@
+use std::fs;
+use std::io;
+use std::path::Path;
+use thiserror::Error;
+
+use crate::{godot::GodotVersion, ui::format_size};
+
+#[derive(Error, Debug, PartialEq, Eq)]
+#[error(
+    "Not enough disk space: {} needed, {} available",
+    format_size(*.required),
+    format_size(*.available)
+)]
+pub struct DiskSpaceError {
+    pub required: u64,
+    pub available: u64,
+}
+
+/// Checks that a filesystem can hold a download and its extracted installation
+pub struct DiskSpaceChecker {
+    version: GodotVersion,
+}
+
+impl DiskSpaceChecker {
+    pub fn new(version: &GodotVersion) -> Self {
+        Self {
+            version: version.clone(),
+        }
+    }
+
+    /// Space needed for an archive of `archive_bytes` plus its extracted installation
+    pub fn required_bytes(&self, archive_bytes: u64) -> u64 {
+        archive_bytes + self.version.estimated_install_size(archive_bytes)
+    }
+
+    /// Fail with a [`DiskSpaceError`] if the filesystem of `install_dir` is too full
+    pub fn check(&self, install_dir: &Path, archive_bytes: u64) -> anyhow::Result<()> {
+        let available = available_space(install_dir)?;
+        self.check_available(archive_bytes, available)?;
+        Ok(())
+    }
+
+    fn check_available(&self, archive_bytes: u64, available: u64) -> Result<(), DiskSpaceError> {
+        let required = self.required_bytes(archive_bytes);
+        if available < required {
+            return Err(DiskSpaceError {
+                required,
+                available,
+            });
+        }
+        Ok(())
+    }
+}
+
+/// Free space available to the current user on the filesystem containing `path`
+/// `path` doesn't have to exist yet; its closest existing ancestor is used.
+pub fn available_space(path: &Path) -> io::Result<u64> {
+    let existing = path
+        .ancestors()
+        .find(|dir| dir.exists())
+        .ok_or_else(|| io::Error::new(io::ErrorKind::NotFound, "No existing parent directory"))?;
+
+    platform_available_space(existing)
+}
+
+/// Total size of the files under `path`, without following symlinks
+pub fn dir_size(path: &Path) -> io::Result<u64> {
+    let metadata = fs::symlink_metadata(path)?;
+    if !metadata.is_dir() {
+        return Ok(metadata.len());
+    }
+
+    let mut total = 0;
+    for entry in fs::read_dir(path)? {
+        total += dir_size(&entry?.path())?;
+    }
+    Ok(total)
+}
+
+/// Whether two existing paths are on the same filesystem, so they can be renamed across
+#[cfg(unix)]
+pub fn same_filesystem(a: &Path, b: &Path) -> io::Result<bool> {
+    use std::os::unix::fs::MetadataExt;
+    Ok(fs::metadata(a)?.dev() == fs::metadata(b)?.dev())
+}
+
+/// Whether two existing paths are on the same filesystem, so they can be renamed across
+#[cfg(windows)]
+pub fn same_filesystem(a: &Path, b: &Path) -> io::Result<bool> {
+    let volume = |path: &Path| -> io::Result<_> {
+        Ok(path
+            .canonicalize()?
+            .components()
+            .next()
+            .map(|c| c.as_os_str().to_ascii_lowercase()))
+    };
+    Ok(volume(a)? == volume(b)?)
+}
+
+#[cfg(unix)]
+fn platform_available_space(path: &Path) -> io::Result<u64> {
+    use std::ffi::CString;
+    use std::os::unix::ffi::OsStrExt;
+
+    let c_path = CString::new(path.as_os_str().as_bytes())
+        .map_err(|e| io::Error::new(io::ErrorKind::InvalidInput, e))?;
+    let mut stat: libc::statvfs = unsafe { std::mem::zeroed() };
+
+    // SAFETY: `c_path` is a valid NUL-terminated string and `stat` is a valid out pointer
+    if unsafe { libc::statvfs(c_path.as_ptr(), &mut stat) } != 0 {
+        return Err(io::Error::last_os_error());
+    }
+
+    #[allow(clippy::unnecessary_cast)]
+    Ok(stat.f_bavail as u64 * stat.f_frsize as u64)
+}
+
+#[cfg(windows)]
+fn platform_available_space(path: &Path) -> io::Result<u64> {
+    use std::os::windows::ffi::OsStrExt;
+    use windows_sys::Win32::Storage::FileSystem::GetDiskFreeSpaceExW;
+
+    let wide_path: Vec<u16> = path.as_os_str().encode_wide().chain(Some(0)).collect();
+    let mut available = 0u64;
+
+    // SAFETY: `wide_path` is NUL-terminated and the out pointers are valid or null
+    let ok = unsafe {
+        GetDiskFreeSpaceExW(
+            wide_path.as_ptr(),
+            &mut available,
+            std::ptr::null_mut(),
+            std::ptr::null_mut(),
+        )
+    };
+    if ok == 0 {
+        return Err(io::Error::last_os_error());
+    }
+
+    Ok(available)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_check_available() {
+        let checker = DiskSpaceChecker::new(&GodotVersion::new("4.2.1", false).unwrap());
+        assert_eq!(checker.required_bytes(100), 250);
+        assert!(checker.check_available(100, 250).is_ok());
+        assert_eq!(
+            checker.check_available(100, 249),
+            Err(DiskSpaceError {
+                required: 250,
+                available: 249
+            })
+        );
+
+        let dotnet = DiskSpaceChecker::new(&GodotVersion::new("4.2.1", true).unwrap());
+        assert_eq!(dotnet.required_bytes(100), 350);
+    }
+
+    #[test]
+    fn test_available_space_of_missing_dir() {
+        let dir = tempfile::tempdir().unwrap();
+        let space = available_space(&dir.path().join("not/created/yet")).unwrap();
+        assert!(space > 0);
+    }
+
+    #[test]
+    fn test_dir_size() {
+        let dir = tempfile::tempdir().unwrap();
+        fs::create_dir_all(dir.path().join("a/b")).unwrap();
+        fs::write(dir.path().join("a/one"), [0; 10]).unwrap();
+        fs::write(dir.path().join("a/b/two"), [0; 32]).unwrap();
+
+        assert_eq!(dir_size(dir.path()).unwrap(), 42);
+        assert!(same_filesystem(dir.path(), &dir.path().join("a")).unwrap());
+    }
+}
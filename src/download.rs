@@ -0,0 +1,271 @@
+use anyhow::{anyhow, Result};
+use futures_util::StreamExt;
+use indicatif::{ProgressBar, ProgressStyle};
+use reqwest::header::{HeaderMap, CONTENT_RANGE, RANGE};
+use reqwest::{Client, StatusCode};
+use std::path::{Path, PathBuf};
+use tokio::fs::{self, OpenOptions};
+use tokio::io::AsyncWriteExt;
+
+use crate::ui;
+
+/// Where a download into `path` is kept until it completes, e.g. "Godot.zip.part"
+pub fn part_path(path: &Path) -> PathBuf {
+    let mut name = path.file_name().unwrap_or_default().to_os_string();
+    name.push(".part");
+    path.with_file_name(name)
+}
+
+/// Download `url` to `path`, continuing from the `.part` file an interrupted attempt
+/// left behind with a `Range` request. If the server answers 416, ignores the range
+/// and sends the whole file, or resumes from anywhere but the end of the `.part` file,
+/// the download starts over. `path` only appears once the
+/// download is complete; `total_size` only sizes the progress bar.
+pub async fn resume(
+    client: &Client,
+    url: &str,
+    name: &str,
+    total_size: u64,
+    path: &Path,
+) -> Result<()> {
+    let part = part_path(path);
+    let mut offset = match fs::metadata(&part).await {
+        Ok(metadata) => metadata.len(),
+        Err(_) => 0,
+    };
+
+    let mut response = if offset > 0 {
+        client
+            .get(url)
+            .header(RANGE, format!("bytes={}-", offset))
+            .send()
+            .await?
+    } else {
+        client.get(url).send().await?
+    };
+
+    if response.status() == StatusCode::RANGE_NOT_SATISFIABLE {
+        ui::warning("The server can't resume this download, starting over");
+        offset = 0;
+        response = client.get(url).send().await?;
+    }
+
+    if !response.status().is_success() {
+        return Err(anyhow!("Download failed: {}", response.status()));
+    }
+
+    let mut resumed = offset > 0 && response.status() == StatusCode::PARTIAL_CONTENT;
+    if resumed && content_range_start(response.headers()) != Some(offset) {
+        ui::warning("The server resumed the download from the wrong place, starting over");
+        resumed = false;
+        offset = 0;
+        response = client.get(url).send().await?;
+        if !response.status().is_success() {
+            return Err(anyhow!("Download failed: {}", response.status()));
+        }
+    }
+
+    if resumed {
+        println!("📥 Resuming {} from {}", name, ui::format_size(offset));
+    } else {
+        if offset > 0 {
+            ui::warning("The server doesn't support resuming downloads, starting over");
+        }
+        offset = 0;
+        println!("📥 Downloading {}", name);
+    }
+
+    let pb = ProgressBar::new(total_size.max(offset));
+    pb.set_style(
+        ProgressStyle::default_bar()
+            .template("{spinner:.green} [{elapsed_precise}] [{wide_bar:.cyan/blue}] {bytes}/{total_bytes} ({bytes_per_sec}, {eta})")
+            .unwrap()
+            .progress_chars("#>-"),
+    );
+    pb.set_position(offset);
+
+    let mut file = OpenOptions::new()
+        .create(true)
+        .write(true)
+        .append(resumed)
+        .truncate(!resumed)
+        .open(&part)
+        .await?;
+    let mut downloaded = offset;
+    let mut stream = response.bytes_stream();
+
+    while let Some(chunk) = stream.next().await {
+        let chunk = chunk?;
+        file.write_all(&chunk).await?;
+        downloaded += chunk.len() as u64;
+        pb.set_position(downloaded);
+    }
+
+    file.flush().await?;
+    drop(file);
+    fs::rename(&part, path).await?;
+    pb.finish_with_message("✅ Download complete");
+
+    Ok(())
+}
+
+/// The first byte a partial response holds, from its `Content-Range: bytes <start>-<end>/<size>`
+fn content_range_start(headers: &HeaderMap) -> Option<u64> {
+    let range = headers.get(CONTENT_RANGE)?.to_str().ok()?;
+    let (start, _) = range.strip_prefix("bytes ")?.split_once('-')?;
+    start.trim().parse().ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::{Arc, Mutex};
+    use tokio::io::AsyncReadExt;
+    use tokio::net::TcpListener;
+
+    const BODY: &[u8] = b"0123456789abcdef";
+
+    /// Serve one response per request, built from the request's `Range` offset (if any),
+    /// and record the requests
+    async fn mock_server(
+        respond: fn(Option<usize>) -> Vec<u8>,
+        requests: usize,
+    ) -> (String, Arc<Mutex<Vec<String>>>) {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = format!("http://{}/Godot.zip", listener.local_addr().unwrap());
+        let seen = Arc::new(Mutex::new(Vec::new()));
+
+        let log = seen.clone();
+        tokio::spawn(async move {
+            for _ in 0..requests {
+                let (mut socket, _) = listener.accept().await.unwrap();
+                let mut buf = [0; 4096];
+                let n = socket.read(&mut buf).await.unwrap();
+                let request = String::from_utf8_lossy(&buf[..n]).to_lowercase();
+                let offset = request
+                    .lines()
+                    .find_map(|line| line.strip_prefix("range: bytes="))
+                    .and_then(|range| range.trim().trim_end_matches('-').parse().ok());
+                log.lock().unwrap().push(request);
+                socket.write_all(&respond(offset)).await.unwrap();
+            }
+        });
+
+        (url, seen)
+    }
+
+    /// A 206 response with `BODY` from `start` on
+    fn partial(start: usize) -> Vec<u8> {
+        let mut response = format!(
+            "HTTP/1.1 206 Partial Content\r\ncontent-range: bytes {}-{}/{}\r\n\
+             content-length: {}\r\nconnection: close\r\n\r\n",
+            start,
+            BODY.len() - 1,
+            BODY.len(),
+            BODY.len() - start
+        )
+        .into_bytes();
+        response.extend_from_slice(&BODY[start..]);
+        response
+    }
+
+    fn response(status: &str, body: &[u8]) -> Vec<u8> {
+        let mut response = format!(
+            "HTTP/1.1 {}\r\ncontent-length: {}\r\nconnection: close\r\n\r\n",
+            status,
+            body.len()
+        )
+        .into_bytes();
+        response.extend_from_slice(body);
+        response
+    }
+
+    async fn download_with_part(
+        respond: fn(Option<usize>) -> Vec<u8>,
+        requests: usize,
+    ) -> Vec<String> {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("Godot.zip");
+        std::fs::write(part_path(&path), &BODY[..6]).unwrap();
+
+        let (url, seen) = mock_server(respond, requests).await;
+        let client = Client::new();
+        resume(&client, &url, "Godot.zip", BODY.len() as u64, &path)
+            .await
+            .unwrap();
+
+        assert_eq!(std::fs::read(&path).unwrap(), BODY);
+        assert!(!part_path(&path).exists());
+        let requests = seen.lock().unwrap().clone();
+        requests
+    }
+
+    #[test]
+    fn test_part_path() {
+        assert_eq!(
+            part_path(Path::new("/cache/Godot_v4.3-stable_linux.x86_64.zip")),
+            Path::new("/cache/Godot_v4.3-stable_linux.x86_64.zip.part")
+        );
+    }
+
+    #[tokio::test]
+    async fn test_resume_with_range() {
+        let requests = download_with_part(
+            |offset| match offset {
+                Some(offset) => partial(offset),
+                None => response("200 OK", BODY),
+            },
+            1,
+        )
+        .await;
+        assert!(requests[0].contains("range: bytes=6-"));
+    }
+
+    #[tokio::test]
+    async fn test_resume_from_wrong_offset() {
+        // A server that answers a range from 6 with bytes from 4 on
+        let requests = download_with_part(
+            |offset| match offset {
+                Some(_) => partial(4),
+                None => response("200 OK", BODY),
+            },
+            2,
+        )
+        .await;
+        assert!(requests[0].contains("range: bytes=6-"));
+        assert!(!requests[1].contains("range:"));
+    }
+
+    #[tokio::test]
+    async fn test_resume_ignored_range() {
+        let requests = download_with_part(|_| response("200 OK", BODY), 1).await;
+        assert_eq!(requests.len(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_resume_range_not_satisfiable() {
+        let requests = download_with_part(
+            |offset| match offset {
+                Some(_) => response("416 Range Not Satisfiable", b""),
+                None => response("200 OK", BODY),
+            },
+            2,
+        )
+        .await;
+        assert!(requests[0].contains("range:"));
+        assert!(!requests[1].contains("range:"));
+    }
+
+    #[tokio::test]
+    async fn test_fresh_download() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("Godot.zip");
+        let (url, seen) = mock_server(|_| response("200 OK", BODY), 1).await;
+
+        resume(&Client::new(), &url, "Godot.zip", 0, &path)
+            .await
+            .unwrap();
+        assert_eq!(std::fs::read(&path).unwrap(), BODY);
+        assert!(!seen.lock().unwrap()[0].contains("range:"));
+    }
+}
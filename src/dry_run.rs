@@ -0,0 +1,36 @@
+/// Whether a step makes its changes, or with --dry-run only says what it would do:
+/// to people, or (with --json) to nobody, since other tools read a plan instead
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum DryRunMode {
+    #[default]
+    Off,
+    Report,
+    Json,
+}
+
+impl DryRunMode {
+    pub fn new(dry_run: bool, json: bool) -> Self {
+        match (dry_run, json) {
+            (false, _) => Self::Off,
+            (true, false) => Self::Report,
+            (true, true) => Self::Json,
+        }
+    }
+
+    pub fn is_dry_run(self) -> bool {
+        self != Self::Off
+    }
+
+    /// In a dry run, say what the step would do (unless printing JSON) and return true
+    /// so the caller skips it; otherwise return false so the caller does it
+    pub fn skip(self, action: impl FnOnce() -> String) -> bool {
+        match self {
+            Self::Off => false,
+            Self::Report => {
+                println!("  Would {}", action());
+                true
+            }
+            Self::Json => true,
+        }
+    }
+}
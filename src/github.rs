@@ -1,10 +1,17 @@
 use anyhow::{anyhow, Result};
 use chrono::{DateTime, Utc};
-use indicatif::{ProgressBar, ProgressStyle};
-use reqwest::Client;
+use reqwest::{Client, RequestBuilder};
 use serde::{Deserialize, Serialize};
-use std::path::Path;
-use tokio::io::AsyncWriteExt;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+use crate::{
+    config::Config,
+    download,
+    godot::GodotVersion,
+    remote::{self, Backoff},
+};
 
 #[derive(Debug, Clone, Deserialize, Serialize)]
 pub struct GitHubRelease {
@@ -25,12 +32,13 @@ pub struct GitHubAsset {
 impl GitHubRelease {
     /// Get platform patterns for asset matching, in order of preference
     pub fn get_platform_patterns() -> Vec<&'static str> {
-        let os = std::env::consts::OS;
+        let os = GodotVersion::target_os();
         let arch = std::env::consts::ARCH;
 
         match (os, arch) {
             ("windows", "x86_64") => vec!["win64"],
             ("windows", "x86") => vec!["win32", "win64"], // Fallback to 64-bit if 32-bit not available
+            ("windows", "aarch64") => vec!["windows_arm64"], // Native builds since 4.3
             ("macos", _) => vec!["macos"],                // macOS universal binaries
             ("linux", "x86_64") => vec!["linux.x86_64", "linux"], // Prefer specific, fallback to generic
             ("linux", "x86") => vec!["linux.x86_32", "linux.x86_64", "linux"],
@@ -56,8 +64,29 @@ impl GitHubRelease {
         }
     }
 
+    /// Whether the release has a .NET build for any platform
+    pub fn has_dotnet_build(&self) -> bool {
+        self.assets.iter().any(|asset| {
+            let name = asset.name.to_lowercase();
+            name.contains("_mono_") && name.ends_with(".zip")
+        })
+    }
+
     /// Find a Godot asset for the current platform
     pub fn find_godot_asset(&self, is_dotnet: bool) -> Option<&GitHubAsset> {
+        // The archive named by the version's platform suffix, which handles Godot 3's
+        // names and can't pick e.g. 3.x's linux_server builds by accident
+        let exact = GodotVersion::from_tag(&self.tag_name)
+            .ok()
+            .map(|version| GodotVersion {
+                is_dotnet,
+                ..version
+            })
+            .and_then(|version| version.archive_name().ok());
+        if let Some(asset) = exact.and_then(|name| self.assets.iter().find(|a| a.name == name)) {
+            return Some(asset);
+        }
+
         let platform_patterns = Self::get_platform_patterns();
 
         // Try to find an asset matching our platform patterns (in order of preference)
@@ -81,86 +110,205 @@ impl GitHubRelease {
 pub struct GitHubClient {
     client: Client,
     api_url: String,
+    backoff: Backoff,
+    /// From `GDENV_GITHUB_TOKEN`, sent with API requests only
+    token: Option<String>,
+    /// Where the release list is cached, and for how long (the `cache_ttl_hours` setting)
+    releases_cache: Option<(PathBuf, Duration)>,
+    /// The `offline` setting: every request fails without touching the network
+    offline: bool,
 }
 
-impl GitHubClient {
-    pub fn new(api_url: String) -> Self {
-        let client = Client::builder()
-            .user_agent("gdenv/0.1.0")
-            .build()
-            .expect("Failed to create HTTP client");
+/// Name of the file in the cache directory the release list is kept in
+pub const RELEASES_CACHE_FILE: &str = "releases.json";
 
-        Self { client, api_url }
+impl GitHubClient {
+    /// Create a client for the configured API URL, honoring the `proxy` setting
+    pub fn from_config(config: &Config) -> Self {
+        let ttl_hours = config.settings.cache_ttl_hours;
+        Self {
+            offline: config.settings.offline,
+            releases_cache: (ttl_hours > 0).then(|| {
+                (
+                    config.cache_dir.join(RELEASES_CACHE_FILE),
+                    Duration::from_secs(ttl_hours * 60 * 60),
+                )
+            }),
+            ..Self::with_proxy(
+                config.github_api_url.clone(),
+                config.settings.proxy.as_deref(),
+                Backoff::new(config.settings.retries),
+            )
+        }
     }
 
-    pub async fn get_godot_releases(
-        &self,
-        include_prereleases: bool,
-    ) -> Result<Vec<GitHubRelease>> {
-        let url = format!("{}/repos/godotengine/godot-builds/releases", self.api_url);
+    fn with_proxy(api_url: String, proxy: Option<&str>, backoff: Backoff) -> Self {
+        let mut builder = remote::client_builder();
 
-        println!("🔍 Fetching available Godot versions...");
+        if let Some(proxy) = proxy {
+            match reqwest::Proxy::all(proxy) {
+                Ok(proxy) => builder = builder.proxy(proxy),
+                Err(e) => crate::ui::warning(&format!("Ignoring invalid proxy '{}': {}", proxy, e)),
+            }
+        }
 
-        let response = self.client.get(&url).send().await?;
+        let client = builder.build().expect("Failed to create HTTP client");
+
+        Self {
+            client,
+            api_url,
+            backoff,
+            token: std::env::var(remote::GITHUB_TOKEN_VAR)
+                .ok()
+                .filter(|token| !token.trim().is_empty()),
+            releases_cache: None,
+            offline: false,
+        }
+    }
 
-        if !response.status().is_success() {
-            return Err(anyhow!("GitHub API request failed: {}", response.status()));
+    /// Fail when the `offline` setting is on, before any request is made
+    fn ensure_online(&self) -> Result<()> {
+        if self.offline {
+            return Err(anyhow!(
+                "gdenv is offline; run 'gdenv config set offline false' to allow network access"
+            ));
+        }
+        Ok(())
+    }
+
+    /// A GET request to the GitHub API, authenticated if a token is set
+    fn api_get(&self, url: &str) -> RequestBuilder {
+        let request = self.client.get(url);
+        match &self.token {
+            Some(token) => request.bearer_auth(token.trim()),
+            None => request,
         }
+    }
+
+    /// The underlying HTTP client, for requests outside the GitHub API. Fails when the
+    /// `offline` setting is on.
+    pub fn http_client(&self) -> Result<&Client> {
+        self.ensure_online()?;
+        Ok(&self.client)
+    }
 
-        let mut releases: Vec<GitHubRelease> = response.json().await?;
+    /// Every Godot release, newest first, from the cache while it's fresh
+    pub async fn get_godot_releases(
+        &self,
+        include_prereleases: bool,
+    ) -> Result<Vec<GitHubRelease>> {
+        let mut releases = match self.cached_releases() {
+            Some(releases) => releases,
+            None => self.refresh_godot_releases().await?,
+        };
 
         if !include_prereleases {
             releases.retain(|r| !r.prerelease);
         }
 
         // Sort by published date (newest first)
+        #[allow(clippy::unnecessary_sort_by)]
         releases.sort_by(|a, b| b.published_at.cmp(&a.published_at));
 
         Ok(releases)
     }
 
-    pub async fn download_asset_with_progress(
-        &self,
-        asset: &GitHubAsset,
-        path: &Path,
-    ) -> Result<()> {
-        println!("📥 Downloading {}", asset.name);
+    /// Fetch every Godot release, prereleases included, even if the cache is still fresh,
+    /// and cache them
+    pub async fn refresh_godot_releases(&self) -> Result<Vec<GitHubRelease>> {
+        let releases = self.fetch_godot_releases().await?;
+        self.cache_releases(&releases);
+        Ok(releases)
+    }
 
-        let response = self.client.get(&asset.browser_download_url).send().await?;
+    /// The cached release list, unless it's older than the cache TTL or unreadable
+    fn cached_releases(&self) -> Option<Vec<GitHubRelease>> {
+        let (path, ttl) = self.releases_cache.as_ref()?;
+        let age = fs::metadata(path).ok()?.modified().ok()?.elapsed().ok()?;
+        if age > *ttl {
+            return None;
+        }
+        serde_json::from_slice(&fs::read(path).ok()?).ok()
+    }
 
-        if !response.status().is_success() {
-            return Err(anyhow!("Download failed: {}", response.status()));
+    /// Cache the release list. Failing to is harmless: the next command fetches it again.
+    fn cache_releases(&self, releases: &[GitHubRelease]) {
+        if let Some((path, _)) = &self.releases_cache {
+            if let Ok(json) = serde_json::to_vec(releases) {
+                let _ = fs::write(path, json);
+            }
         }
+    }
 
-        let total_size = asset.size;
+    /// Fetch every release, prereleases included, from the GitHub API
+    async fn fetch_godot_releases(&self) -> Result<Vec<GitHubRelease>> {
+        self.ensure_online()?;
+        let mut url = Some(format!(
+            "{}/repos/godotengine/godot-builds/releases?per_page=100",
+            self.api_url
+        ));
 
-        // Create progress bar
-        let pb = ProgressBar::new(total_size);
-        pb.set_style(
-            ProgressStyle::default_bar()
-                .template("{spinner:.green} [{elapsed_precise}] [{wide_bar:.cyan/blue}] {bytes}/{total_bytes} ({bytes_per_sec}, {eta})")
-                .unwrap()
-                .progress_chars("#>-"),
-        );
+        println!("🔍 Fetching available Godot versions...");
 
-        // Create the file
-        let mut file = tokio::fs::File::create(path).await?;
-        let mut downloaded = 0u64;
-        let mut stream = response.bytes_stream();
+        // The API returns at most 100 releases a page, so follow the pages to the oldest
+        let mut releases: Vec<GitHubRelease> = Vec::new();
+        while let Some(page_url) = url {
+            let response = remote::send_with_backoff(self.api_get(&page_url), self.backoff).await?;
 
-        use futures_util::StreamExt;
+            if !response.status().is_success() {
+                return Err(remote::github_api_error(&response));
+            }
 
-        while let Some(chunk) = stream.next().await {
-            let chunk = chunk?;
-            file.write_all(&chunk).await?;
-            downloaded += chunk.len() as u64;
-            pb.set_position(downloaded);
+            url = remote::next_page_url(response.headers());
+            releases.extend(response.json::<Vec<GitHubRelease>>().await?);
         }
 
-        file.flush().await?;
-        pb.finish_with_message("✅ Download complete");
+        Ok(releases)
+    }
 
-        Ok(())
+    /// Get the latest release of gdenv itself
+    pub async fn get_latest_gdenv_release(&self) -> Result<GitHubRelease> {
+        self.ensure_online()?;
+        let url = format!("{}/repos/bytemeadow/gdenv/releases/latest", self.api_url);
+
+        let response = remote::send_with_backoff(self.api_get(&url), self.backoff).await?;
+
+        if !response.status().is_success() {
+            return Err(remote::github_api_error(&response));
+        }
+
+        Ok(response.json().await?)
+    }
+
+    /// Download any URL to `path`, resuming an interrupted download of it.
+    /// `total_size` only sizes the progress bar.
+    pub async fn download_with_progress(
+        &self,
+        url: &str,
+        name: &str,
+        total_size: u64,
+        path: &Path,
+    ) -> Result<()> {
+        download::resume(self.http_client()?, url, name, total_size, path).await
+    }
+}
+
+/// A release with the named assets, a prerelease if its tag names one
+#[cfg(test)]
+pub fn fake_release(tag: &str, assets: &[&str]) -> GitHubRelease {
+    GitHubRelease {
+        tag_name: tag.to_string(),
+        name: tag.to_string(),
+        published_at: chrono::Utc::now(),
+        prerelease: GodotVersion::from_tag(tag).is_ok_and(|v| v.is_prerelease()),
+        assets: assets
+            .iter()
+            .map(|name| GitHubAsset {
+                name: name.to_string(),
+                browser_download_url: format!("https://example.com/{}", name),
+                size: 0,
+            })
+            .collect(),
     }
 }
 
@@ -285,4 +433,61 @@ mod tests {
 
         assert_eq!(release_v.version(), Some("4.3.0-beta2".to_string()));
     }
+
+    #[tokio::test]
+    async fn test_releases_cache() {
+        let root = tempfile::tempdir().unwrap();
+        let mut config = Config::with_data_dir(root.path());
+        config.github_api_url = "http://127.0.0.1:9".to_string();
+        fs::create_dir_all(&config.cache_dir).unwrap();
+
+        fs::write(
+            config.cache_dir.join(RELEASES_CACHE_FILE),
+            serde_json::to_vec(&[
+                fake_release("4.3-stable", &[]),
+                fake_release("4.4-beta1", &[]),
+            ])
+            .unwrap(),
+        )
+        .unwrap();
+
+        // A fresh cache is used without asking the (unreachable) API
+        let releases = GitHubClient::from_config(&config)
+            .get_godot_releases(false)
+            .await
+            .unwrap();
+        assert_eq!(releases.len(), 1);
+        assert_eq!(releases[0].tag_name, "4.3-stable");
+
+        // A TTL of 0 turns the cache off
+        config.settings.cache_ttl_hours = 0;
+        assert!(GitHubClient::from_config(&config)
+            .get_godot_releases(false)
+            .await
+            .is_err());
+    }
+
+    #[tokio::test]
+    async fn test_offline_fails_fast() {
+        let root = tempfile::tempdir().unwrap();
+        let mut config = Config::with_data_dir(root.path());
+        config.settings.offline = true;
+        config.settings.cache_ttl_hours = 0;
+        let client = GitHubClient::from_config(&config);
+
+        let offline = |result: Result<()>| {
+            let error = result.unwrap_err().to_string();
+            assert!(error.contains("offline"), "{}", error);
+        };
+        offline(client.get_godot_releases(true).await.map(|_| ()));
+        offline(client.get_latest_gdenv_release().await.map(|_| ()));
+        offline(client.http_client().map(|_| ()));
+        let path = root.path().join("godot.zip");
+        offline(
+            client
+                .download_with_progress("https://example.com/godot.zip", "Godot", 0, &path)
+                .await,
+        );
+        assert!(!path.exists());
+    }
 }
@@ -1,33 +1,466 @@
-use anyhow::Result;
-use semver::Version;
+use anyhow::{anyhow, Result};
+use semver::{Version, VersionReq};
 use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::cmp::Ordering;
 use std::fmt;
+use std::path::{Path, PathBuf};
 use std::str::FromStr;
+use std::sync::OnceLock;
+use thiserror::Error;
 
-#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+use crate::project;
+
+/// Matches the version Godot prints for `--version` (e.g. "4.2.1.stable.mono.official.b09f793f5")
+/// or in its banner (e.g. "Godot Engine v3.5.3.stable.official.6c814135b")
+pub const VERSION_OUTPUT_REGEX: &str = r"(?:Godot Engine v|\b)(\d+)\.(\d+)(?:\.(\d+))?\.(stable|(?:dev|alpha|beta|rc)\d*)((?:\.[a-z_]+)*)";
+
+/// Environment variable that picks which builds to manage inside WSL
+pub const WSL_MODE_VAR: &str = "GDENV_WSL_MODE";
+
+/// [`WSL_MODE_VAR`] value that selects Windows builds instead of Linux ones
+pub const WSL_NATIVE_WINDOWS: &str = "native-windows";
+
+/// Official names of Godot releases by (major, minor), for releases named beyond their number.
+/// Godot doesn't name its releases yet, so this is empty.
+const MARKETING_NAMES: &[((u64, u64), &str)] = &[];
+
+/// An inclusive range of file sizes in bytes
+type SizeRange = (u64, u64);
+
+/// Plausible sizes of the editor executable by (platform suffix, major version): half to
+/// one and a half times its typical size, so only truncated or wrong files stand out
+const EXECUTABLE_SIZE_RANGES: &[((&str, u64), SizeRange)] = &[
+    (("linux.x86_64", 4), around_mib(130)),
+    (("linux.x86_32", 4), around_mib(120)),
+    (("linux.arm64", 4), around_mib(110)),
+    (("linux.arm32", 4), around_mib(90)),
+    (("win64.exe", 4), around_mib(120)),
+    (("win32.exe", 4), around_mib(110)),
+    (("windows_arm64.exe", 4), around_mib(110)),
+    // Universal binaries hold both the x86_64 and the arm64 editor
+    (("macos.universal", 4), around_mib(240)),
+    (("x11.64", 3), around_mib(75)),
+    (("x11.32", 3), around_mib(70)),
+    (("win64.exe", 3), around_mib(70)),
+    (("win32.exe", 3), around_mib(60)),
+    (("osx.universal", 3), around_mib(150)),
+];
+
+/// A macOS version as (major, minor), e.g. (10, 15) for Catalina
+pub type MacosVersion = (u32, u32);
+
+/// The oldest macOS each Godot series runs on, keyed by the first (major, minor)
+/// Godot version it applies to, in ascending order
+const MINIMUM_MACOS_VERSIONS: &[((u64, u64), MacosVersion)] =
+    &[((3, 0), (10, 12)), ((4, 0), (10, 15))];
+
+/// The range from half to one and a half times `typical` MiB, in bytes
+const fn around_mib(typical: u64) -> SizeRange {
+    let typical = typical * 1024 * 1024;
+    (typical / 2, typical * 3 / 2)
+}
+
+/// The rendering backends of Godot 4
+#[derive(Debug, Clone, Copy, PartialEq, Eq, clap::ValueEnum)]
+pub enum RenderingMethod {
+    #[value(name = "forward_plus")]
+    ForwardPlus,
+    #[value(name = "mobile")]
+    Mobile,
+    #[value(name = "gl_compatibility")]
+    GlCompatibility,
+}
+
+impl RenderingMethod {
+    /// Whether the method renders through Vulkan (or D3D12/Metal) rather than OpenGL
+    pub fn uses_vulkan(&self) -> bool {
+        matches!(self, Self::ForwardPlus | Self::Mobile)
+    }
+}
+
+impl fmt::Display for RenderingMethod {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::ForwardPlus => write!(f, "forward_plus"),
+            Self::Mobile => write!(f, "mobile"),
+            Self::GlCompatibility => write!(f, "gl_compatibility"),
+        }
+    }
+}
+
+/// The engines Godot can simulate 3D physics with
+#[derive(Debug, Clone, Copy, PartialEq, Eq, clap::ValueEnum)]
+pub enum PhysicsBackend {
+    /// Godot's own physics engine
+    #[value(name = "godot_physics")]
+    GodotPhysics,
+    /// Bullet, the default of Godot 3, removed in Godot 4
+    #[value(name = "bullet")]
+    Bullet,
+    /// Jolt, through the godot-jolt addon from Godot 4.2 and built in from Godot 4.4
+    #[value(name = "jolt")]
+    Jolt,
+}
+
+impl fmt::Display for PhysicsBackend {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::GodotPhysics => write!(f, "godot_physics"),
+            Self::Bullet => write!(f, "bullet"),
+            Self::Jolt => write!(f, "jolt"),
+        }
+    }
+}
+
+/// External memory profilers `gdenv run` can launch Godot under
+#[derive(Debug, Clone, Copy, PartialEq, Eq, clap::ValueEnum)]
+pub enum MemProfiler {
+    Heaptrack,
+    Massif,
+    Dhat,
+}
+
+impl MemProfiler {
+    /// The program that runs Godot under the profiler
+    pub fn binary(&self) -> &'static str {
+        match self {
+            Self::Heaptrack => "heaptrack",
+            Self::Massif | Self::Dhat => "valgrind",
+        }
+    }
+
+    /// Arguments for [`binary`](Self::binary) that make it write its profile to `output`
+    pub fn args(&self, output: &Path) -> Vec<String> {
+        let output = output.display();
+        match self {
+            Self::Heaptrack => vec!["-o".to_string(), output.to_string()],
+            Self::Massif => vec![
+                "--tool=massif".to_string(),
+                format!("--massif-out-file={}", output),
+            ],
+            Self::Dhat => vec![
+                "--tool=dhat".to_string(),
+                format!("--dhat-out-file={}", output),
+            ],
+        }
+    }
+
+    /// A command line that shows the profile written to `output`
+    pub fn analyze_command(&self, output: &Path) -> String {
+        match self {
+            Self::Heaptrack => format!("heaptrack_print {}", output.display()),
+            Self::Massif => format!("ms_print {}", output.display()),
+            // DHAT's viewer is a web page shipped with valgrind
+            Self::Dhat => format!(
+                "open dh_view.html from your valgrind installation and load {}",
+                output.display()
+            ),
+        }
+    }
+
+    /// The file extension of the profile, which heaptrack also picks its compression by
+    fn output_extension(&self) -> &'static str {
+        match self {
+            Self::Heaptrack => "heaptrack.zst",
+            Self::Massif => "massif.out",
+            Self::Dhat => "dhat.json",
+        }
+    }
+}
+
+/// A server Godot builds can be downloaded from
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize, clap::ValueEnum)]
+#[serde(rename_all = "lowercase")]
+pub enum Source {
+    /// The `godotengine/godot-builds` GitHub releases
+    #[default]
+    #[value(name = "github")]
+    GitHub,
+    /// The legacy downloads.tuxfamily.org mirror
+    #[value(name = "tuxfamily")]
+    TuxFamily,
+}
+
+impl Source {
+    pub const ALL: [Source; 2] = [Source::GitHub, Source::TuxFamily];
+
+    /// The sources to try in turn: `preferred` first, then the others unless `only` is set
+    pub fn download_order(preferred: Source, only: bool) -> Vec<Source> {
+        let mut order = vec![preferred];
+        if !only {
+            order.extend(Self::ALL.into_iter().filter(|s| *s != preferred));
+        }
+        order
+    }
+}
+
+impl FromStr for Source {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s {
+            "github" => Ok(Self::GitHub),
+            "tuxfamily" => Ok(Self::TuxFamily),
+            _ => Err(anyhow::anyhow!(
+                "Invalid source '{}' (expected 'github' or 'tuxfamily')",
+                s
+            )),
+        }
+    }
+}
+
+impl fmt::Display for Source {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::GitHub => write!(f, "github"),
+            Self::TuxFamily => write!(f, "tuxfamily"),
+        }
+    }
+}
+
+/// The release stage of a Godot build, from its prerelease tag ("beta2" is `Beta`).
+/// Ordered as the stages come in a release cycle, earliest first.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum GodotChannel {
+    Dev,
+    Alpha,
+    Beta,
+    Rc,
+    Stable,
+}
+
+impl fmt::Display for GodotChannel {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Stable => write!(f, "stable"),
+            Self::Alpha => write!(f, "alpha"),
+            Self::Beta => write!(f, "beta"),
+            Self::Rc => write!(f, "rc"),
+            Self::Dev => write!(f, "dev"),
+        }
+    }
+}
+
+/// The part after `@` in a qualified version string like "4.2.1-dotnet@linux.x86_64"
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum VersionQualifier {
+    /// A platform suffix, e.g. "linux.x86_64" or "x11.64"
+    Platform(&'static str),
+    Source(Source),
+}
+
+impl FromStr for VersionQualifier {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        let platforms = GodotVersion::all_platform_suffixes()
+            .iter()
+            .chain(GodotVersion::all_legacy_platform_suffixes());
+
+        if let Some(platform) = platforms.into_iter().find(|p| **p == s) {
+            Ok(Self::Platform(platform))
+        } else if let Ok(source) = s.parse() {
+            Ok(Self::Source(source))
+        } else {
+            Err(anyhow!(
+                "Unknown version qualifier '@{}' (expected a platform like 'linux.x86_64' or a source like 'github')",
+                s
+            ))
+        }
+    }
+}
+
+/// Serializes as its canonical string (e.g. "4.2.1-dotnet"), see [`DetailedGodotVersion`]
+/// for the field-by-field form. Ordered by release, see the `Ord` impl.
+#[derive(Debug, Clone, PartialEq, Eq)]
 pub struct GodotVersion {
     pub version: Version,
     pub is_dotnet: bool,
 }
 
+/// Architectures Rust targets that Godot publishes no official builds for
+const UNSUPPORTED_ARCHES: &[&str] = &[
+    "riscv64",
+    "powerpc",
+    "powerpc64",
+    "s390x",
+    "mips",
+    "mips64",
+    "loongarch64",
+    "sparc64",
+];
+
+#[derive(Error, Debug, PartialEq, Eq)]
+#[error(
+    "Godot has no official builds for the {arch} architecture; build it yourself and \
+     manage it with `gdenv adopt <path>`"
+)]
+pub struct UnsupportedArchError {
+    pub arch: String,
+}
+
+/// Something a version requirement accepts that is valid semver but risky for Godot
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum GodotVersionParseWarning {
+    /// The requirement accepts several major versions, e.g. ">=4.2" also accepts 5.0;
+    /// projects don't survive Godot's major upgrades unchanged
+    MajorVersionNotPinned { majors: Vec<u64> },
+}
+
+impl fmt::Display for GodotVersionParseWarning {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::MajorVersionNotPinned { majors } => {
+                let majors: Vec<String> = majors.iter().map(|m| format!("{}.x", m)).collect();
+                write!(
+                    f,
+                    "accepts more than one major version ({})",
+                    majors.join(", ")
+                )
+            }
+        }
+    }
+}
+
+/// A semver range of Godot versions, e.g. ">=4.2, <4.3", as a `.godot-version` can pin
+/// instead of one version. Following semver, prereleases only match when a comparator
+/// names a prerelease of the same version, so ">=4.3.0-beta.1, <4.4" accepts 4.3-rc1
+/// but ">=4.2, <4.4" doesn't accept 4.3-beta2.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct GodotVersionReq {
+    pub req: VersionReq,
+}
+
+impl GodotVersionReq {
+    /// Whether `s` is written as a range rather than a single version
+    pub fn is_range(s: &str) -> bool {
+        let s = s.trim();
+        s.starts_with(['<', '>', '=', '~', '^']) || s.contains([',', '*'])
+    }
+
+    /// Pick the newest of `candidates` in the range
+    pub fn resolve(&self, candidates: &[GodotVersion]) -> Option<GodotVersion> {
+        GodotVersion::resolve_latest(candidates, &self.req, false)
+    }
+}
+
+impl FromStr for GodotVersionReq {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        let (req, _) = GodotVersion::parse_semver_requirement(s)?;
+        Ok(Self { req })
+    }
+}
+
+impl fmt::Display for GodotVersionReq {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.req)
+    }
+}
+
+/// A [`GodotVersion`] that serializes as `{version, is_dotnet}` instead of a flat string
+#[allow(dead_code)]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DetailedGodotVersion {
+    pub version: Version,
+    pub is_dotnet: bool,
+}
+
 impl GodotVersion {
     /// Get the platform suffix for the current OS and architecture
-    pub fn get_platform_suffix() -> &'static str {
-        let os = std::env::consts::OS;
-        let arch = std::env::consts::ARCH;
+    pub fn get_platform_suffix() -> Result<&'static str> {
+        Self::get_platform_suffix_for_target(Self::target_os(), std::env::consts::ARCH)
+    }
 
+    /// Get the platform suffix of the builds for an OS and architecture
+    /// (as in `std::env::consts`), or an error if Godot isn't built for them
+    pub fn get_platform_suffix_for_target(os: &str, arch: &str) -> Result<&'static str> {
         match (os, arch) {
-            ("windows", "x86_64") => "win64.exe",
-            ("windows", "x86") => "win32.exe",
-            ("macos", _) => "macos.universal", // macOS universal binaries work on both Intel and Apple Silicon
-            ("linux", "x86_64") => "linux.x86_64",
-            ("linux", "x86") => "linux.x86_32",
-            ("linux", "arm") => "linux.arm32",
-            ("linux", "aarch64") => "linux.arm64",
-            // Fallbacks for common cases
-            ("windows", _) => "win64.exe", // Default to 64-bit on Windows
-            ("linux", _) => "linux.x86_64", // Default to x86_64 on Linux
-            _ => "linux.x86_64",           // Ultimate fallback
+            ("windows", "x86_64") => Ok("win64.exe"),
+            ("windows", "x86") => Ok("win32.exe"),
+            // Native builds since Godot 4.3; older versions have none to install
+            ("windows", "aarch64") => Ok("windows_arm64.exe"),
+            // macOS universal binaries work on both Intel and Apple Silicon
+            ("macos", "x86_64" | "aarch64") => Ok("macos.universal"),
+            ("linux", "x86_64") => Ok("linux.x86_64"),
+            ("linux", "x86") => Ok("linux.x86_32"),
+            ("linux", "arm") => Ok("linux.arm32"),
+            ("linux", "aarch64") => Ok("linux.arm64"),
+            (_, arch) if UNSUPPORTED_ARCHES.contains(&arch) => Err(UnsupportedArchError {
+                arch: arch.to_string(),
+            }
+            .into()),
+            _ => Err(anyhow!(
+                "No Godot builds are available for {} on {}",
+                os,
+                arch
+            )),
+        }
+    }
+
+    /// Get the OS whose builds gdenv manages. This is the host OS, except inside WSL with
+    /// `GDENV_WSL_MODE=native-windows`, where Windows builds are used instead.
+    pub fn target_os() -> &'static str {
+        Self::target_os_for(
+            Self::is_running_in_wsl(),
+            std::env::var(WSL_MODE_VAR).ok().as_deref(),
+        )
+    }
+
+    fn target_os_for(in_wsl: bool, wsl_mode: Option<&str>) -> &'static str {
+        if in_wsl && wsl_mode == Some(WSL_NATIVE_WINDOWS) {
+            "windows"
+        } else {
+            std::env::consts::OS
+        }
+    }
+
+    /// Whether gdenv runs inside the Windows Subsystem for Linux
+    pub fn is_running_in_wsl() -> bool {
+        static IN_WSL: OnceLock<bool> = OnceLock::new();
+        *IN_WSL.get_or_init(|| {
+            cfg!(target_os = "linux")
+                && std::fs::read_to_string("/proc/version").is_ok_and(|v| is_wsl_kernel(&v))
+        })
+    }
+
+    /// Get every platform suffix Godot 4 publishes archives for. Windows on ARM, which
+    /// only has builds since 4.3, comes last.
+    pub fn all_platform_suffixes() -> &'static [&'static str] {
+        &[
+            "win64.exe",
+            "win32.exe",
+            "macos.universal",
+            "linux.x86_64",
+            "linux.x86_32",
+            "linux.arm32",
+            "linux.arm64",
+            "windows_arm64.exe",
+        ]
+    }
+
+    /// Get every platform suffix Godot 3 published archives for
+    pub fn all_legacy_platform_suffixes() -> &'static [&'static str] {
+        &[
+            "win64.exe",
+            "win32.exe",
+            "osx.universal",
+            "x11.64",
+            "x11.32",
+        ]
+    }
+
+    /// Get every platform suffix published for this version's major release
+    pub fn platform_suffixes(&self) -> &'static [&'static str] {
+        if self.version.major < 4 {
+            Self::all_legacy_platform_suffixes()
+        } else if self.version.major == 4 && self.version.minor < 3 {
+            let all = Self::all_platform_suffixes();
+            &all[..all.len() - 1]
+        } else {
+            Self::all_platform_suffixes()
         }
     }
     pub fn new(version_str: &str, is_dotnet: bool) -> Result<Self> {
@@ -42,8 +475,10 @@ impl GodotVersion {
     /// - "4.3.0-beta2" -> "4.3.0-beta.2"
     /// - "4.1.0-rc.1" -> "4.1.0-rc.1"
     /// - "4.2.1-stable" -> "4.2.1"
+    /// - "4.2.1.stable.official.b09f793f5" -> "4.2.1" (as printed by `godot --version`)
     fn normalize_version_string(version_str: &str) -> Result<String> {
-        let version_str = version_str.trim();
+        let (version_str, _) = strip_build_tokens(version_str.trim());
+        let version_str = version_str.as_str();
 
         // Remove common suffixes that aren't standard semver
         let cleaned = version_str.strip_suffix("-stable").unwrap_or(version_str);
@@ -109,6 +544,17 @@ impl GodotVersion {
             }
         }
 
+        if cleaned.contains("-dev") && !cleaned.contains("-dev.") {
+            // Convert "4.4.0-dev5" to "4.4.0-dev.5", so dev10 sorts after dev5
+            if let Some((base, dev_part)) = cleaned.split_once("-dev") {
+                if let Ok(dev_num) = dev_part.parse::<u32>() {
+                    return Ok(format!("{}-dev.{}", base, dev_num));
+                } else if dev_part.is_empty() {
+                    return Ok(format!("{}-dev", base));
+                }
+            }
+        }
+
         Ok(cleaned.to_string())
     }
 
@@ -121,198 +567,2160 @@ impl GodotVersion {
             .replace("-beta.", "-beta")
             .replace("-rc.", "-rc")
             .replace("-alpha.", "-alpha")
+            .replace("-dev.", "-dev")
     }
 
-    /// Get the expected executable path within the extracted directory
-    pub fn get_executable_path(&self) -> String {
-        let os = std::env::consts::OS;
-        let _arch = std::env::consts::ARCH;
-
-        match os {
-            "macos" => {
-                if self.is_dotnet {
-                    "Godot_mono.app/Contents/MacOS/Godot".to_string()
-                } else {
-                    "Godot.app/Contents/MacOS/Godot".to_string()
-                }
-            }
-            "windows" => {
-                let version_part = if self.version.pre.is_empty() {
-                    format!("{}-stable", self.version)
-                } else {
-                    self.godot_version_string()
-                };
-
-                if self.is_dotnet {
-                    format!(
-                        "Godot_v{}_mono_{}/Godot_v{}_mono_{}.exe",
-                        version_part, "win64", version_part, "win64"
-                    )
-                } else {
-                    format!("Godot_v{}_{}.exe", version_part, "win64")
-                }
-            }
-            "linux" => {
-                let version_part = if self.version.pre.is_empty() {
-                    format!("{}-stable", self.version)
-                } else {
-                    self.godot_version_string()
-                };
-
-                let platform_suffix = Self::get_platform_suffix();
-
-                if self.is_dotnet {
-                    // Dotnet versions extract to a subfolder
-                    let folder_name = format!("Godot_v{}_mono_{}", version_part, platform_suffix);
-                    let exe_name = format!("Godot_v{}_mono_{}", version_part, platform_suffix);
-                    format!("{}/{}", folder_name, exe_name)
-                } else {
-                    // Non-dotnet versions extract directly
-                    format!("Godot_v{}_{}", version_part, platform_suffix)
-                }
-            }
-            _ => {
-                // Fallback - just look for Godot executable
-                "Godot".to_string()
-            }
-        }
-    }
-
-    pub fn installation_name(&self) -> String {
+    /// Get the version string used in lockfiles and serialized data, which marks .NET builds
+    /// with a `-dotnet` suffix (e.g. "4.2.1-dotnet"). It parses back with `str::parse`.
+    pub fn canonical_string(&self) -> String {
         if self.is_dotnet {
-            format!("godot-{}-dotnet", self.godot_version_string())
+            format!("{}-dotnet", self.godot_version_string())
         } else {
-            format!("godot-{}", self.godot_version_string())
+            self.godot_version_string()
         }
     }
 
-    #[allow(dead_code)]
-    pub fn archive_name(&self) -> String {
-        let platform_suffix = Self::get_platform_suffix();
+    /// Whether two versions name the same Godot build. `==` compares the parsed fields
+    /// strictly; parsing already makes "4.2.1-stable" equal to "4.2.1" and "4.2" equal
+    /// to "4.2.0". This also ignores semver build metadata, so "4.2.1+b09f793f5" is
+    /// equivalent to "4.2.1" but not equal to it.
+    pub fn semantically_equivalent(&self, other: &GodotVersion) -> bool {
+        let (a, b) = (&self.version, &other.version);
+        (a.major, a.minor, a.patch, &a.pre) == (b.major, b.minor, b.patch, &b.pre)
+            && self.is_dotnet == other.is_dotnet
+    }
+
+    /// Get the Godot release tag for this version, as used in release and archive names
+    /// Examples:
+    /// - "4.2.1" -> "4.2.1-stable"
+    /// - "4.2.0" -> "4.2-stable"
+    /// - "4.3.0-beta2" -> "4.3-beta2"
+    pub fn to_tag(&self) -> String {
+        let version = &self.version;
 
-        let version_part = if self.version.pre.is_empty() {
-            format!("{}-stable", self.version)
+        // Godot drops a zero patch number from its tags
+        let base = if version.patch == 0 {
+            format!("{}.{}", version.major, version.minor)
         } else {
-            self.godot_version_string()
+            format!("{}.{}.{}", version.major, version.minor, version.patch)
         };
 
-        if self.is_dotnet {
-            format!("Godot_v{}_mono_{}.zip", version_part, platform_suffix)
+        if version.pre.is_empty() {
+            format!("{}-stable", base)
         } else {
-            format!("Godot_v{}_{}.zip", version_part, platform_suffix)
+            format!("{}-{}", base, version.pre.as_str().replace('.', ""))
         }
     }
 
-    #[allow(dead_code)]
-    pub fn is_prerelease(&self) -> bool {
-        !self.version.pre.is_empty()
+    /// Parse a Godot release tag (e.g. "4.2-stable", "v4.3-beta2") into a version
+    pub fn from_tag(tag: &str) -> Result<Self> {
+        let tag = tag.trim();
+        Self::new(tag.strip_prefix('v').unwrap_or(tag), false)
     }
-}
 
-impl FromStr for GodotVersion {
-    type Err = anyhow::Error;
+    /// Parse a version string with an optional `@` qualifier naming a platform or source,
+    /// e.g. "4.2.1-dotnet@linux.x86_64" or "4.3@tuxfamily"
+    pub fn parse_qualified(s: &str) -> Result<(Self, Option<VersionQualifier>)> {
+        let s = s.trim();
+        let (version, qualifier) = match s.split_once('@') {
+            Some((version, qualifier)) => (version, Some(qualifier.parse()?)),
+            None => (s, None),
+        };
 
-    fn from_str(s: &str) -> Result<Self> {
-        // Default to non-.NET version
-        Self::new(s, false)
+        // " (.NET)" is how `Display` marks .NET builds, so its output parses back
+        let dotnet = [" (.NET)", "-dotnet", "-mono"]
+            .iter()
+            .find_map(|marker| version.strip_suffix(marker));
+        let version = match dotnet {
+            Some(version) => Self::new(version.trim_end(), true)?,
+            None => Self::new(version, strip_build_tokens(version).1)?,
+        };
+        Ok((version, qualifier))
     }
-}
 
-impl fmt::Display for GodotVersion {
-    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        if self.is_dotnet {
-            write!(f, "{} (.NET)", self.godot_version_string())
-        } else {
-            write!(f, "{}", self.godot_version_string())
+    /// Parse a semver requirement such as "^4.2" or ">=4.2, <4.4", warning about
+    /// parts of it that are valid semver but risky for Godot versions
+    pub fn parse_semver_requirement(
+        req: &str,
+    ) -> Result<(VersionReq, Vec<GodotVersionParseWarning>)> {
+        let parsed = VersionReq::parse(req.trim())
+            .map_err(|e| anyhow!("Invalid version requirement '{}': {}", req.trim(), e))?;
+
+        // A major is accepted if its first or some late release matches
+        let highest = parsed
+            .comparators
+            .iter()
+            .map(|c| c.major)
+            .max()
+            .unwrap_or(0);
+        let majors: Vec<u64> = (0..=highest + 1)
+            .filter(|&major| {
+                [Version::new(major, 0, 0), Version::new(major, 999, 999)]
+                    .iter()
+                    .any(|v| parsed.matches(v))
+            })
+            .collect();
+
+        let mut warnings = Vec::new();
+        if majors.len() > 1 {
+            warnings.push(GodotVersionParseWarning::MajorVersionNotPinned { majors });
         }
+        Ok((parsed, warnings))
     }
-}
 
-#[cfg(test)]
-mod tests {
-    use super::*;
+    /// Parse a game's own git tag that names the Godot version it targets,
+    /// e.g. "engine/4.2.1", "godot-v4.3-beta2" or "godot/4.2.1-dotnet"
+    pub fn from_git_tag(tag: &str) -> Result<Self> {
+        let tag = tag.trim();
+        let version = ["engine/", "godot-", "godot/"]
+            .iter()
+            .find_map(|prefix| tag.strip_prefix(prefix))
+            .unwrap_or(tag);
+        let version = version.strip_prefix('v').unwrap_or(version);
 
-    #[test]
-    fn test_version_parsing() {
-        // Test stable versions
-        let v1 = GodotVersion::new("4.2.1", false).unwrap();
-        assert_eq!(v1.godot_version_string(), "4.2.1");
-        assert!(!v1.is_prerelease());
+        version
+            .parse()
+            .map_err(|e| anyhow!("'{}' is not a Godot version tag: {}", tag, e))
+    }
 
-        // Test stable with suffix
-        let v2 = GodotVersion::new("4.2.1-stable", false).unwrap();
-        assert_eq!(v2.godot_version_string(), "4.2.1");
-        assert!(!v2.is_prerelease());
+    /// Read the Godot version a project targets from its `project.godot`
+    /// Godot 4 stores the "major.minor" version in `config/features`; projects
+    /// using C# also list a "C#" feature. Returns `None` if no version is listed.
+    pub fn from_project_file(path: &Path) -> Result<Option<Self>> {
+        let features = project::read_features(path)?;
+        let is_dotnet = features.iter().any(|f| f == "C#");
 
-        // Test beta versions
-        let v3 = GodotVersion::new("4.3.0-beta2", false).unwrap();
-        assert_eq!(v3.godot_version_string(), "4.3.0-beta2");
-        assert!(v3.is_prerelease());
+        Ok(features
+            .iter()
+            .filter(|f| f.starts_with(|c: char| c.is_ascii_digit()))
+            .find_map(|f| Self::new(f, is_dotnet).ok()))
+    }
 
-        // Test rc versions
-        let v4 = GodotVersion::new("4.1.0-rc.1", false).unwrap();
-        assert_eq!(v4.godot_version_string(), "4.1.0-rc1");
-        assert!(v4.is_prerelease());
+    /// Get the version as Godot lists it in a project's `config/features`: "major.minor"
+    pub fn as_project_feature_string(&self) -> String {
+        format!("{}.{}", self.version.major, self.version.minor)
+    }
 
-        // Test .NET versions
-        let v5 = GodotVersion::new("4.2.1", true).unwrap();
-        assert_eq!(v5.to_string(), "4.2.1 (.NET)");
-        assert_eq!(v5.installation_name(), "godot-4.2.1-dotnet");
+    /// Set the version a project targets in its `project.godot`, the inverse of
+    /// [`from_project_file`](Self::from_project_file). Only the version in
+    /// `config/features` changes; other features (including "C#") and the rest of
+    /// the file are kept as they are.
+    pub fn write_to_project_file(&self, path: &Path) -> Result<()> {
+        let content = std::fs::read_to_string(path)?;
+        let mut found = false;
 
-        // Test short prerelease versions like "4.5-beta1"
-        let v6 = GodotVersion::new("4.5-beta1", false).unwrap();
-        assert_eq!(v6.godot_version_string(), "4.5.0-beta1");
-        assert!(v6.is_prerelease());
-    }
+        let updated: String = content
+            .split_inclusive('\n')
+            .map(|line| {
+                let body = line.trim_end_matches(['\r', '\n']);
+                match body.trim().strip_prefix("config/features=") {
+                    Some(value) if !found => {
+                        found = true;
+                        let ending = &line[body.len()..];
+                        format!(
+                            "config/features={}{}",
+                            self.with_project_feature(value),
+                            ending
+                        )
+                    }
+                    _ => line.to_string(),
+                }
+            })
+            .collect();
 
-    #[test]
-    fn test_archive_names() {
-        let v1 = GodotVersion::new("4.2.1", false).unwrap();
-        let archive = v1.archive_name();
-        assert!(archive.contains("Godot_v4.2.1-stable_"));
-        assert!(archive.ends_with(".zip"));
+        if !found {
+            return Err(anyhow!(
+                "{} has no config/features to record the Godot version in",
+                path.display()
+            ));
+        }
 
-        let v2 = GodotVersion::new("4.3.0-beta2", true).unwrap();
-        let archive = v2.archive_name();
-        assert!(archive.contains("Godot_v4.3.0-beta2_mono_"));
-        assert!(archive.ends_with(".zip"));
+        std::fs::write(path, updated)?;
+        Ok(())
     }
 
-    #[test]
-    fn test_platform_suffix_detection() {
-        // Test that we get a valid platform suffix (this tests the current system)
-        let suffix = GodotVersion::get_platform_suffix();
-        assert!(!suffix.is_empty());
+    /// Replace the version in a `config/features` value, e.g.
+    /// `PackedStringArray("4.2", "Forward Plus")` -> `PackedStringArray("4.3", "Forward Plus")`
+    fn with_project_feature(&self, value: &str) -> String {
+        // Godot 4 wraps the list in PackedStringArray(...), Godot 3 in PoolStringArray(...)
+        let (wrapper, inner) = value
+            .split_once('(')
+            .and_then(|(wrapper, rest)| Some((wrapper, rest.rsplit_once(')')?.0)))
+            .unwrap_or(("PackedStringArray", value));
 
-        // Should be one of the expected patterns
-        let valid_suffixes = [
-            "win64.exe",
-            "win32.exe",
-            "macos.universal",
-            "linux.x86_64",
-            "linux.x86_32",
-            "linux.arm32",
-            "linux.arm64",
-        ];
-        assert!(
-            valid_suffixes.contains(&suffix),
-            "Got unexpected suffix: {}",
-            suffix
-        );
+        let version = format!("\"{}\"", self.as_project_feature_string());
+        let mut features: Vec<String> = inner
+            .split(',')
+            .map(|f| f.trim().to_string())
+            .filter(|f| !f.is_empty())
+            .collect();
+        match features.iter_mut().find(|f| {
+            f.trim_matches('"')
+                .starts_with(|c: char| c.is_ascii_digit())
+        }) {
+            Some(feature) => *feature = version,
+            None => features.insert(0, version),
+        }
+
+        format!("{}({})", wrapper, features.join(", "))
     }
 
-    #[test]
-    fn test_executable_path_construction() {
-        // Test that we can construct executable paths
-        let v1 = GodotVersion::new("4.2.1", false).unwrap();
-        let exe_path = v1.get_executable_path();
-        assert!(!exe_path.is_empty());
+    /// The editor VS Code's Godot Tools extension is set up to use, from
+    /// `godotTools.editorPath` (or its `.godot3`/`.godot4` variants) in the workspace's
+    /// `.vscode/settings.json`. Returns `None` if no editor path is set. The path comes
+    /// from the repository, so it isn't run here; see [`try_from_path`](Self::try_from_path).
+    pub fn vscode_editor_path(workspace: &Path) -> Result<Option<PathBuf>> {
+        static EDITOR_PATH: OnceLock<regex::Regex> = OnceLock::new();
 
+        let settings = workspace.join(".vscode").join("settings.json");
+        let Ok(content) = std::fs::read_to_string(&settings) else {
+            return Ok(None);
+        };
+
+        // settings.json may contain comments and trailing commas, so it isn't parsed as JSON
+        let regex = EDITOR_PATH.get_or_init(|| {
+            regex::Regex::new(
+                r#""godotTools\.editorPath(?:\.godot[34])?"\s*:\s*("(?:[^"\\]|\\.)*")"#,
+            )
+            .unwrap()
+        });
+        let Some(captures) = regex.captures(&content) else {
+            return Ok(None);
+        };
+        let path: String = serde_json::from_str(&captures[1])
+            .map_err(|e| anyhow!("Invalid editor path in {}: {}", settings.display(), e))?;
+
+        let path = path.replace("${workspaceFolder}", &workspace.to_string_lossy());
+        Ok(Some(workspace.join(path)))
+    }
+
+    /// The editor the JetBrains GodotSupport plugin is set up to use, from the path
+    /// option in the workspace's `.idea/godot.xml`. Returns `None` if no editor path is
+    /// set. Like [`vscode_editor_path`](Self::vscode_editor_path), it isn't run here.
+    pub fn jetbrains_editor_path(workspace: &Path) -> Result<Option<PathBuf>> {
+        static EDITOR_PATH: OnceLock<regex::Regex> = OnceLock::new();
+
+        let godot_xml = workspace.join(".idea").join("godot.xml");
+        let Ok(content) = std::fs::read_to_string(&godot_xml) else {
+            return Ok(None);
+        };
+
+        let regex = EDITOR_PATH.get_or_init(|| {
+            regex::Regex::new(r#"<option\s+name="[^"]*[Pp]ath"\s+value="([^"]*)""#).unwrap()
+        });
+        let Some(captures) = regex.captures(&content) else {
+            return Ok(None);
+        };
+
+        // JetBrains writes paths with macros for the project and home directories
+        let home = dirs::home_dir().unwrap_or_default();
+        let path = captures[1]
+            .replace("$PROJECT_DIR$", &workspace.to_string_lossy())
+            .replace("$USER_HOME$", &home.to_string_lossy())
+            .replace("&quot;", "\"")
+            .replace("&apos;", "'")
+            .replace("&lt;", "<")
+            .replace("&gt;", ">")
+            .replace("&amp;", "&");
+        Ok(Some(workspace.join(path)))
+    }
+
+    /// Detect the version of a Godot executable by running it with `--version`
+    /// Falls back to `--help`, whose banner includes the version, for builds without `--version`.
+    pub fn try_from_path(exe: &Path) -> Result<Self> {
+        for flag in ["--version", "--help"] {
+            let Ok(output) = std::process::Command::new(exe).arg(flag).output() else {
+                continue;
+            };
+
+            let text = format!(
+                "{}{}",
+                String::from_utf8_lossy(&output.stdout),
+                String::from_utf8_lossy(&output.stderr)
+            );
+            if let Ok(version) = Self::parse_version_output(&text) {
+                return Ok(version);
+            }
+        }
+
+        Err(anyhow::anyhow!(
+            "Could not detect the Godot version of {}",
+            exe.display()
+        ))
+    }
+
+    /// Parse the version Godot reports about itself, see [`VERSION_OUTPUT_REGEX`]
+    pub fn parse_version_output(output: &str) -> Result<Self> {
+        let regex = regex::Regex::new(VERSION_OUTPUT_REGEX)?;
+        let captures = regex
+            .captures(output)
+            .ok_or_else(|| anyhow::anyhow!("No Godot version found in '{}'", output.trim()))?;
+
+        let major = &captures[1];
+        let minor = &captures[2];
+        let patch = captures.get(3).map_or("0", |m| m.as_str());
+        let status = &captures[4];
+        let is_dotnet = captures[5].split('.').any(|token| token == "mono");
+
+        let version_str = if status == "stable" {
+            format!("{}.{}.{}", major, minor, patch)
+        } else {
+            format!("{}.{}.{}-{}", major, minor, patch, status)
+        };
+
+        Self::new(&version_str, is_dotnet)
+    }
+
+    /// Get the platform suffix of this version's builds for the current platform
+    pub fn platform_suffix(&self) -> Result<&'static str> {
+        self.platform_suffix_for(Self::get_platform_suffix()?)
+    }
+
+    /// Translate a Godot 4 platform suffix (as [`get_platform_suffix`](Self::get_platform_suffix)
+    /// returns) into the one this version's builds use. Godot 3 named them differently:
+    /// - "linux.x86_64" -> "x11.64", "linux.x86_32" -> "x11.32"
+    /// - "macos.universal" -> "osx.universal" (or "osx.64" before 3.3, which had no
+    ///   universal builds)
+    ///
+    /// Windows on ARM builds only exist from Godot 4.3, so asking for them for an older
+    /// version is an error rather than a download that 404s.
+    pub fn platform_suffix_for(&self, suffix: &'static str) -> Result<&'static str> {
+        if suffix == "windows_arm64.exe" && !self.platform_suffixes().contains(&suffix) {
+            return Err(anyhow!(
+                "Godot v{} has no Windows on ARM builds; they were first published for 4.3",
+                self.godot_version_string()
+            ));
+        }
+
+        if self.version.major >= 4 {
+            return Ok(suffix);
+        }
+
+        match suffix {
+            "linux.x86_64" => Ok("x11.64"),
+            "linux.x86_32" => Ok("x11.32"),
+            "macos.universal" if self.version < Version::new(3, 3, 0) => Ok("osx.64"),
+            "macos.universal" => Ok("osx.universal"),
+            "win64.exe" | "win32.exe" => Ok(suffix),
+            _ => Err(anyhow!(
+                "Godot v{} has no official builds for {}",
+                self.godot_version_string(),
+                suffix
+            )),
+        }
+    }
+
+    /// Get the expected executable path within the extracted directory
+    pub fn get_executable_path(&self) -> Result<String> {
+        self.get_executable_path_for_target(Self::target_os(), std::env::consts::ARCH)
+    }
+
+    /// Get the executable path within an installation of the build for an OS and
+    /// architecture (as in `std::env::consts`), which needn't be this platform's
+    pub fn get_executable_path_for_target(&self, os: &str, arch: &str) -> Result<String> {
+        let suffix = self.platform_suffix_for(Self::get_platform_suffix_for_target(os, arch)?)?;
+        Ok(self.get_executable_path_for(suffix))
+    }
+
+    /// Get the path of the executable within an installation of the build for
+    /// `platform_suffix`, which needn't be this platform's
+    pub fn get_executable_path_for(&self, platform_suffix: &str) -> String {
+        let version_part = self.to_tag();
+
+        if platform_suffix.starts_with("macos.") || platform_suffix.starts_with("osx.") {
+            format!("{}/Contents/MacOS/Godot", self.macos_app_bundle_name())
+        } else if let Some(platform) = platform_suffix.strip_suffix(".exe") {
+            if self.is_dotnet {
+                format!(
+                    "Godot_v{}_mono_{}/Godot_v{}_mono_{}.exe",
+                    version_part, platform, version_part, platform
+                )
+            } else {
+                format!("Godot_v{}_{}.exe", version_part, platform)
+            }
+        } else if self.is_dotnet {
+            // Dotnet versions extract to a subfolder named like the archive
+            let folder_name = format!(
+                "Godot_v{}_mono_{}",
+                version_part,
+                Self::dotnet_platform_name(platform_suffix)
+            );
+            let exe_name = format!("Godot_v{}_mono_{}", version_part, platform_suffix);
+            format!("{}/{}", folder_name, exe_name)
+        } else {
+            // Non-dotnet versions extract directly
+            format!("Godot_v{}_{}", version_part, platform_suffix)
+        }
+    }
+
+    /// Get the path of the console executable within the extracted directory. Godot 4's
+    /// Windows builds ship a `_console.exe` next to the editor that keeps stdout attached,
+    /// for CI and headless use; other platforms and Godot 3 have only the one executable.
+    pub fn get_console_executable_path(&self) -> Result<String> {
+        Ok(self.get_console_executable_path_for(self.platform_suffix()?))
+    }
+
+    /// Get the path of the console executable within an installation of the build for
+    /// `platform_suffix`, see [`get_console_executable_path`](Self::get_console_executable_path)
+    pub fn get_console_executable_path_for(&self, platform_suffix: &str) -> String {
+        let path = self.get_executable_path_for(platform_suffix);
+        match path.strip_suffix(".exe") {
+            Some(gui) if self.version.major >= 4 => format!("{}_console.exe", gui),
+            _ => path,
+        }
+    }
+
+    /// Get the name of the macOS app bundle in the release archive
+    pub fn macos_app_bundle_name(&self) -> String {
+        if self.is_dotnet {
+            "Godot_mono.app".to_string()
+        } else {
+            "Godot.app".to_string()
+        }
+    }
+
+    /// Find the editor executable in an extracted macOS archive
+    /// Most archives have the app bundle at the top level, but some wrap it in a
+    /// version-stamped folder (e.g. "Godot_v4.2.1-stable_mono_macos.universal/Godot_mono.app").
+    #[cfg_attr(not(target_os = "macos"), allow(dead_code))]
+    pub fn find_macos_executable(&self, install_path: &Path) -> Option<PathBuf> {
+        let bundle_exe = format!("{}/Contents/MacOS/Godot", self.macos_app_bundle_name());
+        let platform = if self.version.major < 4 {
+            "osx.universal"
+        } else {
+            "macos.universal"
+        };
+        let mono = if self.is_dotnet { "_mono" } else { "" };
+        let stamped_dir = format!("Godot_v{}{}_{}", self.to_tag(), mono, platform);
+
+        [
+            install_path.join(&bundle_exe),
+            install_path.join(stamped_dir).join(&bundle_exe),
+        ]
+        .into_iter()
+        .find(|path| path.is_file())
+    }
+
+    /// Get where `gdenv run --memory-profiler` writes the profile by default: a file in
+    /// the current directory named after the installation and gdenv's process ID, e.g.
+    /// "godot-4.2.1.12345.massif.out", so profiles of separate runs don't overwrite each other
+    pub fn memory_profile_output_path(&self, profiler: MemProfiler) -> PathBuf {
+        PathBuf::from(format!(
+            "{}.{}.{}",
+            self.installation_name(),
+            std::process::id(),
+            profiler.output_extension()
+        ))
+    }
+
+    pub fn installation_name(&self) -> String {
+        if self.is_dotnet {
+            format!("godot-{}-dotnet", self.godot_version_string())
+        } else {
+            format!("godot-{}", self.godot_version_string())
+        }
+    }
+
+    #[allow(dead_code)]
+    pub fn archive_name(&self) -> Result<String> {
+        self.archive_name_for_target(Self::target_os(), std::env::consts::ARCH)
+    }
+
+    /// Parse the version out of an archive filename, the inverse of
+    /// [`archive_name_for_platform`](Self::archive_name_for_platform): e.g.
+    /// "Godot_v4.3-beta2_mono_linux_x86_64.zip" is 4.3.0-beta2 (.NET). Names that aren't
+    /// an official archive for a known platform are an error.
+    pub fn from_archive_name(name: &str) -> Result<Self> {
+        let invalid = || anyhow!("'{}' is not the name of a Godot archive", name);
+        let rest = name
+            .strip_prefix("Godot_v")
+            .and_then(|rest| rest.strip_suffix(".zip"))
+            .ok_or_else(invalid)?;
+
+        // Release tags contain no underscores, so the version ends at the first one
+        let (tag, platform) = rest.split_once('_').ok_or_else(invalid)?;
+        let is_dotnet = platform.starts_with("mono_");
+        let version = Self::new(tag, is_dotnet).map_err(|_| invalid())?;
+
+        Self::all_platform_suffixes()
+            .iter()
+            .chain(Self::all_legacy_platform_suffixes())
+            .chain(&["osx.64"])
+            .any(|suffix| version.archive_name_for_platform(suffix) == name)
+            .then_some(version)
+            .ok_or_else(invalid)
+    }
+
+    /// Get the GitHub archive filename of the build for an OS and architecture
+    /// (as in `std::env::consts`), which needn't be this platform's
+    pub fn archive_name_for_target(&self, os: &str, arch: &str) -> Result<String> {
+        let suffix = self.platform_suffix_for(Self::get_platform_suffix_for_target(os, arch)?)?;
+        Ok(self.archive_name_for_platform(suffix))
+    }
+
+    /// Get the archive filename of this version for a platform suffix
+    /// Every download source uses the filenames of the official builds; the only irregularity is
+    /// that .NET archives spell the platform differently from the standard ones:
+    /// - "linux.x86_64" -> "Godot_v4.2.1-stable_mono_linux_x86_64.zip"
+    /// - "x11.64" -> "Godot_v3.5.3-stable_mono_x11_64.zip"
+    /// - "win64.exe" -> "Godot_v4.2.1-stable_mono_win64.zip"
+    pub fn archive_name_for_platform(&self, platform_suffix: &str) -> String {
+        let version_part = self.to_tag();
+
+        if self.is_dotnet {
+            format!(
+                "Godot_v{}_mono_{}.zip",
+                version_part,
+                Self::dotnet_platform_name(platform_suffix)
+            )
+        } else {
+            format!("Godot_v{}_{}.zip", version_part, platform_suffix)
+        }
+    }
+
+    /// Get how .NET archives and the folders they extract to spell a platform suffix
+    fn dotnet_platform_name(platform_suffix: &str) -> String {
+        match platform_suffix.strip_suffix(".exe") {
+            Some(windows) => windows.to_string(),
+            None if platform_suffix.starts_with("linux.")
+                || platform_suffix.starts_with("x11.") =>
+            {
+                platform_suffix.replace('.', "_")
+            }
+            None => platform_suffix.to_string(),
+        }
+    }
+
+    /// Get the GitHub download URL of the archive for the current platform
+    pub fn download_url(&self) -> Result<String> {
+        self.download_url_for_source(Source::GitHub)
+    }
+
+    /// Get the download URL of the archive for the current platform on a download source
+    pub fn download_url_for_source(&self, source: Source) -> Result<String> {
+        Ok(self.download_url_for_platform(source, self.platform_suffix()?))
+    }
+
+    /// Get the download URL of the archive for a platform suffix on a download source
+    /// TuxFamily keeps each release in "<version>/[<prerelease>/][mono/]".
+    pub fn download_url_for_platform(&self, source: Source, platform_suffix: &str) -> String {
+        let archive_name = self.archive_name_for_platform(platform_suffix);
+
+        match source {
+            Source::GitHub => format!(
+                "https://github.com/godotengine/godot-builds/releases/download/{}/{}",
+                self.to_tag(),
+                archive_name
+            ),
+            Source::TuxFamily => {
+                let tag = self.to_tag();
+                let (version_dir, channel) = tag.split_once('-').unwrap_or((&tag, "stable"));
+
+                let mut url = format!(
+                    "https://downloads.tuxfamily.org/godotengine/{}/",
+                    version_dir
+                );
+                if channel != "stable" {
+                    url.push_str(channel);
+                    url.push('/');
+                }
+                if self.is_dotnet {
+                    url.push_str("mono/");
+                }
+                url.push_str(&archive_name);
+                url
+            }
+        }
+    }
+
+    /// Get the range of sizes in bytes the editor executable of this version should have
+    /// on the current platform, or `None` if it isn't known
+    pub fn expected_executable_size_range_bytes(&self) -> Option<(u64, u64)> {
+        self.expected_executable_size_range_for(self.platform_suffix().ok()?)
+    }
+
+    /// Get the expected executable size range for a platform suffix, see
+    /// [`expected_executable_size_range_bytes`](Self::expected_executable_size_range_bytes)
+    pub fn expected_executable_size_range_for(&self, platform_suffix: &str) -> Option<(u64, u64)> {
+        EXECUTABLE_SIZE_RANGES
+            .iter()
+            .find(|((platform, major), _)| {
+                *platform == platform_suffix && *major == self.version.major
+            })
+            .map(|(_, range)| *range)
+    }
+
+    /// Estimate the on-disk size of an installation extracted from an archive
+    /// .NET builds ship the GodotSharp assemblies, which compress better than the editor binary
+    pub fn estimated_install_size(&self, archive_bytes: u64) -> u64 {
+        if self.is_dotnet {
+            archive_bytes * 5 / 2
+        } else {
+            archive_bytes * 3 / 2
+        }
+    }
+
+    /// Get a stable identifier for this exact installation on the current platform
+    /// See [`build_id_for`](Self::build_id_for) for how it's computed.
+    pub fn build_id(&self) -> Result<String> {
+        Ok(self.build_id_for(Self::get_platform_suffix()?))
+    }
+
+    /// Compute the build ID of this version for a platform suffix
+    /// The ID is the lowercase hex SHA-256 of the UTF-8 string
+    /// "{version}\n{platform_suffix}\n{is_dotnet}", e.g. "4.2.1\nlinux.x86_64\nfalse"
+    /// or "4.3.0-beta2\nwin64.exe\ntrue", with no trailing newline.
+    pub fn build_id_for(&self, platform_suffix: &str) -> String {
+        let input = format!(
+            "{}\n{}\n{}",
+            self.godot_version_string(),
+            platform_suffix,
+            self.is_dotnet
+        );
+        format!("{:x}", Sha256::digest(input.as_bytes()))
+    }
+
+    /// Get the version of the `Godot.NET.Sdk` NuGet package that matches this engine
+    /// version, or `None` before Godot 4. NuGet separates prerelease numbers with a dot.
+    /// Examples:
+    /// - "4.2.1" -> "4.2.1"
+    /// - "4.3-beta2" -> "4.3.0-beta.2"
+    /// - "4.4-dev3" -> "4.4.0-dev.3"
+    pub fn nuget_package_version(&self) -> Option<String> {
+        if self.version.major < 4 {
+            return None;
+        }
+
+        let core = format!(
+            "{}.{}.{}",
+            self.version.major, self.version.minor, self.version.patch
+        );
+        if self.version.pre.is_empty() {
+            return Some(core);
+        }
+
+        let pre = self.version.pre.as_str();
+        let pre = match pre.find(|c: char| c.is_ascii_digit()) {
+            Some(i) if !pre[..i].ends_with('.') => format!("{}.{}", &pre[..i], &pre[i..]),
+            _ => pre.to_string(),
+        };
+        Some(format!("{}-{}", core, pre))
+    }
+
+    /// Get the filename suffix Godot gives an exported game binary for a platform
+    /// Examples (for a Godot 4 export of "MyGame"):
+    /// - "linux.x86_64" -> "MyGame.x86_64"
+    /// - "win64.exe" -> "MyGame.exe"
+    /// - "macos.universal" -> "MyGame.app"
+    pub fn export_binary_suffix(&self, platform: &str) -> &'static str {
+        if platform.starts_with("win") || platform.ends_with(".exe") {
+            return ".exe";
+        }
+
+        if platform.starts_with("macos") || platform.starts_with("osx") {
+            return ".app";
+        }
+
+        match platform.rsplit('.').next() {
+            Some("x86_64") | Some("64") => ".x86_64",
+            // Godot 3 named 32-bit Linux exports ".x86", Godot 4 uses ".x86_32"
+            Some("x86_32") | Some("32") if self.version.major < 4 => ".x86",
+            Some("x86_32") | Some("32") => ".x86_32",
+            Some("arm64") => ".arm64",
+            Some("arm32") => ".arm32",
+            _ => ".x86_64",
+        }
+    }
+
+    /// Get the rendering method Godot uses by default, or `None` before Godot 4,
+    /// which has no choice of rendering method
+    pub fn preferred_rendering_method(&self) -> Option<RenderingMethod> {
+        (self.version.major >= 4).then_some(RenderingMethod::ForwardPlus)
+    }
+
+    /// Get the oldest macOS version (major, minor) this version's macOS builds run on,
+    /// or `None` for versions older than any known requirement
+    pub fn minimum_macos_version(&self) -> Option<MacosVersion> {
+        let series = (self.version.major, self.version.minor);
+        MINIMUM_MACOS_VERSIONS
+            .iter()
+            .rev()
+            .find(|(since, _)| *since <= series)
+            .map(|(_, minimum)| *minimum)
+    }
+
+    /// Get the system calls worth tracing to see which files Godot looks for: strace's
+    /// `%file` class (opens, stats, access checks and symlink lookups, under whichever
+    /// names this architecture has), plus the memory maps .NET builds load assemblies with
+    pub fn strace_suggested_filter(&self) -> &'static str {
+        if self.is_dotnet {
+            "%file,mmap"
+        } else {
+            "%file"
+        }
+    }
+
+    /// Get the command-line arguments that scale the editor and project UI by `factor`,
+    /// e.g. 2.0 to test HiDPI rendering on a regular screen. Godot 3 has no such option
+    /// (only the editor's display scale setting), so it gets none.
+    pub fn display_scale_arg(&self, factor: f32) -> Vec<String> {
+        if self.version.major >= 4 {
+            vec!["--display-scale".to_string(), factor.to_string()]
+        } else {
+            Vec::new()
+        }
+    }
+
+    /// Get the engine Godot simulates 3D physics with unless a project picks another
+    pub fn default_physics_engine(&self) -> PhysicsBackend {
+        if self.version.major >= 4 {
+            PhysicsBackend::GodotPhysics
+        } else {
+            PhysicsBackend::Bullet
+        }
+    }
+
+    /// Whether Jolt physics can be used, through the godot-jolt addon (4.2 and 4.3)
+    /// or built in (4.4 on)
+    pub fn supports_jolt(&self) -> bool {
+        self.version >= Version::new(4, 2, 0)
+    }
+
+    /// Get the value of the `physics/3d/physics_engine` project setting that selects
+    /// `backend`, or `None` if this version can't use it
+    pub fn physics_engine_setting(&self, backend: PhysicsBackend) -> Option<&'static str> {
+        match (self.version.major, backend) {
+            (3, PhysicsBackend::GodotPhysics) => Some("GodotPhysics"),
+            (3, PhysicsBackend::Bullet) => Some("Bullet"),
+            (4.., PhysicsBackend::GodotPhysics) => Some("GodotPhysics3D"),
+            (4.., PhysicsBackend::Jolt) if self.version >= Version::new(4, 4, 0) => {
+                Some("Jolt Physics")
+            }
+            (4.., PhysicsBackend::Jolt) if self.supports_jolt() => Some("JoltPhysics3D"),
+            _ => None,
+        }
+    }
+
+    /// Get the URL of the hosted web editor for this version, which only exists for
+    /// Godot 4 and can't run C#. The site names releases like "4.2.1.stable".
+    pub fn web_editor_url(&self) -> Option<String> {
+        (self.version.major >= 4 && !self.is_dotnet).then(|| {
+            format!(
+                "https://editor.godotengine.org/releases/{}/",
+                self.to_tag().replace('-', ".")
+            )
+        })
+    }
+
+    /// Get the name Godot looks for this version's export templates under
+    /// (e.g. "4.2.1.stable" or "4.2.1.stable.mono")
+    pub fn export_templates_name(&self) -> String {
+        let name = self.to_tag().replace('-', ".");
+        if self.is_dotnet {
+            format!("{}.mono", name)
+        } else {
+            name
+        }
+    }
+
+    /// Get the URL of the source code tarball GitHub generates for this version's tag
+    /// in the engine repository, e.g.
+    /// "https://github.com/godotengine/godot/archive/refs/tags/4.2.1-stable.tar.gz"
+    pub fn source_code_url(&self) -> String {
+        format!(
+            "https://github.com/godotengine/godot/archive/refs/tags/{}.tar.gz",
+            self.to_tag()
+        )
+    }
+
+    /// Get the file name GitHub gives the source code tarball, e.g. "godot-4.2.1-stable.tar.gz"
+    pub fn source_code_file_name(&self) -> String {
+        format!("godot-{}.tar.gz", self.to_tag())
+    }
+
+    /// Get the download URL of this version's export templates archive
+    pub fn export_templates_url(&self) -> String {
+        let flavor = if self.is_dotnet { "_mono" } else { "" };
+        format!(
+            "https://github.com/godotengine/godot-builds/releases/download/{tag}/Godot_v{tag}{flavor}_export_templates.tpz",
+            tag = self.to_tag(),
+            flavor = flavor
+        )
+    }
+
+    /// Get the name Godot markets this release under, e.g. "Godot 4.2"
+    pub fn to_marketing_name(&self) -> String {
+        self.marketing_name_from(MARKETING_NAMES)
+    }
+
+    fn marketing_name_from(&self, names: &[((u64, u64), &str)]) -> String {
+        let key = (self.version.major, self.version.minor);
+        match names.iter().find(|(release, _)| *release == key) {
+            Some((_, name)) => name.to_string(),
+            None => format!("Godot {}.{}", key.0, key.1),
+        }
+    }
+
+    pub fn is_prerelease(&self) -> bool {
+        !self.version.pre.is_empty()
+    }
+
+    /// Whether `req` accepts this version. As in semver, a prerelease only matches a
+    /// requirement naming a prerelease of the same major.minor.patch, so "^4" doesn't
+    /// match 4.3-beta2; see [`resolve_latest`](Self::resolve_latest) to include them.
+    pub fn matches(&self, req: &VersionReq) -> bool {
+        req.matches(&self.version)
+    }
+
+    /// The newest of `candidates` that `req` accepts. With `include_prerelease`, a
+    /// prerelease also counts if `req` accepts the release it leads up to, so "^4"
+    /// then picks 4.3-beta2 over 4.2.2.
+    pub fn resolve_latest(
+        candidates: &[GodotVersion],
+        req: &VersionReq,
+        include_prerelease: bool,
+    ) -> Option<GodotVersion> {
+        candidates
+            .iter()
+            .filter(|v| {
+                v.matches(req)
+                    || (include_prerelease
+                        && v.is_prerelease()
+                        && req.matches(&Version::new(
+                            v.version.major,
+                            v.version.minor,
+                            v.version.patch,
+                        )))
+            })
+            .max()
+            .cloned()
+    }
+
+    /// The release stage of this version. Unrecognized prerelease tags count as stable.
+    pub fn channel(&self) -> GodotChannel {
+        let pre = self.version.pre.as_str();
+        [
+            ("dev", GodotChannel::Dev),
+            ("alpha", GodotChannel::Alpha),
+            ("beta", GodotChannel::Beta),
+            ("rc", GodotChannel::Rc),
+        ]
+        .into_iter()
+        .find(|(kind, _)| pre.starts_with(kind))
+        .map_or(GodotChannel::Stable, |(_, channel)| channel)
+    }
+
+    /// Whether this editor can open a project made with `project_version` without
+    /// upgrading it. Godot only records "major.minor" in `project.godot`, so any build
+    /// of the same minor release, prereleases included, can open a stable project
+    /// version. A prerelease project version needs an editor at least that recent.
+    pub fn can_open_project(&self, project_version: &GodotVersion) -> bool {
+        let (editor, project) = (&self.version, &project_version.version);
+
+        if editor.major != project.major || editor.minor < project.minor {
+            return false;
+        }
+
+        if editor.minor > project.minor || !project_version.is_prerelease() {
+            return true;
+        }
+
+        !self.is_prerelease() || editor.pre >= project.pre
+    }
+}
+
+impl FromStr for GodotVersion {
+    type Err = anyhow::Error;
+
+    /// Parse a canonical version string, where a `-dotnet` or `-mono` suffix, or the
+    /// " (.NET)" that `Display` writes, marks .NET builds.
+    /// An `@` qualifier (e.g. "@linux.x86_64") is accepted and dropped; use
+    /// [`GodotVersion::parse_qualified`] to read it.
+    fn from_str(s: &str) -> Result<Self> {
+        Self::parse_qualified(s).map(|(version, _)| version)
+    }
+}
+
+impl Ord for GodotVersion {
+    /// Order by release: the numeric version, then the channel (dev < alpha < beta <
+    /// rc < stable, which semver's lexical prerelease order gets wrong for dev), then the
+    /// prerelease number. Remaining ties fall back to semver, then standard before .NET.
+    fn cmp(&self, other: &Self) -> Ordering {
+        let release = |v: &GodotVersion| {
+            let number = v
+                .version
+                .pre
+                .split('.')
+                .nth(1)
+                .and_then(|n| n.parse::<u64>().ok());
+            (
+                v.version.major,
+                v.version.minor,
+                v.version.patch,
+                v.channel(),
+                number,
+            )
+        };
+
+        release(self)
+            .cmp(&release(other))
+            .then_with(|| self.version.cmp(&other.version))
+            .then_with(|| self.is_dotnet.cmp(&other.is_dotnet))
+    }
+}
+
+impl PartialOrd for GodotVersion {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Serialize for GodotVersion {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(&self.canonical_string())
+    }
+}
+
+impl<'de> Deserialize<'de> for GodotVersion {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let s = String::deserialize(deserializer)?;
+        s.parse().map_err(serde::de::Error::custom)
+    }
+}
+
+impl From<GodotVersion> for DetailedGodotVersion {
+    fn from(v: GodotVersion) -> Self {
+        Self {
+            version: v.version,
+            is_dotnet: v.is_dotnet,
+        }
+    }
+}
+
+impl From<DetailedGodotVersion> for GodotVersion {
+    fn from(v: DetailedGodotVersion) -> Self {
+        Self {
+            version: v.version,
+            is_dotnet: v.is_dotnet,
+        }
+    }
+}
+
+impl fmt::Display for GodotVersion {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.is_dotnet {
+            write!(f, "{} (.NET)", self.godot_version_string())
+        } else {
+            write!(f, "{}", self.godot_version_string())
+        }
+    }
+}
+
+/// Split Godot's own dot-separated version format (e.g. "4.2.1.stable.mono.official.b09f793f5"
+/// or "4.3.beta2.official") into a plain version ("4.2.1", "4.3-beta2") and whether it
+/// names a .NET ("mono") build. Other strings are returned unchanged.
+fn strip_build_tokens(version_str: &str) -> (String, bool) {
+    let tokens: Vec<&str> = version_str.split('.').collect();
+    let numeric = tokens
+        .iter()
+        .take(3)
+        .take_while(|token| token.starts_with(|c: char| c.is_ascii_digit()))
+        .count();
+    if numeric < 2 || numeric == tokens.len() {
+        return (version_str.to_string(), false);
+    }
+
+    let (core, rest) = tokens.split_at(numeric);
+    let status = rest[0];
+    let is_prerelease = ["dev", "alpha", "beta", "rc"].iter().any(|kind| {
+        status
+            .strip_prefix(kind)
+            .is_some_and(|number| number.chars().all(|c| c.is_ascii_digit()))
+    });
+
+    let core = core.join(".");
+    let version = if status == "stable" {
+        core
+    } else if is_prerelease {
+        format!("{}-{}", core, status)
+    } else {
+        return (version_str.to_string(), false);
+    };
+    (version, rest.contains(&"mono"))
+}
+
+/// WSL kernels identify themselves in `/proc/version`
+/// (e.g. "Linux version 5.15.153.1-microsoft-standard-WSL2")
+fn is_wsl_kernel(proc_version: &str) -> bool {
+    let proc_version = proc_version.to_lowercase();
+    proc_version.contains("microsoft") || proc_version.contains("wsl")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_godot_version_req() {
+        let v = |version: &str| GodotVersion::new(version, false).unwrap();
+        let candidates = [v("4.1.4"), v("4.2.1"), v("4.2.2"), v("4.3-beta2"), v("4.3")];
+
+        let req: GodotVersionReq = ">=4.2, <4.3".parse().unwrap();
+        assert_eq!(req.resolve(&candidates), Some(v("4.2.2")));
+        let req: GodotVersionReq = ">=4.2, <4.4".parse().unwrap();
+        assert_eq!(req.resolve(&candidates), Some(v("4.3")));
+        assert_eq!(req.resolve(&candidates[..4]), Some(v("4.2.2")));
+
+        // Only ranges naming a prerelease of that version accept its prereleases
+        let req: GodotVersionReq = ">=4.3.0-beta.1, <4.4".parse().unwrap();
+        assert_eq!(req.resolve(&candidates[..4]), Some(v("4.3-beta2")));
+        let req: GodotVersionReq = "^5".parse().unwrap();
+        assert_eq!(req.resolve(&candidates), None);
+
+        assert!(GodotVersionReq::is_range(">=4.2, <4.3"));
+        assert!(GodotVersionReq::is_range("~4.2"));
+        assert!(GodotVersionReq::is_range("4.2.*"));
+        assert!(!GodotVersionReq::is_range("4.2.1"));
+        assert!(!GodotVersionReq::is_range("4.3-beta2"));
+        assert!(!GodotVersionReq::is_range("4.2.1-dotnet@linux.x86_64"));
+        assert!("not a range".parse::<GodotVersionReq>().is_err());
+    }
+
+    #[test]
+    fn test_semantically_equivalent() {
+        let v = |version: &str, is_dotnet: bool| GodotVersion::new(version, is_dotnet).unwrap();
+
+        assert_eq!(v("4.2.1", false), v("4.2.1-stable", false));
+        assert_eq!(v("4.2", false), v("4.2.0", false));
+        assert!(v("4.2", false).semantically_equivalent(&v("4.2.0-stable", false)));
+
+        let with_build = v("4.2.1+b09f793f5", false);
+        assert_ne!(with_build, v("4.2.1", false));
+        assert!(with_build.semantically_equivalent(&v("4.2.1", false)));
+
+        assert!(!v("4.2.1", false).semantically_equivalent(&v("4.2.1", true)));
+        assert!(!v("4.2", false).semantically_equivalent(&v("4.2.1", false)));
+        assert!(!v("4.3", false).semantically_equivalent(&v("4.3-rc1", false)));
+    }
+
+    #[test]
+    fn test_version_parsing() {
+        // Test stable versions
+        let v1 = GodotVersion::new("4.2.1", false).unwrap();
+        assert_eq!(v1.godot_version_string(), "4.2.1");
+        assert!(!v1.is_prerelease());
+
+        // Test stable with suffix
+        let v2 = GodotVersion::new("4.2.1-stable", false).unwrap();
+        assert_eq!(v2.godot_version_string(), "4.2.1");
+        assert!(!v2.is_prerelease());
+
+        // Test beta versions
+        let v3 = GodotVersion::new("4.3.0-beta2", false).unwrap();
+        assert_eq!(v3.godot_version_string(), "4.3.0-beta2");
+        assert!(v3.is_prerelease());
+
+        // Test rc versions
+        let v4 = GodotVersion::new("4.1.0-rc.1", false).unwrap();
+        assert_eq!(v4.godot_version_string(), "4.1.0-rc1");
+        assert!(v4.is_prerelease());
+
+        // Test .NET versions
+        let v5 = GodotVersion::new("4.2.1", true).unwrap();
+        assert_eq!(v5.to_string(), "4.2.1 (.NET)");
+        assert_eq!(v5.installation_name(), "godot-4.2.1-dotnet");
+
+        // Test short prerelease versions like "4.5-beta1"
+        let v6 = GodotVersion::new("4.5-beta1", false).unwrap();
+        assert_eq!(v6.godot_version_string(), "4.5.0-beta1");
+        assert!(v6.is_prerelease());
+
+        // Test dev snapshots, which number like the other prereleases
+        let v7 = GodotVersion::new("4.4-dev5", false).unwrap();
+        assert_eq!(v7.version.pre.as_str(), "dev.5");
+        assert_eq!(v7, GodotVersion::new("4.4.0-dev5", false).unwrap());
+        assert_eq!(v7.godot_version_string(), "4.4.0-dev5");
+        assert_eq!(v7.to_tag(), "4.4-dev5");
+        assert!(v7 < GodotVersion::new("4.4-dev10", false).unwrap());
+    }
+
+    #[test]
+    fn test_write_to_project_file() {
+        let dir = tempfile::tempdir().unwrap();
+        let project_file = dir.path().join("project.godot");
+        std::fs::write(
+            &project_file,
+            "config_version=5\r\n\r\n[application]\r\n\r\nconfig/name=\"Demo\"\r\n\
+             config/features=PackedStringArray(\"4.2\", \"C#\", \"Forward Plus\")\r\n",
+        )
+        .unwrap();
+
+        let version = GodotVersion::new("4.3.1", true).unwrap();
+        assert_eq!(version.as_project_feature_string(), "4.3");
+        version.write_to_project_file(&project_file).unwrap();
+
+        assert_eq!(
+            std::fs::read_to_string(&project_file).unwrap(),
+            "config_version=5\r\n\r\n[application]\r\n\r\nconfig/name=\"Demo\"\r\n\
+             config/features=PackedStringArray(\"4.3\", \"C#\", \"Forward Plus\")\r\n"
+        );
+        assert_eq!(
+            GodotVersion::from_project_file(&project_file).unwrap(),
+            Some(GodotVersion::new("4.3", true).unwrap())
+        );
+
+        // A features list without a version gets one
+        std::fs::write(
+            &project_file,
+            "[application]\nconfig/features=PackedStringArray(\"Mobile\")\n",
+        )
+        .unwrap();
+        version.write_to_project_file(&project_file).unwrap();
+        assert_eq!(
+            project::read_features(&project_file).unwrap(),
+            ["4.3", "Mobile"]
+        );
+
+        std::fs::write(&project_file, "[application]\nconfig/name=\"Old\"\n").unwrap();
+        assert!(version.write_to_project_file(&project_file).is_err());
+    }
+
+    #[test]
+    fn test_version_parsing_real_world_input() {
+        // Pasted from `godot --version` or a changelog
+        assert_eq!(
+            GodotVersion::new("4.2.1.stable.official.abc", false).unwrap(),
+            GodotVersion::new("4.2.1", false).unwrap()
+        );
+        assert_eq!(
+            GodotVersion::new("  4.3.0-beta2 ", false)
+                .unwrap()
+                .godot_version_string(),
+            "4.3.0-beta2"
+        );
+        assert_eq!(
+            GodotVersion::new("4.3.beta2.official.6c814135b", false)
+                .unwrap()
+                .godot_version_string(),
+            "4.3.0-beta2"
+        );
+
+        // The dotnet-aware parser picks up "mono" builds
+        let mono: GodotVersion = "4.2.1.stable.mono".parse().unwrap();
+        assert_eq!(mono, GodotVersion::new("4.2.1", true).unwrap());
+        let official: GodotVersion = "4.2.1.stable.official.b09f793f5".parse().unwrap();
+        assert!(!official.is_dotnet);
+
+        assert!(GodotVersion::new("4.2.1.nightly", false).is_err());
+    }
+
+    #[test]
+    fn test_parse_version_output() {
+        let cases = [
+            ("4.2.1.stable.official.b09f793f5\n", "4.2.1", false),
+            ("4.2.1.stable.mono.official.b09f793f5", "4.2.1", true),
+            ("4.3.beta2.official.b75f0485b", "4.3.0-beta2", false),
+            ("4.4.dev5.official.9e6098432", "4.4.0-dev5", false),
+            (
+                "Godot Engine v3.5.3.stable.official.6c814135b - https://godotengine.org",
+                "3.5.3",
+                false,
+            ),
+            ("3.6.rc1.mono.official.de2f0f147", "3.6.0-rc1", true),
+        ];
+
+        for (output, version, is_dotnet) in cases {
+            let parsed = GodotVersion::parse_version_output(output).unwrap();
+            assert_eq!(parsed.godot_version_string(), version, "{}", output);
+            assert_eq!(parsed.is_dotnet, is_dotnet, "{}", output);
+        }
+
+        assert!(GodotVersion::parse_version_output("command not found").is_err());
+    }
+
+    #[test]
+    fn test_archive_names() {
+        let v1 = GodotVersion::new("4.2.1", false).unwrap();
+        assert_eq!(
+            v1.archive_name_for_target("linux", "x86_64").unwrap(),
+            "Godot_v4.2.1-stable_linux.x86_64.zip"
+        );
+
+        let v2 = GodotVersion::new("4.3.0-beta2", true).unwrap();
+        assert_eq!(
+            v2.archive_name_for_target("macos", "aarch64").unwrap(),
+            "Godot_v4.3-beta2_mono_macos.universal.zip"
+        );
+        assert_eq!(
+            v2.archive_name_for_target("windows", "x86_64").unwrap(),
+            "Godot_v4.3-beta2_mono_win64.zip"
+        );
+
+        let v3 = GodotVersion::new("3.5.3", false).unwrap();
+        assert_eq!(
+            v3.archive_name_for_target("linux", "x86_64").unwrap(),
+            "Godot_v3.5.3-stable_x11.64.zip"
+        );
+        assert!(v3.archive_name_for_target("linux", "aarch64").is_err());
+        assert!(v1.archive_name_for_target("freebsd", "x86_64").is_err());
+    }
+
+    #[test]
+    fn test_archive_names_by_source() {
+        let stable = GodotVersion::new("4.2.1", false).unwrap();
+        let mono = GodotVersion::new("4.2.1", true).unwrap();
+        let legacy_mono = GodotVersion::new("3.5.3", true).unwrap();
+
+        assert_eq!(
+            stable.archive_name_for_platform("linux.x86_64"),
+            "Godot_v4.2.1-stable_linux.x86_64.zip"
+        );
+        assert_eq!(
+            stable.archive_name_for_platform("win64.exe"),
+            "Godot_v4.2.1-stable_win64.exe.zip"
+        );
+        assert_eq!(
+            mono.archive_name_for_platform("linux.x86_64"),
+            "Godot_v4.2.1-stable_mono_linux_x86_64.zip"
+        );
+        assert_eq!(
+            mono.archive_name_for_platform("win64.exe"),
+            "Godot_v4.2.1-stable_mono_win64.zip"
+        );
+        assert_eq!(
+            mono.archive_name_for_platform("macos.universal"),
+            "Godot_v4.2.1-stable_mono_macos.universal.zip"
+        );
+        assert_eq!(
+            legacy_mono.archive_name_for_platform("x11.64"),
+            "Godot_v3.5.3-stable_mono_x11_64.zip"
+        );
+
+        let beta = GodotVersion::new("4.3-beta2", true).unwrap();
+        assert!(beta
+            .download_url_for_source(Source::TuxFamily)
+            .unwrap()
+            .starts_with(
+                "https://downloads.tuxfamily.org/godotengine/4.3/beta2/mono/Godot_v4.3-beta2_mono_"
+            ));
+        assert!(stable
+            .download_url_for_source(Source::TuxFamily)
+            .unwrap()
+            .starts_with("https://downloads.tuxfamily.org/godotengine/4.2.1/Godot_v4.2.1-stable_"));
+        assert!(stable
+            .download_url_for_source(Source::GitHub)
+            .unwrap()
+            .starts_with(
+                "https://github.com/godotengine/godot-builds/releases/download/4.2.1-stable/"
+            ));
+    }
+
+    #[test]
+    fn test_archive_names_drop_zero_patch() {
+        let v1 = GodotVersion::new("4.3.0", false).unwrap();
+        assert!(v1
+            .archive_name_for_target("linux", "x86_64")
+            .unwrap()
+            .starts_with("Godot_v4.3-stable_"));
+
+        let v2 = GodotVersion::new("4.3.1", false).unwrap();
+        assert!(v2
+            .archive_name_for_target("linux", "x86_64")
+            .unwrap()
+            .starts_with("Godot_v4.3.1-stable_"));
+
+        let v3 = GodotVersion::new("4.3", true).unwrap();
+        assert!(v3
+            .archive_name_for_target("linux", "x86_64")
+            .unwrap()
+            .starts_with("Godot_v4.3-stable_mono_"));
+
+        // Executables inside the archive follow the same naming
+        assert!(v1
+            .get_executable_path_for_target("linux", "x86_64")
+            .unwrap()
+            .starts_with("Godot_v4.3-stable_"));
+        assert!(v2
+            .get_executable_path_for_target("windows", "x86_64")
+            .unwrap()
+            .starts_with("Godot_v4.3.1-stable_"));
+    }
+
+    #[test]
+    fn test_release_tags() {
+        let cases = [
+            ("4.2.1", "4.2.1-stable"),
+            ("4.2.0", "4.2-stable"),
+            ("4.3.0-beta2", "4.3-beta2"),
+            ("4.1.1-rc1", "4.1.1-rc1"),
+        ];
+
+        for (version, tag) in cases {
+            let v = GodotVersion::new(version, false).unwrap();
+            assert_eq!(v.to_tag(), tag);
+            assert_eq!(GodotVersion::from_tag(tag).unwrap(), v);
+        }
+
+        assert_eq!(
+            GodotVersion::from_tag("v4.2-stable").unwrap(),
+            GodotVersion::new("4.2.0", false).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_from_git_tag() {
+        let cases = [
+            ("engine/4.2.1", "4.2.1", false),
+            ("engine/v4.3", "4.3.0", false),
+            ("godot-4.2.1-stable", "4.2.1", false),
+            ("godot-v4.3-beta2", "4.3.0-beta2", false),
+            ("godot/4.1.0-rc1", "4.1.0-rc1", false),
+            ("godot/4.2.1-dotnet", "4.2.1", true),
+            ("v4.2", "4.2.0", false),
+            ("4.2.2", "4.2.2", false),
+            (" engine/4.4.1\n", "4.4.1", false),
+        ];
+
+        for (tag, version, is_dotnet) in cases {
+            assert_eq!(
+                GodotVersion::from_git_tag(tag).unwrap(),
+                GodotVersion::new(version, is_dotnet).unwrap(),
+                "{}",
+                tag
+            );
+        }
+
+        for tag in ["release-1.0", "engine/", "godot-main", "engine/4.x"] {
+            assert!(GodotVersion::from_git_tag(tag).is_err(), "{}", tag);
+        }
+    }
+
+    #[test]
+    fn test_parse_semver_requirement() {
+        for req in ["^4.2", "~4.2.1", ">=4.2, <4.4", "=4.3.0", " 4.2 "] {
+            let (_, warnings) = GodotVersion::parse_semver_requirement(req).unwrap();
+            assert!(warnings.is_empty(), "{}", req);
+        }
+
+        let cases: [(&str, &[u64]); 4] = [
+            (">=4.2", &[4, 5]),
+            ("<4", &[0, 1, 2, 3]),
+            (">=3.5, <5", &[3, 4]),
+            ("*", &[0, 1]),
+        ];
+        for (req, majors) in cases {
+            let (_, warnings) = GodotVersion::parse_semver_requirement(req).unwrap();
+            assert_eq!(
+                warnings,
+                [GodotVersionParseWarning::MajorVersionNotPinned {
+                    majors: majors.to_vec()
+                }],
+                "{}",
+                req
+            );
+        }
+
+        let (req, _) = GodotVersion::parse_semver_requirement("^4.2").unwrap();
+        assert!(req.matches(&Version::new(4, 3, 0)));
+        assert!(GodotVersion::parse_semver_requirement("4.x.beta").is_err());
+    }
+
+    #[test]
+    fn test_ide_editor_paths() {
+        let workspace = tempfile::tempdir().unwrap();
+        let workspace = workspace.path();
+        assert_eq!(GodotVersion::vscode_editor_path(workspace).unwrap(), None);
+        assert_eq!(
+            GodotVersion::jetbrains_editor_path(workspace).unwrap(),
+            None
+        );
+
+        let editor = PathBuf::from(format!("{}/bin/godot", workspace.display()));
+        std::fs::create_dir_all(workspace.join(".vscode")).unwrap();
+        std::fs::write(
+            workspace.join(".vscode/settings.json"),
+            "{\n  // Godot Tools\n  \"godotTools.editorPath.godot4\": \"${workspaceFolder}/bin/godot\",\n}\n",
+        )
+        .unwrap();
+        assert_eq!(
+            GodotVersion::vscode_editor_path(workspace).unwrap(),
+            Some(editor.clone())
+        );
+
+        std::fs::create_dir_all(workspace.join(".idea")).unwrap();
+        std::fs::write(
+            workspace.join(".idea/godot.xml"),
+            "<project version=\"4\">\n  <component name=\"GodotProjectSettings\">\n    \
+             <option name=\"godotPath\" value=\"$PROJECT_DIR$/bin/godot\" />\n  \
+             </component>\n</project>\n",
+        )
+        .unwrap();
+        assert_eq!(
+            GodotVersion::jetbrains_editor_path(workspace).unwrap(),
+            Some(editor)
+        );
+
+        std::fs::write(
+            workspace.join(".vscode/settings.json"),
+            "{\"godotTools.editorPath\": \"relative/godot\"}",
+        )
+        .unwrap();
+        assert_eq!(
+            GodotVersion::vscode_editor_path(workspace).unwrap(),
+            Some(workspace.join("relative/godot"))
+        );
+    }
+
+    #[test]
+    fn test_platform_suffix_detection() {
+        // Test that we get a valid platform suffix (this tests the current system)
+        let suffix = GodotVersion::get_platform_suffix().unwrap();
+        assert!(!suffix.is_empty());
+
+        // Should be one of the expected patterns
+        assert!(
+            GodotVersion::all_platform_suffixes().contains(&suffix),
+            "Got unexpected suffix: {}",
+            suffix
+        );
+    }
+
+    #[test]
+    fn test_channel() {
+        for (version, channel) in [
+            ("4.2.1", GodotChannel::Stable),
+            ("4.3-dev6", GodotChannel::Dev),
+            ("4.3-alpha1", GodotChannel::Alpha),
+            ("4.3-beta2", GodotChannel::Beta),
+            ("4.3-rc1", GodotChannel::Rc),
+            ("4.3.0-custom", GodotChannel::Stable),
+        ] {
+            let version = GodotVersion::new(version, false).unwrap();
+            assert_eq!(version.channel(), channel, "{}", version);
+        }
+        assert_eq!(GodotChannel::Rc.to_string(), "rc");
+        assert!(GodotChannel::Dev < GodotChannel::Alpha);
+        assert!(GodotChannel::Rc < GodotChannel::Stable);
+    }
+
+    #[test]
+    fn test_resolve_latest() {
+        let v = |version: &str| GodotVersion::new(version, false).unwrap();
+        let candidates = [v("4.2.0"), v("4.2.2"), v("4.3.0-beta2"), v("4.2.1")];
+        let req = |req: &str| VersionReq::parse(req).unwrap();
+
+        assert!(v("4.2.1").matches(&req("~4.2")));
+        assert!(!v("4.3.0-beta2").matches(&req("^4")));
+        assert!(v("4.3.0-beta2").matches(&req(">=4.3.0-beta.1")));
+
+        for include_prerelease in [false, true] {
+            assert_eq!(
+                GodotVersion::resolve_latest(&candidates, &req("~4.2"), include_prerelease),
+                Some(v("4.2.2"))
+            );
+        }
+        assert_eq!(
+            GodotVersion::resolve_latest(&candidates, &req("^4"), false),
+            Some(v("4.2.2"))
+        );
+        assert_eq!(
+            GodotVersion::resolve_latest(&candidates, &req("^4"), true),
+            Some(v("4.3.0-beta2"))
+        );
+        assert_eq!(
+            GodotVersion::resolve_latest(&candidates, &req("^5"), true),
+            None
+        );
+    }
+
+    #[test]
+    fn test_from_archive_name() {
+        let versions = [
+            GodotVersion::new("4.2.1", false).unwrap(),
+            GodotVersion::new("4.3.0-beta2", true).unwrap(),
+            GodotVersion::new("4.4-dev5", false).unwrap(),
+            GodotVersion::new("3.5.3", true).unwrap(),
+        ];
+        for version in &versions {
+            for suffix in version.platform_suffixes() {
+                let name = version.archive_name_for_platform(suffix);
+                assert_eq!(
+                    GodotVersion::from_archive_name(&name).unwrap(),
+                    *version,
+                    "{}",
+                    name
+                );
+            }
+        }
+
+        assert_eq!(
+            GodotVersion::from_archive_name("Godot_v4.3-beta2_mono_linux_x86_64.zip").unwrap(),
+            GodotVersion::new("4.3-beta2", true).unwrap()
+        );
+        for name in [
+            "Godot_v4.3-stable_linux.x86_64",
+            "Godot_v4.3-stable_amiga.zip",
+            "Godot_v4.3-stable_export_templates.tpz",
+            "godot-4.3-stable.tar.gz",
+            "Godot_vnope_linux.x86_64.zip",
+        ] {
+            assert!(GodotVersion::from_archive_name(name).is_err(), "{}", name);
+        }
+    }
+
+    #[test]
+    fn test_prerelease_ordering() {
+        let v = |version: &str| GodotVersion::new(version, false).unwrap();
+        let mut versions = vec![
+            v("4.3"),
+            v("4.3-rc1"),
+            v("4.3-dev2"),
+            v("4.3-beta10"),
+            v("4.2.2"),
+            v("4.3-alpha1"),
+            v("4.3-beta2"),
+            v("4.3-dev10"),
+            v("4.3.1-dev1"),
+            v("4.3-rc2"),
+        ];
+        versions.sort();
+
+        let sorted: Vec<String> = versions.iter().map(|v| v.godot_version_string()).collect();
+        assert_eq!(
+            sorted,
+            [
+                "4.2.2",
+                "4.3.0-dev2",
+                "4.3.0-dev10",
+                "4.3.0-alpha1",
+                "4.3.0-beta2",
+                "4.3.0-beta10",
+                "4.3.0-rc1",
+                "4.3.0-rc2",
+                "4.3.0",
+                "4.3.1-dev1",
+            ]
+        );
+        assert!(GodotVersion::new("4.3", false).unwrap() < GodotVersion::new("4.3", true).unwrap());
+    }
+
+    #[test]
+    fn test_godot_3_platform_suffixes() {
+        let v3 = GodotVersion::new("3.5.3", false).unwrap();
+        let mono = GodotVersion::new("3.5.3", true).unwrap();
+        let cases = [
+            (
+                "linux.x86_64",
+                "x11.64",
+                "Godot_v3.5.3-stable_x11.64",
+                "Godot_v3.5.3-stable_x11.64.zip",
+                "Godot_v3.5.3-stable_mono_x11_64.zip",
+            ),
+            (
+                "win64.exe",
+                "win64.exe",
+                "Godot_v3.5.3-stable_win64.exe",
+                "Godot_v3.5.3-stable_win64.exe.zip",
+                "Godot_v3.5.3-stable_mono_win64.zip",
+            ),
+            (
+                "macos.universal",
+                "osx.universal",
+                "Godot.app/Contents/MacOS/Godot",
+                "Godot_v3.5.3-stable_osx.universal.zip",
+                "Godot_v3.5.3-stable_mono_osx.universal.zip",
+            ),
+        ];
+        for (suffix, legacy, executable, archive, mono_archive) in cases {
+            let platform = v3.platform_suffix_for(suffix).unwrap();
+            assert_eq!(platform, legacy);
+            assert_eq!(v3.get_executable_path_for(platform), executable);
+            assert_eq!(v3.archive_name_for_platform(platform), archive);
+            assert_eq!(mono.archive_name_for_platform(platform), mono_archive);
+        }
+
+        let old = GodotVersion::new("3.2.3", false).unwrap();
+        assert_eq!(
+            old.platform_suffix_for("macos.universal").unwrap(),
+            "osx.64"
+        );
+        assert!(v3.platform_suffix_for("linux.arm64").is_err());
+        assert!(v3.platform_suffix_for("windows_arm64.exe").is_err());
+
+        let v4 = GodotVersion::new("4.2.1", false).unwrap();
+        assert_eq!(
+            v4.platform_suffix_for("linux.x86_64").unwrap(),
+            "linux.x86_64"
+        );
+        let error = v4.platform_suffix_for("windows_arm64.exe").unwrap_err();
+        assert!(error.to_string().contains("4.3"), "{}", error);
+        // 4.3 prereleases had Windows on ARM builds too
+        let beta = GodotVersion::new("4.3-beta1", false).unwrap();
+        assert!(beta.platform_suffixes().contains(&"windows_arm64.exe"));
+        assert_eq!(
+            beta.platform_suffix_for("windows_arm64.exe").unwrap(),
+            "windows_arm64.exe"
+        );
+        assert_eq!(
+            GodotVersion::new("4.3", false)
+                .unwrap()
+                .archive_name_for_target("windows", "aarch64")
+                .unwrap(),
+            "Godot_v4.3-stable_windows_arm64.exe.zip"
+        );
+    }
+
+    #[test]
+    fn test_platform_suffixes_by_major() {
+        let v4 = GodotVersion::new("4.2.1", false).unwrap();
+        assert!(v4.platform_suffixes().contains(&"linux.x86_64"));
+        assert!(v4.platform_suffixes().contains(&"macos.universal"));
+        assert!(!v4.platform_suffixes().contains(&"windows_arm64.exe"));
+        let v4_3 = GodotVersion::new("4.3", false).unwrap();
+        assert!(v4_3.platform_suffixes().contains(&"windows_arm64.exe"));
+
+        let v3 = GodotVersion::new("3.5.3", false).unwrap();
+        assert!(v3.platform_suffixes().contains(&"x11.64"));
+        assert!(v3.platform_suffixes().contains(&"osx.universal"));
+        assert!(!v3.platform_suffixes().contains(&"linux.x86_64"));
+    }
+
+    #[test]
+    fn test_executable_path_construction() {
+        let v1 = GodotVersion::new("4.2.1", false).unwrap();
         let v2 = GodotVersion::new("4.2.1", true).unwrap();
-        let dotnet_exe_path = v2.get_executable_path();
-        assert!(!dotnet_exe_path.is_empty());
 
         // Paths should be different for dotnet vs non-dotnet
-        assert_ne!(exe_path, dotnet_exe_path);
+        for (os, arch) in [
+            ("linux", "x86_64"),
+            ("windows", "x86_64"),
+            ("macos", "aarch64"),
+        ] {
+            assert_ne!(
+                v1.get_executable_path_for_target(os, arch).unwrap(),
+                v2.get_executable_path_for_target(os, arch).unwrap()
+            );
+        }
+        assert_eq!(
+            v2.get_executable_path_for_target("linux", "x86_64")
+                .unwrap(),
+            "Godot_v4.2.1-stable_mono_linux_x86_64/Godot_v4.2.1-stable_mono_linux.x86_64"
+        );
+        assert_eq!(
+            GodotVersion::new("3.5.3", true)
+                .unwrap()
+                .get_executable_path_for("x11.64"),
+            "Godot_v3.5.3-stable_mono_x11_64/Godot_v3.5.3-stable_mono_x11.64"
+        );
+
+        // Any platform's layout can be asked for, whatever this one is
+        assert_eq!(
+            v1.get_executable_path_for("linux.x86_64"),
+            "Godot_v4.2.1-stable_linux.x86_64"
+        );
+        assert_eq!(
+            v2.get_executable_path_for("win64.exe"),
+            "Godot_v4.2.1-stable_mono_win64/Godot_v4.2.1-stable_mono_win64.exe"
+        );
+
+        let v4_3 = GodotVersion::new("4.3", false).unwrap();
+        assert_eq!(
+            v4_3.get_executable_path_for("windows_arm64.exe"),
+            "Godot_v4.3-stable_windows_arm64.exe"
+        );
+        assert_eq!(
+            v4_3.archive_name_for_platform("windows_arm64.exe"),
+            "Godot_v4.3-stable_windows_arm64.exe.zip"
+        );
+        let v4_3_mono = GodotVersion::new("4.3", true).unwrap();
+        assert_eq!(
+            v4_3_mono.get_executable_path_for("windows_arm64.exe"),
+            "Godot_v4.3-stable_mono_windows_arm64/Godot_v4.3-stable_mono_windows_arm64.exe"
+        );
+        assert_eq!(
+            v4_3_mono.archive_name_for_platform("windows_arm64.exe"),
+            "Godot_v4.3-stable_mono_windows_arm64.zip"
+        );
+        assert_eq!(
+            v1.get_executable_path_for("macos.universal"),
+            "Godot.app/Contents/MacOS/Godot"
+        );
+    }
+
+    #[test]
+    fn test_console_executable_path() {
+        for is_dotnet in [false, true] {
+            let version = GodotVersion::new("4.2.1", is_dotnet).unwrap();
+            for suffix in ["win64.exe", "win32.exe"] {
+                let gui = version.get_executable_path_for(suffix);
+                let console = version.get_console_executable_path_for(suffix);
+                assert_ne!(gui, console);
+                assert_eq!(console.replacen("_console", "", 1), gui);
+                assert!(console.ends_with("_console.exe"), "{}", console);
+            }
+            for suffix in ["linux.x86_64", "macos.universal"] {
+                assert_eq!(
+                    version.get_console_executable_path_for(suffix),
+                    version.get_executable_path_for(suffix)
+                );
+            }
+        }
+
+        assert_eq!(
+            GodotVersion::new("4.3", false)
+                .unwrap()
+                .get_console_executable_path_for("win64.exe"),
+            "Godot_v4.3-stable_win64_console.exe"
+        );
+        let v3 = GodotVersion::new("3.5.3", false).unwrap();
+        assert_eq!(
+            v3.get_console_executable_path_for("win64.exe"),
+            v3.get_executable_path_for("win64.exe")
+        );
+    }
+
+    #[test]
+    fn test_export_binary_suffix() {
+        let v4 = GodotVersion::new("4.2.1", false).unwrap();
+        assert_eq!(v4.export_binary_suffix("linux.x86_64"), ".x86_64");
+        assert_eq!(v4.export_binary_suffix("linux.x86_32"), ".x86_32");
+        assert_eq!(v4.export_binary_suffix("linux.arm64"), ".arm64");
+        assert_eq!(v4.export_binary_suffix("win64.exe"), ".exe");
+        assert_eq!(v4.export_binary_suffix("macos.universal"), ".app");
+
+        let v3 = GodotVersion::new("3.5.3", false).unwrap();
+        assert_eq!(v3.export_binary_suffix("x11.64"), ".x86_64");
+        assert_eq!(v3.export_binary_suffix("x11.32"), ".x86");
+        assert_eq!(v3.export_binary_suffix("osx.universal"), ".app");
+    }
+
+    #[test]
+    fn test_nuget_package_version() {
+        let nuget = |version: &str| {
+            GodotVersion::new(version, true)
+                .unwrap()
+                .nuget_package_version()
+        };
+        assert_eq!(nuget("4.2.1").as_deref(), Some("4.2.1"));
+        assert_eq!(nuget("4.3").as_deref(), Some("4.3.0"));
+        assert_eq!(nuget("4.3-beta2").as_deref(), Some("4.3.0-beta.2"));
+        assert_eq!(nuget("4.2-rc1").as_deref(), Some("4.2.0-rc.1"));
+        assert_eq!(nuget("4.4-dev3").as_deref(), Some("4.4.0-dev.3"));
+        assert_eq!(nuget("3.5.3"), None);
+    }
+
+    #[test]
+    fn test_source_code_url() {
+        let v = |version: &str| GodotVersion::new(version, false).unwrap();
+        assert_eq!(
+            v("4.2.1").source_code_url(),
+            "https://github.com/godotengine/godot/archive/refs/tags/4.2.1-stable.tar.gz"
+        );
+        assert_eq!(
+            v("4.3-beta2").source_code_url(),
+            "https://github.com/godotengine/godot/archive/refs/tags/4.3-beta2.tar.gz"
+        );
+        assert_eq!(v("4.2").source_code_file_name(), "godot-4.2-stable.tar.gz");
+    }
+
+    #[test]
+    fn test_display_scale_arg() {
+        let v4 = GodotVersion::new("4.2.1", false).unwrap();
+        assert_eq!(v4.display_scale_arg(2.0), ["--display-scale", "2"]);
+        assert_eq!(v4.display_scale_arg(1.5), ["--display-scale", "1.5"]);
+        let v3 = GodotVersion::new("3.5.3", false).unwrap();
+        assert!(v3.display_scale_arg(2.0).is_empty());
+    }
+
+    #[test]
+    fn test_build_id() {
+        let v = GodotVersion::new("4.2.1", false).unwrap();
+        assert_eq!(
+            v.build_id_for("linux.x86_64"),
+            "b6feb28048806588c4daca1370980792ba016f11ea91f42c25d1912ff3332e1c"
+        );
+
+        let v = GodotVersion::new("4.3-beta2", true).unwrap();
+        assert_eq!(
+            v.build_id_for("win64.exe"),
+            "850898534cf2cccf3175e0adc3430281c6c3926d224e05191df5653119feee9e"
+        );
+        assert_ne!(v.build_id_for("win64.exe"), v.build_id_for("linux.x86_64"));
+    }
+
+    #[test]
+    fn test_find_macos_executable() {
+        let dir = tempfile::tempdir().unwrap();
+
+        let v = GodotVersion::new("4.2.1", false).unwrap();
+        assert_eq!(v.macos_app_bundle_name(), "Godot.app");
+        assert_eq!(v.find_macos_executable(dir.path()), None);
+
+        let flat = dir.path().join("Godot.app/Contents/MacOS/Godot");
+        std::fs::create_dir_all(flat.parent().unwrap()).unwrap();
+        std::fs::write(&flat, "").unwrap();
+        assert_eq!(v.find_macos_executable(dir.path()), Some(flat));
+
+        let mono = GodotVersion::new("4.2.1", true).unwrap();
+        assert_eq!(mono.macos_app_bundle_name(), "Godot_mono.app");
+        let stamped = dir
+            .path()
+            .join("Godot_v4.2.1-stable_mono_macos.universal/Godot_mono.app/Contents/MacOS/Godot");
+        std::fs::create_dir_all(stamped.parent().unwrap()).unwrap();
+        std::fs::write(&stamped, "").unwrap();
+        assert_eq!(mono.find_macos_executable(dir.path()), Some(stamped));
+    }
+
+    #[test]
+    fn test_can_open_project() {
+        let can_open = |editor: &str, project: &str| {
+            GodotVersion::new(editor, false)
+                .unwrap()
+                .can_open_project(&GodotVersion::new(project, false).unwrap())
+        };
+
+        assert!(can_open("4.2.1", "4.2"));
+        assert!(can_open("4.3", "4.2"));
+        assert!(can_open("4.3-beta2", "4.3"));
+        assert!(!can_open("4.2.1", "4.3"));
+        assert!(!can_open("4.3", "3.5"));
+        assert!(!can_open("3.6", "4.0"));
+
+        // Prerelease projects need at least that prerelease
+        assert!(can_open("4.3", "4.3-rc1"));
+        assert!(can_open("4.3-rc1", "4.3-beta2"));
+        assert!(!can_open("4.3-beta1", "4.3-beta2"));
+    }
+
+    #[test]
+    fn test_preferred_rendering_method() {
+        let v3 = GodotVersion::new("3.6", false).unwrap();
+        assert_eq!(v3.preferred_rendering_method(), None);
+
+        let v4 = GodotVersion::new("4.2.1", true).unwrap();
+        assert_eq!(
+            v4.preferred_rendering_method(),
+            Some(RenderingMethod::ForwardPlus)
+        );
+        assert_eq!(
+            RenderingMethod::GlCompatibility.to_string(),
+            "gl_compatibility"
+        );
+        assert!(!RenderingMethod::GlCompatibility.uses_vulkan());
+    }
+
+    #[test]
+    fn test_minimum_macos_version() {
+        for (version, minimum) in [
+            ("2.1.6", None),
+            ("3.6", Some((10, 12))),
+            ("4.0", Some((10, 15))),
+            ("4.3-beta2", Some((10, 15))),
+        ] {
+            assert_eq!(
+                GodotVersion::new(version, false)
+                    .unwrap()
+                    .minimum_macos_version(),
+                minimum,
+                "{}",
+                version
+            );
+        }
+    }
+
+    #[test]
+    fn test_strace_suggested_filter() {
+        let standard = GodotVersion::new("4.2.1", false).unwrap();
+        let dotnet = GodotVersion::new("4.2.1", true).unwrap();
+        assert_eq!(standard.strace_suggested_filter(), "%file");
+        assert!(!standard.strace_suggested_filter().contains("mmap"));
+        assert!(dotnet.strace_suggested_filter().ends_with(",mmap"));
+    }
+
+    #[test]
+    fn test_physics_engines() {
+        let v = |version: &str| GodotVersion::new(version, false).unwrap();
+
+        assert_eq!(v("3.6").default_physics_engine(), PhysicsBackend::Bullet);
+        assert_eq!(
+            v("4.2.1").default_physics_engine(),
+            PhysicsBackend::GodotPhysics
+        );
+        assert!(!v("4.1.4").supports_jolt());
+        assert!(v("4.2").supports_jolt());
+
+        let cases = [
+            ("3.6", PhysicsBackend::Bullet, Some("Bullet")),
+            ("3.6", PhysicsBackend::Jolt, None),
+            ("4.1.4", PhysicsBackend::Bullet, None),
+            ("4.1.4", PhysicsBackend::Jolt, None),
+            (
+                "4.2.1",
+                PhysicsBackend::GodotPhysics,
+                Some("GodotPhysics3D"),
+            ),
+            ("4.3", PhysicsBackend::Jolt, Some("JoltPhysics3D")),
+            ("4.4", PhysicsBackend::Jolt, Some("Jolt Physics")),
+        ];
+        for (version, backend, setting) in cases {
+            assert_eq!(
+                v(version).physics_engine_setting(backend),
+                setting,
+                "{} {}",
+                version,
+                backend
+            );
+        }
+    }
+
+    #[test]
+    fn test_web_editor_url() {
+        assert_eq!(
+            GodotVersion::new("4.2.1", false).unwrap().web_editor_url(),
+            Some("https://editor.godotengine.org/releases/4.2.1.stable/".to_string())
+        );
+        assert_eq!(
+            GodotVersion::new("4.3-beta2", false)
+                .unwrap()
+                .web_editor_url()
+                .unwrap(),
+            "https://editor.godotengine.org/releases/4.3.beta2/"
+        );
+        assert_eq!(
+            GodotVersion::new("3.6", false).unwrap().web_editor_url(),
+            None
+        );
+        assert_eq!(
+            GodotVersion::new("4.2.1", true).unwrap().web_editor_url(),
+            None
+        );
+    }
+
+    #[test]
+    fn test_marketing_names() {
+        let v = GodotVersion::new("4.2.1", true).unwrap();
+        assert_eq!(v.to_marketing_name(), "Godot 4.2");
+        assert_eq!(
+            GodotVersion::new("3.6-rc1", false)
+                .unwrap()
+                .to_marketing_name(),
+            "Godot 3.6"
+        );
+
+        let names = [((4, 2), "Godot 4.2 Example"), ((4, 3), "Other")];
+        assert_eq!(v.marketing_name_from(&names), "Godot 4.2 Example");
+        assert_eq!(
+            GodotVersion::new("4.4", false)
+                .unwrap()
+                .marketing_name_from(&names),
+            "Godot 4.4"
+        );
+    }
+
+    #[test]
+    fn test_qualified_versions() {
+        let cases = [
+            ("4.2.1", "4.2.1", false, None),
+            (
+                "4.2.1-dotnet@linux.x86_64",
+                "4.2.1",
+                true,
+                Some(VersionQualifier::Platform("linux.x86_64")),
+            ),
+            (
+                "3.5.3@x11.64",
+                "3.5.3",
+                false,
+                Some(VersionQualifier::Platform("x11.64")),
+            ),
+            (
+                "4.3-beta2@tuxfamily",
+                "4.3.0-beta2",
+                false,
+                Some(VersionQualifier::Source(Source::TuxFamily)),
+            ),
+        ];
+
+        for (input, version, is_dotnet, qualifier) in cases {
+            let expected = GodotVersion::new(version, is_dotnet).unwrap();
+            assert_eq!(
+                GodotVersion::parse_qualified(input).unwrap(),
+                (expected.clone(), qualifier)
+            );
+            assert_eq!(input.parse::<GodotVersion>().unwrap(), expected);
+        }
+
+        let dotnet = GodotVersion::new("4.2.1", true).unwrap();
+        for input in ["4.2.1 (.NET)", "4.2.1-mono", "4.2.1-dotnet"] {
+            assert_eq!(input.parse::<GodotVersion>().unwrap(), dotnet, "{}", input);
+        }
+        assert!("4.2.1 (.NET)".parse::<GodotVersion>().unwrap().is_dotnet);
+        for version in [dotnet, GodotVersion::new("4.3-beta2", true).unwrap()] {
+            assert_eq!(
+                version.to_string().parse::<GodotVersion>().unwrap(),
+                version
+            );
+        }
+
+        let error = "4.2.1@amiga".parse::<GodotVersion>().unwrap_err();
+        assert!(error.to_string().contains("'@amiga'"), "{}", error);
+        assert!("4.2.1@".parse::<GodotVersion>().is_err());
+    }
+
+    #[test]
+    fn test_serde_string_form() {
+        let dotnet = GodotVersion::new("4.3-beta2", true).unwrap();
+        let json = serde_json::to_string(&dotnet).unwrap();
+        assert_eq!(json, "\"4.3.0-beta2-dotnet\"");
+        assert_eq!(serde_json::from_str::<GodotVersion>(&json).unwrap(), dotnet);
+
+        let standard: GodotVersion = serde_json::from_str("\"4.2.1\"").unwrap();
+        assert_eq!(standard, GodotVersion::new("4.2.1", false).unwrap());
+        assert!(serde_json::from_str::<GodotVersion>("\"not a version\"").is_err());
+
+        let detailed = serde_json::to_value(DetailedGodotVersion::from(dotnet.clone())).unwrap();
+        assert_eq!(detailed["is_dotnet"], true);
+        assert_eq!(detailed["version"], "4.3.0-beta.2");
+        let parsed: DetailedGodotVersion = serde_json::from_value(detailed).unwrap();
+        assert_eq!(GodotVersion::from(parsed), dotnet);
+    }
+
+    #[test]
+    fn test_wsl_detection() {
+        assert!(is_wsl_kernel(
+            "Linux version 5.15.153.1-microsoft-standard-WSL2 (root@65c757a075e2) (gcc (GCC) 11.2.0)"
+        ));
+        assert!(is_wsl_kernel(
+            "Linux version 4.4.0-19041-Microsoft (Microsoft@Microsoft.com) (gcc version 5.4.0)"
+        ));
+        assert!(!is_wsl_kernel(
+            "Linux version 6.8.0-45-generic (buildd@lcy02-amd64-075) (x86_64-linux-gnu-gcc-13)"
+        ));
+
+        let host = std::env::consts::OS;
+        assert_eq!(
+            GodotVersion::target_os_for(true, Some(WSL_NATIVE_WINDOWS)),
+            "windows"
+        );
+        assert_eq!(GodotVersion::target_os_for(true, None), host);
+        assert_eq!(GodotVersion::target_os_for(true, Some("linux")), host);
+        assert_eq!(
+            GodotVersion::target_os_for(false, Some(WSL_NATIVE_WINDOWS)),
+            host
+        );
+    }
+
+    #[test]
+    fn test_expected_executable_size_range() {
+        let v4 = GodotVersion::new("4.2.1", false).unwrap();
+        for platform in GodotVersion::all_platform_suffixes() {
+            let (min, max) = v4.expected_executable_size_range_for(platform).unwrap();
+            assert!(min < max, "{}", platform);
+        }
+        let (min, max) = v4
+            .expected_executable_size_range_for("linux.x86_64")
+            .unwrap();
+        assert_eq!((min, max), (65 * 1024 * 1024, 195 * 1024 * 1024));
+
+        let v3 = GodotVersion::new("3.5.3", false).unwrap();
+        for platform in GodotVersion::all_legacy_platform_suffixes() {
+            assert!(v3.expected_executable_size_range_for(platform).is_some());
+        }
+        assert_eq!(v3.expected_executable_size_range_for("linux.x86_64"), None);
+        assert_eq!(
+            GodotVersion::new("2.1", false)
+                .unwrap()
+                .expected_executable_size_range_for("x11.64"),
+            None
+        );
+    }
+
+    #[test]
+    fn test_memory_profiles() {
+        let version = GodotVersion::new("4.2.1", true).unwrap();
+        let pid = std::process::id();
+
+        let massif = version.memory_profile_output_path(MemProfiler::Massif);
+        assert_eq!(
+            massif,
+            PathBuf::from(format!("godot-4.2.1-dotnet.{}.massif.out", pid))
+        );
+        assert_eq!(
+            MemProfiler::Massif.args(&massif),
+            [
+                "--tool=massif".to_string(),
+                format!("--massif-out-file={}", massif.display())
+            ]
+        );
+
+        let heaptrack = version.memory_profile_output_path(MemProfiler::Heaptrack);
+        assert!(heaptrack.to_string_lossy().ends_with(".heaptrack.zst"));
+        assert_eq!(MemProfiler::Heaptrack.binary(), "heaptrack");
+        assert_eq!(
+            MemProfiler::Heaptrack.analyze_command(&heaptrack),
+            format!("heaptrack_print {}", heaptrack.display())
+        );
+        assert_eq!(
+            MemProfiler::Dhat.args(Path::new("out.json"))[0],
+            "--tool=dhat"
+        );
+    }
+
+    #[test]
+    fn test_platform_suffix_for_target() {
+        let cases = [
+            ("windows", "x86_64", "win64.exe"),
+            ("windows", "x86", "win32.exe"),
+            ("macos", "x86_64", "macos.universal"),
+            ("macos", "aarch64", "macos.universal"),
+            ("linux", "x86_64", "linux.x86_64"),
+            ("linux", "x86", "linux.x86_32"),
+            ("linux", "arm", "linux.arm32"),
+            ("linux", "aarch64", "linux.arm64"),
+            ("windows", "aarch64", "windows_arm64.exe"),
+        ];
+        for (os, arch, suffix) in cases {
+            assert_eq!(
+                GodotVersion::get_platform_suffix_for_target(os, arch).unwrap(),
+                suffix
+            );
+        }
+
+        for arch in ["riscv64", "powerpc64", "s390x"] {
+            let error = GodotVersion::get_platform_suffix_for_target("linux", arch).unwrap_err();
+            assert_eq!(
+                error.downcast_ref::<UnsupportedArchError>(),
+                Some(&UnsupportedArchError {
+                    arch: arch.to_string()
+                })
+            );
+            assert!(error.to_string().contains("gdenv adopt"), "{}", error);
+        }
+
+        // These used to fall back to x86_64 builds that can't run there
+        for (os, arch) in [
+            ("windows", "arm"),
+            ("freebsd", "x86_64"),
+            ("android", "aarch64"),
+        ] {
+            let error = GodotVersion::get_platform_suffix_for_target(os, arch).unwrap_err();
+            assert!(error.to_string().contains(os), "{}", error);
+        }
+    }
+
+    #[test]
+    fn test_export_templates() {
+        let v = GodotVersion::new("4.2", false).unwrap();
+        assert_eq!(v.export_templates_name(), "4.2.stable");
+        assert_eq!(
+            v.export_templates_url(),
+            "https://github.com/godotengine/godot-builds/releases/download/4.2-stable/Godot_v4.2-stable_export_templates.tpz"
+        );
+
+        let dotnet = GodotVersion::new("4.3-rc1", true).unwrap();
+        assert_eq!(dotnet.export_templates_name(), "4.3.rc1.mono");
+        assert!(dotnet
+            .export_templates_url()
+            .ends_with("/4.3-rc1/Godot_v4.3-rc1_mono_export_templates.tpz"));
     }
 }
@@ -0,0 +1,144 @@
+use anyhow::{anyhow, Result};
+use clap::ValueEnum;
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+use crate::{config::Config, godot::GodotVersion, ui};
+
+/// Points in the install/uninstall pipeline where a global hook can run
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum HookEvent {
+    PreInstall,
+    PostInstall,
+    PostUninstall,
+}
+
+impl HookEvent {
+    fn key(&self) -> &'static str {
+        match self {
+            HookEvent::PreInstall => "pre_install",
+            HookEvent::PostInstall => "post_install",
+            HookEvent::PostUninstall => "post_uninstall",
+        }
+    }
+}
+
+/// Shell commands run for every install/uninstall, stored in `hooks.toml`
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct GlobalHooks {
+    pub pre_install: Option<String>,
+    pub post_install: Option<String>,
+    pub post_uninstall: Option<String>,
+}
+
+impl GlobalHooks {
+    pub fn path(config: &Config) -> PathBuf {
+        config.config_dir.join("hooks.toml")
+    }
+
+    /// Load the global hooks, returning no hooks if the file doesn't exist
+    pub fn load(config: &Config) -> Result<Self> {
+        Self::load_from(&Self::path(config))
+    }
+
+    fn load_from(path: &Path) -> Result<Self> {
+        if !path.exists() {
+            return Ok(Self::default());
+        }
+
+        let content = fs::read_to_string(path)?;
+        toml::from_str(&content).map_err(|e| anyhow!("Failed to parse {}: {}", path.display(), e))
+    }
+
+    pub fn save(&self, config: &Config) -> Result<()> {
+        let path = Self::path(config);
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        fs::write(&path, toml::to_string(self)?)?;
+        Ok(())
+    }
+
+    pub fn get(&self, event: HookEvent) -> Option<&str> {
+        match event {
+            HookEvent::PreInstall => self.pre_install.as_deref(),
+            HookEvent::PostInstall => self.post_install.as_deref(),
+            HookEvent::PostUninstall => self.post_uninstall.as_deref(),
+        }
+    }
+
+    pub fn set(&mut self, event: HookEvent, command: Option<String>) {
+        let slot = match event {
+            HookEvent::PreInstall => &mut self.pre_install,
+            HookEvent::PostInstall => &mut self.post_install,
+            HookEvent::PostUninstall => &mut self.post_uninstall,
+        };
+        *slot = command;
+    }
+
+    /// Run the hook registered for `event`, if any
+    ///
+    /// The hook receives `GDENV_HOOK`, `GDENV_VERSION`, `GDENV_DOTNET` and
+    /// `GDENV_INSTALL_PATH` in its environment. A failing hook is an error.
+    pub fn run(&self, event: HookEvent, version: &GodotVersion, install_path: &Path) -> Result<()> {
+        let Some(command) = self.get(event) else {
+            return Ok(());
+        };
+
+        ui::info(&format!("Running {} hook: {}", event.key(), command));
+
+        let mut shell = if cfg!(windows) {
+            let mut shell = Command::new("cmd");
+            shell.arg("/C");
+            shell
+        } else {
+            let mut shell = Command::new("sh");
+            shell.arg("-c");
+            shell
+        };
+
+        let status = shell
+            .arg(command)
+            .env("GDENV_HOOK", event.key())
+            .env("GDENV_VERSION", version.godot_version_string())
+            .env("GDENV_DOTNET", version.is_dotnet.to_string())
+            .env("GDENV_INSTALL_PATH", install_path)
+            .status()?;
+
+        if !status.success() {
+            return Err(anyhow!("{} hook failed with {}", event.key(), status));
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_hooks_round_trip() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("hooks.toml");
+        fs::write(&path, "post_install = \"update-desktop-database\"\n").unwrap();
+
+        let mut hooks = GlobalHooks::load_from(&path).unwrap();
+        assert_eq!(hooks.get(HookEvent::PreInstall), None);
+        assert_eq!(
+            hooks.get(HookEvent::PostInstall),
+            Some("update-desktop-database")
+        );
+
+        hooks.set(HookEvent::PostUninstall, Some("echo removed".to_string()));
+        let reparsed: GlobalHooks = toml::from_str(&toml::to_string(&hooks).unwrap()).unwrap();
+        assert_eq!(reparsed, hooks);
+
+        assert_eq!(
+            GlobalHooks::load_from(&dir.path().join("missing.toml")).unwrap(),
+            GlobalHooks::default()
+        );
+    }
+}
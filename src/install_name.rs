@@ -0,0 +1,298 @@
+use anyhow::{anyhow, Result};
+use regex::{Regex, RegexBuilder};
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::sync::OnceLock;
+
+use crate::godot::GodotVersion;
+
+/// How installation directories are named unless `install_dir_template` is set
+pub const DEFAULT_TEMPLATE: &str = "godot-{version}{dotnet}";
+
+/// The placeholders an installation directory template can use:
+/// - `{version}`: the version, e.g. "4.2.1" or "4.3.0-beta2"
+/// - `{channel}`: "stable", or the prerelease kind ("dev", "alpha", "beta" or "rc")
+/// - `{dotnet}`: "-dotnet" for .NET builds, empty otherwise
+/// - `{platform}`: the platform suffix of the builds, e.g. "linux.x86_64"
+const PLACEHOLDERS: &[&str] = &["version", "channel", "dotnet", "platform"];
+
+/// A template for installation directory names (see [`PLACEHOLDERS`]), together
+/// with the parser for the names it produces. Stored in `config.toml` as the template.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(try_from = "String", into = "String")]
+pub struct InstallNameTemplate {
+    template: String,
+    /// Matches the names the template produces, in exact case and in any case
+    regex: Regex,
+    regex_any_case: Regex,
+}
+
+enum Part<'a> {
+    Literal(&'a str),
+    Placeholder(&'a str),
+}
+
+impl InstallNameTemplate {
+    /// Check a template: it must name both `{version}` and `{dotnet}` (so every
+    /// build gets its own directory), use each placeholder at most once, and be
+    /// a single path component
+    pub fn new(template: &str) -> Result<Self> {
+        let parts = split(template)?;
+        let placeholders: Vec<&str> = parts
+            .iter()
+            .filter_map(|part| match part {
+                Part::Placeholder(name) => Some(*name),
+                Part::Literal(_) => None,
+            })
+            .collect();
+
+        for (i, name) in placeholders.iter().enumerate() {
+            if !PLACEHOLDERS.contains(name) {
+                return Err(anyhow!(
+                    "Unknown placeholder '{{{}}}' in '{}' (available: {})",
+                    name,
+                    template,
+                    PLACEHOLDERS
+                        .iter()
+                        .map(|p| format!("{{{}}}", p))
+                        .collect::<Vec<_>>()
+                        .join(", ")
+                ));
+            }
+            if placeholders[..i].contains(name) {
+                return Err(anyhow!("'{{{}}}' appears twice in '{}'", name, template));
+            }
+        }
+        for required in ["version", "dotnet"] {
+            if !placeholders.contains(&required) {
+                return Err(anyhow!(
+                    "'{}' must contain {{{}}} to keep installations apart",
+                    template,
+                    required
+                ));
+            }
+        }
+        if template.contains(['/', '\\']) || template.trim() != template {
+            return Err(anyhow!(
+                "'{}' must be a plain directory name, without slashes or surrounding spaces",
+                template
+            ));
+        }
+
+        let pattern = parts
+            .iter()
+            .map(|part| match part {
+                Part::Literal(text) => regex::escape(text),
+                // Lazy, so a following "{dotnet}" isn't swallowed as a prerelease
+                Part::Placeholder("version") => {
+                    r"(?P<version>\d+\.\d+(?:\.\d+)?(?:-[0-9A-Za-z.]+?)??)".to_string()
+                }
+                Part::Placeholder("channel") => {
+                    r"(?P<channel>stable|dev|alpha|beta|rc)".to_string()
+                }
+                Part::Placeholder("dotnet") => r"(?P<dotnet>-dotnet)?".to_string(),
+                Part::Placeholder(_) => r"(?P<platform>[0-9A-Za-z_.]+?)".to_string(),
+            })
+            .collect::<String>();
+
+        let regex = |case_insensitive| {
+            RegexBuilder::new(&format!("^{}$", pattern))
+                .case_insensitive(case_insensitive)
+                .build()
+                .expect("install name patterns are built from escaped literals")
+        };
+        Ok(Self {
+            template: template.to_string(),
+            regex: regex(false),
+            regex_any_case: regex(true),
+        })
+    }
+
+    /// The default template, built once
+    pub fn default_ref() -> &'static Self {
+        static DEFAULT: OnceLock<InstallNameTemplate> = OnceLock::new();
+        DEFAULT.get_or_init(Self::default)
+    }
+
+    /// Get the directory name of a version's installation
+    pub fn format(&self, version: &GodotVersion) -> String {
+        let dotnet = if version.is_dotnet { "-dotnet" } else { "" };
+        self.template
+            .replace("{version}", &version.godot_version_string())
+            .replace("{channel}", channel(version))
+            .replace("{dotnet}", dotnet)
+            .replace("{platform}", &platform())
+    }
+
+    /// Parse an installation directory name produced by [`format`](Self::format).
+    /// Names for another platform don't parse, as their builds can't run here.
+    pub fn parse(&self, name: &str, case_insensitive: bool) -> Option<GodotVersion> {
+        let regex = if case_insensitive {
+            &self.regex_any_case
+        } else {
+            &self.regex
+        };
+        let captures = regex.captures(name)?;
+
+        let is_dotnet = captures.name("dotnet").is_some();
+        let version = GodotVersion::new(&captures["version"], is_dotnet).ok()?;
+
+        if let Some(name) = captures.name("channel") {
+            if !name.as_str().eq_ignore_ascii_case(channel(&version)) {
+                return None;
+            }
+        }
+        if let Some(name) = captures.name("platform") {
+            if !name.as_str().eq_ignore_ascii_case(&platform()) {
+                return None;
+            }
+        }
+
+        Some(version)
+    }
+}
+
+impl Default for InstallNameTemplate {
+    fn default() -> Self {
+        Self::new(DEFAULT_TEMPLATE).expect("the default template is valid")
+    }
+}
+
+impl PartialEq for InstallNameTemplate {
+    fn eq(&self, other: &Self) -> bool {
+        self.template == other.template
+    }
+}
+
+impl Eq for InstallNameTemplate {}
+
+impl fmt::Display for InstallNameTemplate {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.template)
+    }
+}
+
+impl TryFrom<String> for InstallNameTemplate {
+    type Error = anyhow::Error;
+
+    fn try_from(template: String) -> Result<Self> {
+        Self::new(&template)
+    }
+}
+
+impl From<InstallNameTemplate> for String {
+    fn from(template: InstallNameTemplate) -> Self {
+        template.template
+    }
+}
+
+/// Split a template into literal text and `{placeholder}` names
+fn split(template: &str) -> Result<Vec<Part<'_>>> {
+    let mut parts = Vec::new();
+    let mut rest = template;
+
+    while let Some(start) = rest.find('{') {
+        let end = rest[start..]
+            .find('}')
+            .map(|end| start + end)
+            .ok_or_else(|| anyhow!("Unclosed '{{' in '{}'", template))?;
+        if start > 0 {
+            parts.push(Part::Literal(&rest[..start]));
+        }
+        parts.push(Part::Placeholder(&rest[start + 1..end]));
+        rest = &rest[end + 1..];
+    }
+    if !rest.is_empty() {
+        parts.push(Part::Literal(rest));
+    }
+
+    Ok(parts)
+}
+
+/// "stable", or the kind of prerelease without its number ("beta2" -> "beta")
+fn channel(version: &GodotVersion) -> &'static str {
+    let pre = version.version.pre.as_str();
+    ["dev", "alpha", "beta", "rc"]
+        .into_iter()
+        .find(|kind| pre.starts_with(kind))
+        .unwrap_or("stable")
+}
+
+/// The platform suffix of the builds gdenv manages, or the OS name where Godot has none
+fn platform() -> String {
+    GodotVersion::get_platform_suffix()
+        .map(String::from)
+        .unwrap_or_else(|_| std::env::consts::OS.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn v(version: &str, is_dotnet: bool) -> GodotVersion {
+        GodotVersion::new(version, is_dotnet).unwrap()
+    }
+
+    #[test]
+    fn test_default_template_matches_installation_name() {
+        let template = InstallNameTemplate::default();
+        for version in [v("4.2.1", false), v("4.2.1", true), v("4.3-beta2", true)] {
+            assert_eq!(template.format(&version), version.installation_name());
+            assert_eq!(
+                template.parse(&version.installation_name(), false),
+                Some(version)
+            );
+        }
+        assert_eq!(template.parse("not-godot", false), None);
+        assert_eq!(template.parse("Godot-4.2.1", false), None);
+        assert_eq!(template.parse("Godot-4.2.1", true), Some(v("4.2.1", false)));
+    }
+
+    #[test]
+    fn test_custom_template_round_trip() {
+        let template = InstallNameTemplate::new("{channel}_{version}{dotnet}@{platform}").unwrap();
+        let platform = platform();
+
+        let cases = [
+            (v("4.2.1", false), format!("stable_4.2.1@{}", platform)),
+            (
+                v("4.2.1", true),
+                format!("stable_4.2.1-dotnet@{}", platform),
+            ),
+            (
+                v("4.3-beta2", true),
+                format!("beta_4.3.0-beta2-dotnet@{}", platform),
+            ),
+            (v("4.4-dev3", false), format!("dev_4.4.0-dev3@{}", platform)),
+        ];
+        for (version, name) in cases {
+            assert_eq!(template.format(&version), name);
+            assert_eq!(template.parse(&name, false), Some(version));
+        }
+
+        // Other platforms and inconsistent channels aren't installations of this template
+        assert_eq!(template.parse("stable_4.2.1@amiga.m68k", false), None);
+        assert_eq!(
+            template.parse(&format!("beta_4.2.1@{}", platform), false),
+            None
+        );
+
+        let bare = InstallNameTemplate::new("{version}{dotnet}").unwrap();
+        assert_eq!(bare.format(&v("4.2.1", false)), "4.2.1");
+        assert_eq!(bare.parse("4.2.1-dotnet", false), Some(v("4.2.1", true)));
+    }
+
+    #[test]
+    fn test_invalid_templates() {
+        for template in [
+            "godot-{version}",
+            "godot-{dotnet}",
+            "{version}{dotnet}{version}",
+            "{version}{dotnet}-{arch}",
+            "{version{dotnet}",
+            "builds/{version}{dotnet}",
+        ] {
+            assert!(InstallNameTemplate::new(template).is_err(), "{}", template);
+        }
+    }
+}
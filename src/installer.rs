@@ -1,7 +1,85 @@
-use crate::{config::Config, godot::GodotVersion, ui};
+use crate::{
+    config::Config,
+    disk,
+    dry_run::DryRunMode,
+    godot::GodotVersion,
+    install_name::InstallNameTemplate,
+    lockfile::{LockFile, Pin},
+    paths, ui,
+};
 use anyhow::Result;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
 use std::fs;
+use std::io;
 use std::path::{Path, PathBuf};
+use sysinfo::{ProcessRefreshKind, ProcessesToUpdate, System, UpdateKind};
+
+/// File in an installation directory that points to an adopted, externally managed binary
+pub const ADOPTED_FILE: &str = "adopted-from";
+
+/// File in an installation directory recording where the installation came from
+pub const SOURCE_FILE: &str = "source.json";
+
+/// How an installation got into gdenv, see [`Installer::source_for`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum InstallSource {
+    /// Downloaded and extracted by gdenv, which can also upgrade it
+    Managed,
+    /// A binary registered with `gdenv adopt`, e.g. a custom build
+    Adopted,
+    /// A Steam installation registered with `gdenv import-from-steam`
+    Steam,
+}
+
+impl std::fmt::Display for InstallSource {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Managed => write!(f, "managed"),
+            Self::Adopted => write!(f, "adopted"),
+            Self::Steam => write!(f, "steam"),
+        }
+    }
+}
+
+/// The contents of [`SOURCE_FILE`]
+#[derive(Debug, Serialize, Deserialize)]
+struct SourceRecord {
+    source: InstallSource,
+}
+
+/// Where a candidate binary for a version comes from
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CandidateSource {
+    /// A binary registered with `gdenv adopt`
+    Adopted,
+    /// A binary in an installation gdenv downloaded
+    Managed,
+    /// A Godot of the same version outside gdenv ($GODOT_BIN or PATH)
+    System,
+}
+
+impl std::fmt::Display for CandidateSource {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Adopted => write!(f, "adopted"),
+            Self::Managed => write!(f, "managed"),
+            Self::System => write!(f, "system"),
+        }
+    }
+}
+
+/// A binary the resolver considered for a version, see [`Installer::resolve_binary_candidates`]
+#[derive(Debug, Clone)]
+pub struct Candidate {
+    pub source: CandidateSource,
+    pub path: PathBuf,
+    /// Whether gdenv runs this binary for the version
+    pub selected: bool,
+    /// Why the binary was or wasn't selected
+    pub reason: String,
+}
 
 pub struct Installer {
     config: Config,
@@ -12,34 +90,57 @@ impl Installer {
         Self { config }
     }
 
+    /// Extract a version's archive into its installation directory, or in a dry run say
+    /// that it would
     pub async fn install_version_from_archive(
         &self,
         version: &GodotVersion,
         archive_path: &Path,
+        dry_run: DryRunMode,
     ) -> Result<PathBuf> {
-        let install_path = self
-            .config
-            .installations_dir
-            .join(version.installation_name());
+        let install_path = self.config.install_path(version);
+        let replacing = if install_path.exists() {
+            ", replacing the existing installation"
+        } else {
+            ""
+        };
+        if dry_run.skip(|| {
+            format!(
+                "extract {} to {}{}",
+                archive_path.display(),
+                install_path.display(),
+                replacing
+            )
+        }) {
+            return Ok(install_path);
+        }
+
+        self.extract_archive_to(archive_path, &install_path)?;
+        write_source(&install_path, InstallSource::Managed)?;
+
+        ui::success("Installation complete");
+        Ok(install_path)
+    }
 
+    /// Extract a release archive into `install_path`, replacing whatever is there
+    pub fn extract_archive_to(&self, archive_path: &Path, install_path: &Path) -> Result<()> {
         // Remove existing installation if it exists
         if install_path.exists() {
             ui::info("Removing existing installation...");
-            fs::remove_dir_all(&install_path)?;
+            fs::remove_dir_all(install_path)?;
         }
 
         // Create installation directory
-        fs::create_dir_all(&install_path)?;
+        fs::create_dir_all(install_path)?;
 
         ui::info("Extracting archive...");
-        self.extract_zip(archive_path, &install_path)?;
+        self.extract_zip(archive_path, install_path)?;
 
         // Make the Godot executable... executable (Unix only)
         #[cfg(unix)]
-        self.make_executable(&install_path)?;
+        self.make_executable(install_path)?;
 
-        ui::success("Installation complete");
-        Ok(install_path)
+        Ok(())
     }
 
     fn extract_zip(&self, archive_path: &Path, destination: &Path) -> Result<()> {
@@ -80,6 +181,57 @@ impl Installer {
         Ok(())
     }
 
+    /// Get the directory Godot loads a version's export templates from, which is
+    /// shared with installs made by the editor itself
+    pub fn export_templates_dir(version: &GodotVersion) -> Option<PathBuf> {
+        let godot_dir = if cfg!(target_os = "linux") {
+            "godot"
+        } else {
+            "Godot"
+        };
+        let templates_dir = if version.version.major >= 4 {
+            "export_templates"
+        } else {
+            "templates"
+        };
+
+        Some(
+            dirs::data_dir()?
+                .join(godot_dir)
+                .join(templates_dir)
+                .join(version.export_templates_name()),
+        )
+    }
+
+    /// Install export templates from a downloaded `.tpz` archive, returning their directory
+    pub fn install_export_templates(
+        &self,
+        version: &GodotVersion,
+        archive_path: &Path,
+    ) -> Result<PathBuf> {
+        let templates_dir = Self::export_templates_dir(version)
+            .ok_or_else(|| anyhow::anyhow!("Could not determine the export templates directory"))?;
+        let parent = templates_dir.parent().unwrap();
+        fs::create_dir_all(parent)?;
+
+        // The archive holds a single "templates" directory; unpack it next to its final
+        // location so Godot never sees a half-extracted version
+        let staging = parent.join(format!(".{}.extracting", version.export_templates_name()));
+        if staging.exists() {
+            fs::remove_dir_all(&staging)?;
+        }
+        ui::info("Extracting export templates...");
+        self.extract_zip(archive_path, &staging)?;
+
+        if templates_dir.exists() {
+            fs::remove_dir_all(&templates_dir)?;
+        }
+        fs::rename(staging.join("templates"), &templates_dir)?;
+        fs::remove_dir_all(&staging)?;
+
+        Ok(templates_dir)
+    }
+
     #[cfg(unix)]
     fn make_executable(&self, install_path: &Path) -> Result<()> {
         use std::os::unix::fs::PermissionsExt;
@@ -103,11 +255,50 @@ impl Installer {
         Ok(())
     }
 
+    /// Register an existing Godot binary as an installation of `version`
+    /// The binary stays where it is; uninstalling only forgets about it.
+    pub fn adopt(
+        &self,
+        version: &GodotVersion,
+        executable: &Path,
+        source: InstallSource,
+    ) -> Result<PathBuf> {
+        let install_path = self.config.install_path(version);
+
+        if install_path.exists() {
+            return Err(anyhow::anyhow!("Godot v{} is already installed", version));
+        }
+
+        let executable = executable.canonicalize()?;
+        fs::create_dir_all(&install_path)?;
+        fs::write(
+            install_path.join(ADOPTED_FILE),
+            executable.to_string_lossy().as_bytes(),
+        )?;
+        write_source(&install_path, source)?;
+
+        Ok(install_path)
+    }
+
+    /// Where an installation came from. Installations made before gdenv recorded
+    /// their source count as adopted if they point to an outside binary.
+    pub fn source_for(&self, version: &GodotVersion) -> InstallSource {
+        let install_path = self.config.install_path(version);
+        fs::read_to_string(install_path.join(SOURCE_FILE))
+            .ok()
+            .and_then(|content| serde_json::from_str::<SourceRecord>(&content).ok())
+            .map(|record| record.source)
+            .unwrap_or_else(|| {
+                if install_path.join(ADOPTED_FILE).is_file() {
+                    InstallSource::Adopted
+                } else {
+                    InstallSource::Managed
+                }
+            })
+    }
+
     pub fn uninstall_version(&self, version: &GodotVersion) -> Result<()> {
-        let install_path = self
-            .config
-            .installations_dir
-            .join(version.installation_name());
+        let install_path = self.config.install_path(version);
 
         if !install_path.exists() {
             ui::warning(&format!("Godot v{} is not installed", version));
@@ -129,10 +320,7 @@ impl Installer {
         version: &GodotVersion,
         show_message: bool,
     ) -> Result<()> {
-        let install_path = self
-            .config
-            .installations_dir
-            .join(version.installation_name());
+        let install_path = self.config.install_path(version);
 
         if !install_path.exists() {
             return Err(anyhow::anyhow!("Godot v{} is not installed", version));
@@ -201,13 +389,275 @@ impl Installer {
         Ok(())
     }
 
+    /// Compute a digest of every installation, e.g. for a CI cache key. It hashes the
+    /// lines "<installation name>:<SHA-256 of its Godot binary>\n" in sorted order, so it
+    /// changes whenever a version is installed, removed or replaced.
+    pub fn compute_total_checksum(&self) -> Result<String> {
+        let mut lines = self
+            .list_installed()?
+            .iter()
+            .map(|version| {
+                let executable = self.get_executable(version)?;
+                Ok(format!(
+                    "{}:{}\n",
+                    version.installation_name(),
+                    file_sha256(&executable)?
+                ))
+            })
+            .collect::<Result<Vec<_>>>()?;
+        lines.sort();
+
+        Ok(format!("{:x}", Sha256::digest(lines.concat().as_bytes())))
+    }
+
+    /// Find the installed version a possibly partial spec names: "4.2.1" only matches
+    /// itself, while "4.2" or "4" match the newest installed release of the series
+    /// (so "4.2" picks 4.2.2 over 4.2.0), stable releases first
+    pub fn resolve_installed(&self, spec: &str, is_dotnet: bool) -> Result<Option<GodotVersion>> {
+        let installed = self.list_installed()?;
+        let spec = spec.trim();
+
+        let parts: Vec<&str> = spec.split('.').collect();
+        let numbers: Vec<u64> = parts.iter().filter_map(|p| p.parse().ok()).collect();
+        if numbers.len() != parts.len() || !(1..=2).contains(&numbers.len()) {
+            return Ok(GodotVersion::new(spec, is_dotnet)
+                .ok()
+                .filter(|exact| installed.contains(exact)));
+        }
+
+        Ok(installed
+            .into_iter()
+            .filter(|v| v.is_dotnet == is_dotnet && v.version.major == numbers[0])
+            .filter(|v| numbers.get(1).is_none_or(|&minor| v.version.minor == minor))
+            .max_by_key(|v| (!v.is_prerelease(), v.clone())))
+    }
+
+    /// The installed version a pin selects, if any. Ranges don't say whether they're for
+    /// .NET, so they resolve among the installs matching the `default_dotnet` setting.
+    pub fn resolve_pin(&self, pin: &Pin) -> Result<Option<GodotVersion>> {
+        let installed: Vec<GodotVersion> = self
+            .list_installed()?
+            .into_iter()
+            .filter(|v| {
+                pin.version().is_some() || v.is_dotnet == self.config.settings.default_dotnet
+            })
+            .collect();
+        Ok(pin.resolve(&installed))
+    }
+
+    /// Pick the installed version to use for a project: the version pinned by its
+    /// `.godot-version` if there is one, otherwise the active version
+    pub fn resolve_version_for(&self, project_dir: &Path) -> Result<GodotVersion> {
+        if let Some(lockfile_path) = LockFile::find(project_dir) {
+            let pin = LockFile::read_pin(&lockfile_path)?;
+            return self.resolve_pin(&pin)?.ok_or_else(|| match &pin {
+                Pin::Version(version) => anyhow::anyhow!(
+                    "Godot v{} (pinned by {}) is not installed. Run 'gdenv install' first.",
+                    version,
+                    lockfile_path.display()
+                ),
+                Pin::Range(range) => anyhow::anyhow!(
+                    "No installed Godot version satisfies '{}' (pinned by {}). Run 'gdenv install' first.",
+                    range,
+                    lockfile_path.display()
+                ),
+            });
+        }
+
+        self.get_active_version()?.ok_or_else(|| {
+            anyhow::anyhow!("No active Godot version set. Use 'gdenv use <version>' to set one.")
+        })
+    }
+
+    /// Fail if any of `running_executables` was started from this version's installation
+    /// Removing a running editor fails with a locked-file error on Windows.
+    pub fn ensure_not_running(
+        &self,
+        version: &GodotVersion,
+        running_executables: &[PathBuf],
+    ) -> Result<()> {
+        let install_path = self.config.install_path(version);
+        let install_path = install_path.canonicalize().unwrap_or(install_path);
+
+        let case_insensitive = paths::is_case_insensitive(&self.config.installations_dir);
+        if let Some(exe) = running_executables
+            .iter()
+            .find(|exe| paths::path_starts_with(exe, &install_path, case_insensitive))
+        {
+            return Err(anyhow::anyhow!(
+                "Godot v{} is currently open ({}). Close that editor first.",
+                version,
+                exe.display()
+            ));
+        }
+
+        Ok(())
+    }
+
+    /// Whether a version is installed with its executable still in place.
+    /// This only touches the local filesystem.
+    pub fn is_installed(&self, version: &GodotVersion) -> bool {
+        self.get_executable(version).is_ok()
+    }
+
+    /// Get the on-disk size of a version's installation directory.
+    /// Adopted installations only count gdenv's marker, not the binary they point to.
+    pub fn install_size_bytes(&self, version: &GodotVersion) -> Result<u64> {
+        let install_path = self.config.install_path(version);
+        Ok(disk::dir_size(&install_path)?)
+    }
+
+    /// Get the path to the Godot executable of an installed version
+    pub fn get_executable(&self, version: &GodotVersion) -> Result<PathBuf> {
+        let install_path = self.config.install_path(version);
+
+        if !install_path.exists() {
+            return Err(anyhow::anyhow!("Godot v{} is not installed", version));
+        }
+
+        self.find_godot_executable(&install_path, version)
+    }
+
+    /// Get the console executable of an installed version (see
+    /// [`GodotVersion::get_console_executable_path`]), or the editor executable where
+    /// the build has no separate console one
+    pub fn get_console_executable(&self, version: &GodotVersion) -> Result<PathBuf> {
+        let console = self
+            .config
+            .install_path(version)
+            .join(version.get_console_executable_path()?);
+        if console.is_file() {
+            return Ok(console);
+        }
+        self.get_executable(version)
+    }
+
+    /// Check that the editor executable of `version` has a plausible size, returning a
+    /// warning if it's outside [`GodotVersion::expected_executable_size_range_bytes`].
+    /// A wrong size points to a bad download even when the archive extracted cleanly.
+    pub fn executable_size_warning(&self, version: &GodotVersion) -> Result<Option<String>> {
+        let Some(range) = version.expected_executable_size_range_bytes() else {
+            return Ok(None);
+        };
+
+        let executable = self.get_executable(version)?;
+        // A macOS app bundle is a directory; its binaries are in Contents/MacOS
+        let size = if executable.is_dir() {
+            disk::dir_size(&executable.join("Contents").join("MacOS"))?
+        } else {
+            fs::metadata(&executable)?.len()
+        };
+
+        Ok(size_warning(&executable, size, range))
+    }
+
+    /// List every binary that could provide `version` and which one gdenv picks.
+    /// An adopted binary takes precedence over files in the installation; Godot
+    /// binaries outside gdenv are listed but never used.
+    pub fn resolve_binary_candidates(&self, version: &GodotVersion) -> Vec<Candidate> {
+        let install_path = self.config.install_path(version);
+        let selected = self.get_executable(version).ok();
+        let is_selected = |path: &Path| selected.as_deref() == Some(path);
+        let mut candidates = Vec::new();
+
+        let adopted = fs::read_to_string(install_path.join(ADOPTED_FILE))
+            .ok()
+            .map(|content| PathBuf::from(content.trim()));
+        if let Some(path) = &adopted {
+            let reason = if is_selected(path) {
+                "adopted binaries take precedence over the installation's own files"
+            } else {
+                "the adopted binary no longer exists"
+            };
+            candidates.push(Candidate {
+                source: CandidateSource::Adopted,
+                selected: is_selected(path),
+                path: path.clone(),
+                reason: reason.to_string(),
+            });
+        }
+
+        let expected = version
+            .get_executable_path()
+            .ok()
+            .map(|path| install_path.join(path))
+            .filter(|path| path.is_file());
+        if let Some(path) = &expected {
+            let reason = if is_selected(path) {
+                "the binary gdenv installed"
+            } else {
+                "shadowed by the adopted binary"
+            };
+            candidates.push(Candidate {
+                source: CandidateSource::Managed,
+                selected: is_selected(path),
+                path: path.clone(),
+                reason: reason.to_string(),
+            });
+        }
+
+        // The resolver searches the installation when the binary isn't where it's expected
+        if let Some(path) = selected
+            .as_ref()
+            .filter(|path| Some(*path) != adopted.as_ref() && Some(*path) != expected.as_ref())
+        {
+            candidates.push(Candidate {
+                source: CandidateSource::Managed,
+                path: path.clone(),
+                selected: true,
+                reason: "found by searching the installation".to_string(),
+            });
+        }
+
+        let shim = self.config.bin_dir.join("godot").canonicalize().ok();
+        let system = std::env::var_os("GODOT_BIN")
+            .map(PathBuf::from)
+            .into_iter()
+            .chain(which::which("godot").ok());
+        for path in system {
+            let canonical = path.canonicalize().ok();
+            if canonical.is_none()
+                || canonical == shim
+                || candidates
+                    .iter()
+                    .any(|c| c.path.canonicalize().ok() == canonical)
+            {
+                continue;
+            }
+            if GodotVersion::try_from_path(&path).is_ok_and(|v| v == *version) {
+                candidates.push(Candidate {
+                    source: CandidateSource::System,
+                    path,
+                    selected: false,
+                    reason: "not used: gdenv only runs binaries it installed or adopted"
+                        .to_string(),
+                });
+            }
+        }
+
+        candidates
+    }
+
     fn find_godot_executable(
         &self,
         install_path: &std::path::Path,
         version: &GodotVersion,
     ) -> Result<PathBuf> {
+        // Adopted installations point to a binary outside the installation
+        let adopted_file = install_path.join(ADOPTED_FILE);
+        if adopted_file.is_file() {
+            let executable = PathBuf::from(fs::read_to_string(&adopted_file)?.trim());
+            if !executable.is_file() {
+                return Err(anyhow::anyhow!(
+                    "Adopted Godot binary {} no longer exists",
+                    executable.display()
+                ));
+            }
+            return Ok(executable);
+        }
+
         // First try the expected path based on version info
-        let expected_path = version.get_executable_path();
+        let expected_path = version.get_executable_path()?;
         let expected_exe = install_path.join(&expected_path);
 
         if expected_exe.exists() && expected_exe.is_file() {
@@ -222,9 +672,8 @@ impl Installer {
 
         #[cfg(target_os = "macos")]
         {
-            // On macOS, the executable is inside Godot.app/Contents/MacOS/Godot
-            let godot_app_exe = install_path.join("Godot.app/Contents/MacOS/Godot");
-            if godot_app_exe.exists() {
+            // On macOS, the executable is inside the app bundle, which may be in a versioned folder
+            if let Some(godot_app_exe) = version.find_macos_executable(install_path) {
                 return Ok(godot_app_exe);
             }
         }
@@ -278,54 +727,320 @@ impl Installer {
         let target = fs::read_link(&self.config.active_symlink)?;
 
         // Parse version from the directory name
-        if let Some(dir_name) = target.file_name().and_then(|n| n.to_str()) {
-            if let Some(version_part) = dir_name.strip_prefix("godot-") {
-                let is_dotnet = version_part.ends_with("-dotnet");
-                let version_str = if is_dotnet {
-                    version_part.strip_suffix("-dotnet").unwrap()
-                } else {
-                    version_part
-                };
+        Ok(target
+            .file_name()
+            .and_then(|n| n.to_str())
+            .and_then(|dir_name| self.config.install_name_template().parse(dir_name, false)))
+    }
 
-                if let Ok(version) = GodotVersion::new(version_str, is_dotnet) {
-                    return Ok(Some(version));
-                }
+    pub fn list_installed(&self) -> Result<Vec<GodotVersion>> {
+        let entries = match fs::read_dir(&self.config.installations_dir) {
+            Ok(entries) => entries,
+            Err(e) if e.kind() == io::ErrorKind::NotFound => return Ok(Vec::new()),
+            Err(e) => return Err(e.into()),
+        };
+
+        // An installation may be removed by another process while we scan, so
+        // look up each entry lazily and let `collect_installed` skip vanished ones
+        let entries = entries.map(|entry| {
+            let entry = entry?;
+            let is_dir = entry.file_type()?.is_dir();
+            Ok(is_dir.then(|| entry.file_name().to_string_lossy().into_owned()))
+        });
+
+        let case_insensitive = paths::is_case_insensitive(&self.config.installations_dir);
+        collect_installed(
+            entries,
+            self.config.install_name_template(),
+            case_insensitive,
+        )
+    }
+
+    /// List the builds for other platforms installed with `install --platform`, as
+    /// (version, platform suffix) pairs
+    pub fn list_platform_builds(&self) -> Result<Vec<(GodotVersion, String)>> {
+        let entries = match fs::read_dir(&self.config.installations_dir) {
+            Ok(entries) => entries,
+            Err(e) if e.kind() == io::ErrorKind::NotFound => return Ok(Vec::new()),
+            Err(e) => return Err(e.into()),
+        };
+
+        let template = self.config.install_name_template();
+        let case_insensitive = paths::is_case_insensitive(&self.config.installations_dir);
+        let mut builds = Vec::new();
+        for entry in entries {
+            let entry = entry?;
+            let dir_name = entry.file_name().to_string_lossy().into_owned();
+            let Some((name, platform)) = dir_name.rsplit_once('@') else {
+                continue;
+            };
+            if let Some(version) = template.parse(name, case_insensitive) {
+                builds.push((version, platform.to_string()));
             }
         }
 
-        Ok(None)
+        builds.sort();
+        Ok(builds)
     }
+}
 
-    pub fn list_installed(&self) -> Result<Vec<GodotVersion>> {
-        let mut versions = Vec::new();
+/// List the executables of all running processes. This is best effort:
+/// processes whose executable can't be read are left out.
+pub fn running_executables() -> Vec<PathBuf> {
+    let mut system = System::new();
+    system.refresh_processes_specifics(
+        ProcessesToUpdate::All,
+        true,
+        ProcessRefreshKind::nothing().with_exe(UpdateKind::OnlyIfNotSet),
+    );
+
+    system
+        .processes()
+        .values()
+        .filter_map(|process| process.exe().map(Path::to_path_buf))
+        .collect()
+}
 
-        if !self.config.installations_dir.exists() {
-            return Ok(versions);
+/// Parse installed versions from directory names named by `template`, skipping entries that
+/// disappeared (NotFound) mid-scan. `None` entries are non-directories.
+/// On case-insensitive filesystems, "Godot-4.2.1" and "godot-4.2.1" are the same install.
+fn collect_installed(
+    entries: impl Iterator<Item = io::Result<Option<String>>>,
+    template: &InstallNameTemplate,
+    case_insensitive: bool,
+) -> Result<Vec<GodotVersion>> {
+    let mut versions = Vec::new();
+
+    for entry in entries {
+        let dir_name = match entry {
+            Ok(Some(dir_name)) => dir_name,
+            Ok(None) => continue,
+            Err(e) if e.kind() == io::ErrorKind::NotFound => continue,
+            Err(e) => return Err(e.into()),
+        };
+
+        if let Some(version) = template.parse(&dir_name, case_insensitive) {
+            versions.push(version);
         }
+    }
 
-        for entry in fs::read_dir(&self.config.installations_dir)? {
-            let entry = entry?;
-            if !entry.file_type()?.is_dir() {
-                continue;
-            }
+    versions.sort();
+    versions.dedup();
+    Ok(versions)
+}
 
-            if let Some(dir_name) = entry.file_name().to_str() {
-                if let Some(version_part) = dir_name.strip_prefix("godot-") {
-                    let is_dotnet = version_part.ends_with("-dotnet");
-                    let version_str = if is_dotnet {
-                        version_part.strip_suffix("-dotnet").unwrap()
-                    } else {
-                        version_part
-                    };
-
-                    if let Ok(version) = GodotVersion::new(version_str, is_dotnet) {
-                        versions.push(version);
-                    }
-                }
-            }
+fn size_warning(executable: &Path, size: u64, (min, max): (u64, u64)) -> Option<String> {
+    (size < min || size > max).then(|| {
+        format!(
+            "{} is {}, but Godot executables are {} to {}; the download may be corrupt \
+             (reinstall with --force)",
+            executable.display(),
+            ui::format_size(size),
+            ui::format_size(min),
+            ui::format_size(max)
+        )
+    })
+}
+
+/// Record in an installation where it came from, see [`Installer::source_for`]
+fn write_source(install_path: &Path, source: InstallSource) -> Result<()> {
+    let record = serde_json::to_string_pretty(&SourceRecord { source })?;
+    fs::write(install_path.join(SOURCE_FILE), record)?;
+    Ok(())
+}
+
+/// Get the SHA-256 of a file as lowercase hex
+pub fn file_sha256(path: &Path) -> Result<String> {
+    let mut hasher = Sha256::new();
+    io::copy(&mut fs::File::open(path)?, &mut hasher)?;
+    Ok(format!("{:x}", hasher.finalize()))
+}
+
+/// Write `contents` to the executable of an installation of `version`, as if gdenv had
+/// installed it there, and return the executable's path
+#[cfg(test)]
+pub fn fake_install(
+    config: &Config,
+    version: &GodotVersion,
+    contents: impl AsRef<[u8]>,
+) -> PathBuf {
+    let executable = config
+        .install_path(version)
+        .join(version.get_executable_path().unwrap());
+    fs::create_dir_all(executable.parent().unwrap()).unwrap();
+    fs::write(&executable, contents).unwrap();
+    executable
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_resolve_binary_candidates() {
+        let dir = tempfile::tempdir().unwrap();
+        let config = Config::with_data_dir(dir.path());
+        let installer = Installer::new(config.clone());
+        let version = GodotVersion::new("4.2.1", false).unwrap();
+
+        let managed = fake_install(&config, &version, "");
+
+        let candidates = installer.resolve_binary_candidates(&version);
+        assert_eq!(candidates.len(), 1);
+        assert_eq!(candidates[0].source, CandidateSource::Managed);
+        assert_eq!(candidates[0].path, managed);
+        assert!(candidates[0].selected);
+
+        // A custom build registered for the same version wins over the managed one
+        let custom = dir.path().join("custom-godot");
+        fs::write(&custom, "").unwrap();
+        fs::write(
+            config.install_path(&version).join(ADOPTED_FILE),
+            custom.to_string_lossy().as_bytes(),
+        )
+        .unwrap();
+
+        let candidates = installer.resolve_binary_candidates(&version);
+        let summary: Vec<_> = candidates
+            .iter()
+            .map(|c| (c.source, c.path.clone(), c.selected))
+            .collect();
+        assert_eq!(
+            summary,
+            [
+                (CandidateSource::Adopted, custom, true),
+                (CandidateSource::Managed, managed, false),
+            ]
+        );
+        assert!(candidates[1].reason.contains("shadowed"));
+    }
+
+    #[test]
+    fn test_size_warning() {
+        let executable = Path::new("godot");
+        let range = (100, 300);
+        assert_eq!(size_warning(executable, 100, range), None);
+        assert_eq!(size_warning(executable, 300, range), None);
+        assert!(size_warning(executable, 99, range)
+            .unwrap()
+            .contains("may be corrupt"));
+        assert!(size_warning(executable, 301, range).is_some());
+    }
+
+    #[test]
+    fn test_compute_total_checksum() {
+        let dir = tempfile::tempdir().unwrap();
+        let config = Config::with_data_dir(dir.path());
+        let installer = Installer::new(config.clone());
+
+        // Nothing installed hashes the empty string
+        assert_eq!(
+            installer.compute_total_checksum().unwrap(),
+            "e3b0c44298fc1c149afbf4c8996fb92427ae41e4649b934ca495991b7852b855"
+        );
+
+        let mut executables = Vec::new();
+        for (version, content) in [("4.3", "new"), ("4.2.1", "old")] {
+            let version = GodotVersion::new(version, false).unwrap();
+            let executable = fake_install(&config, &version, content);
+            executables.push(executable);
         }
 
-        versions.sort();
-        Ok(versions)
+        let expected = format!(
+            "godot-4.2.1:{:x}\ngodot-4.3.0:{:x}\n",
+            Sha256::digest(b"old"),
+            Sha256::digest(b"new")
+        );
+        let checksum = installer.compute_total_checksum().unwrap();
+        assert_eq!(
+            checksum,
+            format!("{:x}", Sha256::digest(expected.as_bytes()))
+        );
+
+        fs::write(&executables[0], "patched").unwrap();
+        assert_ne!(installer.compute_total_checksum().unwrap(), checksum);
+    }
+
+    #[test]
+    fn test_list_installed_skips_vanished_entries() {
+        let entries = vec![
+            Ok(Some("godot-4.2.1".to_string())),
+            Err(io::Error::from(io::ErrorKind::NotFound)),
+            Ok(None),
+            Ok(Some("godot-4.3.0-dotnet".to_string())),
+        ];
+
+        let versions =
+            collect_installed(entries.into_iter(), &InstallNameTemplate::default(), false).unwrap();
+        assert_eq!(
+            versions,
+            vec![
+                GodotVersion::new("4.2.1", false).unwrap(),
+                GodotVersion::new("4.3.0", true).unwrap(),
+            ]
+        );
+
+        let entries = vec![
+            Ok(Some("godot-4.2.1".to_string())),
+            Err(io::Error::from(io::ErrorKind::PermissionDenied)),
+        ];
+        assert!(
+            collect_installed(entries.into_iter(), &InstallNameTemplate::default(), false).is_err()
+        );
+    }
+
+    #[test]
+    fn test_list_installed_case_insensitive() {
+        let entries = || {
+            vec![
+                Ok(Some("Godot-4.2.1".to_string())),
+                Ok(Some("godot-4.2.1".to_string())),
+                Ok(Some("GODOT-4.3.0-DOTNET".to_string())),
+            ]
+            .into_iter()
+        };
+
+        // A case-insensitive filesystem treats both spellings as one install
+        assert_eq!(
+            collect_installed(entries(), &InstallNameTemplate::default(), true).unwrap(),
+            vec![
+                GodotVersion::new("4.2.1", false).unwrap(),
+                GodotVersion::new("4.3.0", true).unwrap(),
+            ]
+        );
+
+        // A case-sensitive one only knows the names gdenv creates
+        assert_eq!(
+            collect_installed(entries(), &InstallNameTemplate::default(), false).unwrap(),
+            vec![GodotVersion::new("4.2.1", false).unwrap()]
+        );
+    }
+
+    #[test]
+    fn test_list_platform_builds() {
+        let root = tempfile::tempdir().unwrap();
+        let config = Config::with_data_dir(root.path());
+        let installer = Installer::new(config.clone());
+
+        let version = GodotVersion::new("4.2.1", false).unwrap();
+        fs::create_dir_all(config.install_path(&version)).unwrap();
+        fs::create_dir_all(config.platform_install_path(&version, "linux.x86_64")).unwrap();
+        fs::create_dir_all(config.platform_install_path(&version, "win64.exe")).unwrap();
+
+        assert_eq!(installer.list_installed().unwrap(), vec![version.clone()]);
+        assert_eq!(
+            installer.list_platform_builds().unwrap(),
+            [
+                (version.clone(), "linux.x86_64".to_string()),
+                (version, "win64.exe".to_string()),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_list_installed_missing_dir() {
+        let root = tempfile::tempdir().unwrap();
+        let installer = Installer::new(Config::with_data_dir(&root.path().join("missing")));
+        assert!(installer.list_installed().unwrap().is_empty());
     }
 }
@@ -0,0 +1,267 @@
+use anyhow::{anyhow, Result};
+use semver::Version;
+use std::fmt;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use crate::dry_run::DryRunMode;
+use crate::godot::{GodotVersion, GodotVersionReq};
+
+/// Name of the file that pins a project's Godot version
+pub const LOCKFILE_NAME: &str = ".godot-version";
+
+/// What a `.godot-version` pins: one version, or a range of versions
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Pin {
+    Version(GodotVersion),
+    Range(GodotVersionReq),
+}
+
+impl Pin {
+    /// Whether `version` is the pinned version, or in the pinned range
+    pub fn allows(&self, version: &GodotVersion) -> bool {
+        match self {
+            Self::Version(pinned) => pinned.semantically_equivalent(version),
+            Self::Range(range) => range.req.matches(&version.version),
+        }
+    }
+
+    /// The pinned version, if one is pinned rather than a range
+    pub fn version(&self) -> Option<&GodotVersion> {
+        match self {
+            Self::Version(version) => Some(version),
+            Self::Range(_) => None,
+        }
+    }
+
+    /// Pick the version to use from `candidates`: the pinned version if it's among them,
+    /// or the newest one in the pinned range
+    pub fn resolve(&self, candidates: &[GodotVersion]) -> Option<GodotVersion> {
+        match self {
+            Self::Version(pinned) => candidates.iter().find(|v| *v == pinned).cloned(),
+            Self::Range(range) => range.resolve(candidates),
+        }
+    }
+
+    /// Whether the pin agrees with the version a project targets. `project.godot` only
+    /// records "major.minor", so any patch release matches, and a range matches if it
+    /// admits any patch release of that minor version. Ranges don't say whether they're
+    /// for .NET, so only a pinned version is checked against the project's.
+    pub fn matches_project(&self, project_version: &GodotVersion) -> bool {
+        let (major, minor) = (project_version.version.major, project_version.version.minor);
+        match self {
+            Self::Version(pinned) => {
+                pinned.version.major == major
+                    && pinned.version.minor == minor
+                    && pinned.is_dotnet == project_version.is_dotnet
+            }
+            Self::Range(range) => {
+                // The patches a range admits within one minor version are contiguous and
+                // start either at 0 or at one of its comparators' patch bounds
+                let bounds = range
+                    .req
+                    .comparators
+                    .iter()
+                    .filter(|c| c.major == major && c.minor == Some(minor))
+                    .filter_map(|c| c.patch)
+                    .flat_map(|patch| [patch, patch + 1]);
+                std::iter::once(0)
+                    .chain(bounds)
+                    .any(|patch| range.req.matches(&Version::new(major, minor, patch)))
+            }
+        }
+    }
+}
+
+impl fmt::Display for Pin {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::Version(version) => write!(f, "v{}", version),
+            Self::Range(range) => write!(f, "'{}'", range),
+        }
+    }
+}
+
+/// A `.godot-version` file pinning the Godot version of a project
+#[derive(Debug, Clone)]
+pub struct LockFile {
+    pub path: PathBuf,
+    pub pin: Pin,
+}
+
+impl LockFile {
+    /// Find the closest `.godot-version` in `start` or any of its parents
+    pub fn find(start: &Path) -> Option<PathBuf> {
+        start
+            .ancestors()
+            .map(|dir| dir.join(LOCKFILE_NAME))
+            .find(|path| path.is_file())
+    }
+
+    /// Read a lockfile; see [`read_pin`](Self::read_pin) for what it may pin
+    pub fn read(path: &Path) -> Result<Self> {
+        Ok(Self {
+            path: path.to_path_buf(),
+            pin: Self::read_pin(path)?,
+        })
+    }
+
+    /// Read what a lockfile pins: one version, or a semver range such as ">=4.2, <4.3".
+    /// The version may carry a `-dotnet` suffix (e.g. "4.2.1-dotnet") or be followed by a
+    /// `--dotnet` flag, as in "4.2.1 --dotnet", and an `@` qualifier
+    /// (e.g. "4.2.1-dotnet@linux.x86_64"), which is ignored.
+    pub fn read_pin(path: &Path) -> Result<Pin> {
+        let content = fs::read_to_string(path)?;
+        let pinned = content.trim();
+
+        if pinned.is_empty() {
+            return Err(anyhow!("{} is empty", path.display()));
+        }
+
+        let (pinned, dotnet_flag) = match pinned.strip_suffix("--dotnet") {
+            Some(version) => (version.trim_end(), true),
+            None => (pinned, false),
+        };
+
+        if GodotVersionReq::is_range(pinned) {
+            if dotnet_flag {
+                return Err(anyhow!(
+                    "{}: --dotnet only applies to a single version, not the range '{}'",
+                    path.display(),
+                    pinned
+                ));
+            }
+            Ok(Pin::Range(pinned.parse()?))
+        } else {
+            let version: GodotVersion = pinned.parse()?;
+            Ok(Pin::Version(GodotVersion {
+                is_dotnet: version.is_dotnet || dotnet_flag,
+                ..version
+            }))
+        }
+    }
+
+    /// Write `version` to a lockfile at `path`, or in a dry run say that it would
+    pub fn write(path: &Path, version: &GodotVersion, dry_run: DryRunMode) -> Result<Self> {
+        if !dry_run.skip(|| format!("pin Godot v{} in {}", version, path.display())) {
+            fs::write(path, format!("{}\n", version.canonical_string()))?;
+        }
+
+        Ok(Self {
+            path: path.to_path_buf(),
+            pin: Pin::Version(version.clone()),
+        })
+    }
+
+    /// Whether the pin agrees with the version a project targets; see
+    /// [`Pin::matches_project`]
+    pub fn matches_project(&self, project_version: &GodotVersion) -> bool {
+        self.pin.matches_project(project_version)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_find_and_read_from_subdirectory() {
+        let dir = tempfile::tempdir().unwrap();
+        let nested = dir.path().join("scenes/levels");
+        fs::create_dir_all(&nested).unwrap();
+        fs::write(dir.path().join(LOCKFILE_NAME), "4.2.1-dotnet\n").unwrap();
+
+        let path = LockFile::find(&nested).unwrap();
+        assert_eq!(path, dir.path().join(LOCKFILE_NAME));
+
+        let lockfile = LockFile::read(&path).unwrap();
+        assert_eq!(
+            lockfile.pin,
+            Pin::Version(GodotVersion::new("4.2.1", true).unwrap())
+        );
+        let pin = LockFile::read_pin(&path).unwrap();
+        assert!(pin.allows(&GodotVersion::new("4.2.1-stable", true).unwrap()));
+        assert!(!pin.allows(&GodotVersion::new("4.2.1", false).unwrap()));
+    }
+
+    #[test]
+    fn test_read_dotnet_flag() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join(LOCKFILE_NAME);
+
+        fs::write(&path, "4.2.1 --dotnet\n").unwrap();
+        assert_eq!(
+            LockFile::read(&path).unwrap().pin,
+            Pin::Version(GodotVersion::new("4.2.1", true).unwrap())
+        );
+
+        fs::write(&path, ">=4.2, <4.3 --dotnet\n").unwrap();
+        assert!(LockFile::read_pin(&path).is_err());
+    }
+
+    #[test]
+    fn test_read_range() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join(LOCKFILE_NAME);
+        fs::write(&path, ">=4.2, <4.3\n").unwrap();
+
+        let pin = LockFile::read_pin(&path).unwrap();
+        assert!(matches!(pin, Pin::Range(_)));
+        assert!(pin.allows(&GodotVersion::new("4.2.2", false).unwrap()));
+        assert!(!pin.allows(&GodotVersion::new("4.3", false).unwrap()));
+        assert_eq!(LockFile::read(&path).unwrap().pin, pin);
+        assert_eq!(pin.to_string(), "'>=4.2, <4.3'");
+
+        let installed = [
+            GodotVersion::new("4.2.1", false).unwrap(),
+            GodotVersion::new("4.2.2", false).unwrap(),
+            GodotVersion::new("4.3", false).unwrap(),
+        ];
+        assert_eq!(pin.resolve(&installed), Some(installed[1].clone()));
+        assert!(pin.matches_project(&GodotVersion::new("4.2", true).unwrap()));
+        assert!(!pin.matches_project(&GodotVersion::new("4.3", false).unwrap()));
+
+        // Only 4.2.2 is in range, so the patch bounds have to be tried, not just 4.2.0
+        fs::write(&path, ">4.2.1, <4.2.3\n").unwrap();
+        let narrow = LockFile::read_pin(&path).unwrap();
+        assert!(narrow.matches_project(&GodotVersion::new("4.2", false).unwrap()));
+        assert_eq!(narrow.resolve(&installed), Some(installed[1].clone()));
+    }
+
+    #[test]
+    fn test_matches_project_and_write() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join(LOCKFILE_NAME);
+
+        let lockfile = LockFile::write(
+            &path,
+            &GodotVersion::new("4.2.1", false).unwrap(),
+            DryRunMode::Off,
+        )
+        .unwrap();
+        assert_eq!(fs::read_to_string(&path).unwrap(), "4.2.1\n");
+        assert!(lockfile.matches_project(&GodotVersion::new("4.2", false).unwrap()));
+        assert!(!lockfile.matches_project(&GodotVersion::new("4.3", false).unwrap()));
+        assert!(!lockfile.matches_project(&GodotVersion::new("4.2", true).unwrap()));
+
+        LockFile::write(
+            &path,
+            &GodotVersion::new("4.3", true).unwrap(),
+            DryRunMode::Off,
+        )
+        .unwrap();
+        assert_eq!(
+            LockFile::read(&path).unwrap().pin,
+            Pin::Version(GodotVersion::new("4.3.0", true).unwrap())
+        );
+
+        // A dry run leaves the lockfile alone
+        let before = fs::read_to_string(&path).unwrap();
+        let version = GodotVersion::new("4.4", false).unwrap();
+        for dry_run in [DryRunMode::Report, DryRunMode::Json] {
+            let lockfile = LockFile::write(&path, &version, dry_run).unwrap();
+            assert_eq!(lockfile.pin, Pin::Version(version.clone()));
+            assert_eq!(fs::read_to_string(&path).unwrap(), before);
+        }
+    }
+}
@@ -1,11 +1,27 @@
+mod breaking_changes;
+mod catalog;
 mod cli;
 mod commands;
 mod config;
+mod connectivity;
+mod disk;
+mod download;
+mod dry_run;
 mod error;
 mod github;
 mod godot;
+mod hooks;
+mod install_name;
 mod installer;
+mod lockfile;
+mod manifest;
+mod paths;
+mod platform;
+mod project;
+mod remote;
+mod requirements;
 mod ui;
+mod verify;
 
 use anyhow::Result;
 use clap::Parser;
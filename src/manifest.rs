@@ -0,0 +1,42 @@
+use anyhow::{anyhow, Result};
+use serde::Deserialize;
+use std::fs;
+use std::path::Path;
+
+/// Name of the optional per-project gdenv settings file
+pub const MANIFEST_NAME: &str = "gdenv.toml";
+
+/// Per-project settings from `gdenv.toml`
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(default)]
+pub struct Manifest {
+    /// Range of Godot versions the project accepts (semver requirement, e.g. "~4.2")
+    pub godot: Option<String>,
+
+    pub lint: LintSettings,
+}
+
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(default)]
+pub struct LintSettings {
+    /// Lint rules to suppress (e.g. "prerelease-in-shipping")
+    pub ignore: Vec<String>,
+
+    /// Versions the team has found to be broken and should never be pinned
+    pub known_broken: Vec<String>,
+}
+
+impl Manifest {
+    /// Read `gdenv.toml` from a project directory, if there is one
+    pub fn load(project_dir: &Path) -> Result<Option<Self>> {
+        let path = project_dir.join(MANIFEST_NAME);
+        if !path.exists() {
+            return Ok(None);
+        }
+
+        let content = fs::read_to_string(&path)?;
+        toml::from_str(&content)
+            .map(Some)
+            .map_err(|e| anyhow!("Failed to parse {}: {}", path.display(), e))
+    }
+}
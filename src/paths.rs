@@ -0,0 +1,130 @@
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Component, Path, PathBuf};
+use std::sync::{Mutex, OnceLock};
+
+/// Whether the filesystem holding `dir` ignores case in file names (as on macOS
+/// and Windows by default). Detected once per directory and run by probing `dir`;
+/// if that's not possible, falls back to the platform default.
+pub fn is_case_insensitive(dir: &Path) -> bool {
+    static CASE_INSENSITIVE: OnceLock<Mutex<HashMap<PathBuf, bool>>> = OnceLock::new();
+    let mut cache = CASE_INSENSITIVE
+        .get_or_init(Default::default)
+        .lock()
+        .unwrap_or_else(|e| e.into_inner());
+    *cache.entry(dir.to_path_buf()).or_insert_with(|| {
+        probe_case_insensitive(dir).unwrap_or(cfg!(any(target_os = "macos", windows)))
+    })
+}
+
+/// Look `dir` (or its closest ancestor with letters in its name) up again with the
+/// case of its name swapped, without writing anything
+fn probe_case_insensitive(dir: &Path) -> Option<bool> {
+    let dir = dir.canonicalize().ok()?;
+    let (path, swapped) = dir.ancestors().find_map(|path| {
+        let name = path.file_name()?.to_str()?;
+        let swapped: String = name.chars().map(swap_case).collect();
+        (swapped != name).then(|| (path, path.with_file_name(swapped)))
+    })?;
+    Some(same_file(path, &swapped))
+}
+
+fn swap_case(c: char) -> char {
+    if c.is_ascii_uppercase() {
+        c.to_ascii_lowercase()
+    } else {
+        c.to_ascii_uppercase()
+    }
+}
+
+/// Whether both paths exist and are the same file
+#[cfg(unix)]
+fn same_file(a: &Path, b: &Path) -> bool {
+    use std::os::unix::fs::MetadataExt;
+    match (fs::metadata(a), fs::metadata(b)) {
+        (Ok(a), Ok(b)) => a.dev() == b.dev() && a.ino() == b.ino(),
+        _ => false,
+    }
+}
+
+/// Whether both paths exist and are the same file. Windows can't have two names that
+/// differ only in case in a case-insensitive directory, so existing is enough.
+#[cfg(not(unix))]
+fn same_file(a: &Path, b: &Path) -> bool {
+    a.exists() && b.exists()
+}
+
+/// Compare two paths, ignoring case if `case_insensitive` (see [`is_case_insensitive`])
+pub fn path_eq(a: &Path, b: &Path, case_insensitive: bool) -> bool {
+    a.components().count() == b.components().count() && path_starts_with(a, b, case_insensitive)
+}
+
+/// Whether `path` is `base` or inside it, ignoring case if `case_insensitive`
+pub fn path_starts_with(path: &Path, base: &Path, case_insensitive: bool) -> bool {
+    if !case_insensitive {
+        return path.starts_with(base);
+    }
+
+    let mut components = path.components();
+    base.components()
+        .all(|expected| components.next().is_some_and(|c| component_eq(c, expected)))
+}
+
+/// Compare two file names, ignoring case if `case_insensitive`
+pub fn name_eq(a: &str, b: &str, case_insensitive: bool) -> bool {
+    if case_insensitive {
+        a.to_lowercase() == b.to_lowercase()
+    } else {
+        a == b
+    }
+}
+
+fn component_eq(a: Component, b: Component) -> bool {
+    name_eq(
+        &a.as_os_str().to_string_lossy(),
+        &b.as_os_str().to_string_lossy(),
+        true,
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_path_eq() {
+        let lower = Path::new("/data/installations/godot-4.2.1");
+        let upper = Path::new("/data/installations/Godot-4.2.1");
+
+        assert!(path_eq(lower, upper, true));
+        assert!(!path_eq(lower, upper, false));
+        assert!(path_eq(lower, lower, false));
+        assert!(!path_eq(lower, Path::new("/data/installations"), true));
+
+        let exe = Path::new("/data/installations/GODOT-4.2.1/Godot_v4.2.1-stable_win64.exe");
+        assert!(path_starts_with(exe, lower, true));
+        assert!(!path_starts_with(exe, lower, false));
+    }
+
+    #[test]
+    fn test_probe_matches_platform() {
+        let dir = tempfile::tempdir().unwrap();
+        let probed = probe_case_insensitive(dir.path()).unwrap();
+        if cfg!(target_os = "linux") {
+            assert!(!probed);
+        }
+        // Probing leaves nothing behind
+        assert_eq!(fs::read_dir(dir.path()).unwrap().count(), 0);
+    }
+
+    #[cfg(target_os = "linux")]
+    #[test]
+    fn test_probe_distinguishes_case_variants() {
+        // On a case-sensitive filesystem, "Game" and "gAME" can both exist
+        let root = tempfile::tempdir().unwrap();
+        let dir = root.path().join("Game");
+        fs::create_dir(&dir).unwrap();
+        fs::create_dir(root.path().join("gAME")).unwrap();
+        assert_eq!(probe_case_insensitive(&dir), Some(false));
+    }
+}
@@ -0,0 +1,202 @@
+use std::path::Path;
+use std::process::Command;
+
+use crate::godot::MacosVersion;
+
+/// Directories the Vulkan loader is commonly installed to on Linux
+const LINUX_LIBRARY_DIRS: &[&str] = &[
+    "/usr/lib",
+    "/usr/lib64",
+    "/usr/lib/x86_64-linux-gnu",
+    "/usr/lib/aarch64-linux-gnu",
+    "/usr/lib/arm-linux-gnueabihf",
+    "/usr/local/lib",
+    "/lib",
+    "/lib64",
+];
+
+/// The operating system and architecture gdenv is running on
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PlatformTarget {
+    pub os: &'static str,
+    pub arch: &'static str,
+}
+
+/// The C library a Linux host links against
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Libc {
+    Gnu,
+    Musl,
+    Unknown,
+}
+
+impl PlatformTarget {
+    pub fn current() -> Self {
+        Self {
+            os: std::env::consts::OS,
+            arch: std::env::consts::ARCH,
+        }
+    }
+
+    /// Detect the host's C library. Always `Unknown` outside Linux.
+    pub fn libc(&self) -> Libc {
+        self.libc_with(detect_libc)
+    }
+
+    /// Like [`libc`](Self::libc), but with a custom detector for the Linux case
+    pub fn libc_with(&self, detect: impl FnOnce() -> Libc) -> Libc {
+        if self.os == "linux" {
+            detect()
+        } else {
+            Libc::Unknown
+        }
+    }
+
+    /// Get the host's macOS version as (major, minor), or `None` on other systems or
+    /// if `sw_vers` can't tell
+    pub fn macos_version(&self) -> Option<MacosVersion> {
+        if self.os != "macos" {
+            return None;
+        }
+        let output = Command::new("sw_vers")
+            .arg("-productVersion")
+            .output()
+            .ok()?;
+        parse_macos_version(&String::from_utf8_lossy(&output.stdout))
+    }
+
+    /// Whether a Vulkan loader is installed, which Godot 4's Forward+ and Mobile
+    /// renderers need. This only checks for the library, not for a capable GPU.
+    pub fn has_vulkan(&self) -> bool {
+        match self.os {
+            // Official macOS builds bundle MoltenVK
+            "macos" => true,
+            "windows" => {
+                let system_root =
+                    std::env::var("SystemRoot").unwrap_or_else(|_| "C:\\Windows".into());
+                Path::new(&system_root)
+                    .join("System32")
+                    .join("vulkan-1.dll")
+                    .exists()
+            }
+            _ => {
+                let in_ld_cache = Command::new("ldconfig")
+                    .arg("-p")
+                    .output()
+                    .is_ok_and(|output| {
+                        String::from_utf8_lossy(&output.stdout).contains("libvulkan.so.1")
+                    });
+
+                in_ld_cache
+                    || LINUX_LIBRARY_DIRS
+                        .iter()
+                        .any(|dir| Path::new(dir).join("libvulkan.so.1").exists())
+            }
+        }
+    }
+}
+
+impl Libc {
+    /// A warning to show before installing an official build that may not run with this libc
+    pub fn official_build_warning(&self) -> Option<&'static str> {
+        match self {
+            // Official Linux builds are linked against glibc and won't load on musl
+            Libc::Musl => Some(
+                "This system uses musl libc, but official Godot builds require glibc and may fail \
+                 to start. Consider installing the gcompat package (e.g. 'apk add gcompat'), \
+                 using a glibc-based container image, or your distribution's Godot package.",
+            ),
+            Libc::Gnu | Libc::Unknown => None,
+        }
+    }
+}
+
+fn detect_libc() -> Libc {
+    if cfg!(target_env = "musl") {
+        return Libc::Musl;
+    }
+
+    // musl's ldd prints its banner to stderr and exits non-zero, so check both streams
+    match Command::new("ldd").arg("--version").output() {
+        Ok(output) => parse_ldd_output(&format!(
+            "{}{}",
+            String::from_utf8_lossy(&output.stdout),
+            String::from_utf8_lossy(&output.stderr)
+        )),
+        Err(_) => Libc::Unknown,
+    }
+}
+
+/// Parse `sw_vers -productVersion` output like "14.4.1" or "10.15"
+fn parse_macos_version(output: &str) -> Option<MacosVersion> {
+    let mut parts = output.trim().split('.');
+    let major = parts.next()?.parse().ok()?;
+    let minor = parts.next().map_or(Some(0), |minor| minor.parse().ok())?;
+    Some((major, minor))
+}
+
+fn parse_ldd_output(output: &str) -> Libc {
+    let output = output.to_lowercase();
+    if output.contains("musl") {
+        Libc::Musl
+    } else if output.contains("glibc") || output.contains("gnu libc") {
+        Libc::Gnu
+    } else {
+        Libc::Unknown
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_macos_version() {
+        assert_eq!(parse_macos_version("14.4.1\n"), Some((14, 4)));
+        assert_eq!(parse_macos_version("10.15"), Some((10, 15)));
+        assert_eq!(parse_macos_version("15"), Some((15, 0)));
+        assert_eq!(parse_macos_version(""), None);
+        assert_eq!(parse_macos_version("Sonoma"), None);
+    }
+
+    #[test]
+    fn test_parse_ldd_output() {
+        assert_eq!(
+            parse_ldd_output("ldd (Ubuntu GLIBC 2.35-0ubuntu3.6) 2.35\nCopyright (C) 2022"),
+            Libc::Gnu
+        );
+        assert_eq!(
+            parse_ldd_output("musl libc (x86_64)\nVersion 1.2.4\nDynamic Program Loader"),
+            Libc::Musl
+        );
+        assert_eq!(parse_ldd_output(""), Libc::Unknown);
+    }
+
+    #[test]
+    fn test_musl_host_warns() {
+        let linux = PlatformTarget {
+            os: "linux",
+            arch: "x86_64",
+        };
+        assert_eq!(linux.libc_with(|| Libc::Musl), Libc::Musl);
+
+        let warning = linux
+            .libc_with(|| Libc::Musl)
+            .official_build_warning()
+            .unwrap();
+        assert!(warning.contains("musl"));
+        assert!(warning.contains("gcompat"));
+
+        assert!(linux
+            .libc_with(|| Libc::Gnu)
+            .official_build_warning()
+            .is_none());
+
+        // Only Linux hosts are checked
+        let macos = PlatformTarget {
+            os: "macos",
+            arch: "aarch64",
+        };
+        assert_eq!(macos.libc_with(|| Libc::Musl), Libc::Unknown);
+    }
+}
@@ -0,0 +1,329 @@
+use anyhow::Result;
+use ignore::WalkBuilder;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use crate::lockfile::{LockFile, Pin};
+
+/// Name of the file that marks the root of a Godot project
+pub const PROJECT_FILE: &str = "project.godot";
+
+/// Directories that never contain project sources
+const SKIPPED_DIRS: &[&str] = &[".godot", ".import", ".git", ".mono"];
+
+/// Read the `config/features` array from a `project.godot` file
+/// Example: `config/features=PackedStringArray("4.2", "Forward Plus")` -> ["4.2", "Forward Plus"]
+pub fn read_features(project_file: &Path) -> Result<Vec<String>> {
+    let content = fs::read_to_string(project_file)?;
+
+    for line in content.lines() {
+        if let Some(value) = line.trim().strip_prefix("config/features=") {
+            // Godot 4 wraps the list in PackedStringArray(...), Godot 3 in PoolStringArray(...)
+            let inner = value
+                .split_once('(')
+                .and_then(|(_, rest)| rest.rsplit_once(')'))
+                .map(|(inner, _)| inner)
+                .unwrap_or(value);
+
+            return Ok(inner
+                .split(',')
+                .map(|f| f.trim().trim_matches('"').to_string())
+                .filter(|f| !f.is_empty())
+                .collect());
+        }
+    }
+
+    Ok(Vec::new())
+}
+
+/// What the closest `.godot-version` in `start` or any of its parents pins, or `None`
+/// if there is none. The first file found wins, so one that doesn't parse is an error
+/// rather than skipped.
+pub fn discover_project_version(start: &Path) -> Result<Option<Pin>> {
+    LockFile::find(start)
+        .map(|lockfile_path| LockFile::read_pin(&lockfile_path))
+        .transpose()
+}
+
+/// Where the godot-jolt addon installs itself in a project
+pub const JOLT_ADDON_DIR: &str = "addons/godot-jolt";
+
+/// Whether a project has the godot-jolt addon, which needs Godot 4.2 or newer
+pub fn uses_jolt_addon(project_dir: &Path) -> bool {
+    project_dir.join(JOLT_ADDON_DIR).is_dir()
+}
+
+/// Name of the file Godot keeps export presets in
+pub const EXPORT_PRESETS_FILE: &str = "export_presets.cfg";
+
+/// An export preset from `export_presets.cfg`
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ExportPreset {
+    pub name: String,
+    pub platform: String,
+    /// Where the editor exports to, relative to the project (may be empty)
+    pub export_path: String,
+}
+
+/// Read the export presets of a project, in the order they're defined
+pub fn export_presets(project_dir: &Path) -> Result<Vec<ExportPreset>> {
+    let presets_file = project_dir.join(EXPORT_PRESETS_FILE);
+    if !presets_file.exists() {
+        return Ok(Vec::new());
+    }
+
+    let content = fs::read_to_string(presets_file)?;
+    let mut presets: Vec<ExportPreset> = Vec::new();
+    let mut in_preset = false;
+
+    for line in content.lines() {
+        let line = line.trim();
+        if line.starts_with('[') {
+            // "[preset.0]" starts a preset, "[preset.0.options]" holds its platform options
+            in_preset = line.starts_with("[preset.") && !line.contains(".options]");
+            if in_preset {
+                presets.push(ExportPreset::default());
+            }
+            continue;
+        }
+
+        if !in_preset {
+            continue;
+        }
+        let (Some(preset), Some((key, value))) = (presets.last_mut(), line.split_once('=')) else {
+            continue;
+        };
+
+        let value = value.trim().trim_matches('"').to_string();
+        match key.trim() {
+            "name" => preset.name = value,
+            "platform" => preset.platform = value,
+            "export_path" => preset.export_path = value,
+            _ => {}
+        }
+    }
+
+    Ok(presets)
+}
+
+/// Count the export presets defined in a project's `export_presets.cfg`
+pub fn count_export_presets(project_dir: &Path) -> Result<usize> {
+    Ok(export_presets(project_dir)?.len())
+}
+
+/// Name of the marker file that makes Godot ignore a directory
+pub const GDIGNORE_FILE: &str = ".gdignore";
+
+/// Walks the source files of a Godot project, skipping engine caches, VCS data,
+/// gitignored paths and directories Godot itself ignores
+pub struct ProjectWalker {
+    root: PathBuf,
+}
+
+impl ProjectWalker {
+    pub fn new(root: &Path) -> Self {
+        Self {
+            root: root.to_path_buf(),
+        }
+    }
+
+    /// Collect every file in the project
+    pub fn files(&self) -> Result<Vec<PathBuf>> {
+        let mut files = Vec::new();
+        for entry in walk(&self.root) {
+            let entry = entry?;
+            if entry.file_type().is_some_and(|t| t.is_file()) {
+                files.push(entry.into_path());
+            }
+        }
+
+        files.sort();
+        Ok(files)
+    }
+
+    /// Collect the project files with the given extension (without the dot)
+    pub fn files_with_extension(&self, extension: &str) -> Result<Vec<PathBuf>> {
+        Ok(self
+            .files()?
+            .into_iter()
+            .filter(|path| path.extension().is_some_and(|ext| ext == extension))
+            .collect())
+    }
+}
+
+/// Walk a directory tree, skipping engine caches, VCS data, gitignored paths
+/// and directories Godot itself ignores
+fn walk(root: &Path) -> ignore::Walk {
+    WalkBuilder::new(root)
+        .hidden(false)
+        .parents(false)
+        // Projects are often scanned outside a git checkout (e.g. extracted archives)
+        .require_git(false)
+        .filter_entry(|entry| {
+            let is_dir = entry.file_type().is_some_and(|t| t.is_dir());
+            !is_dir
+                || (!SKIPPED_DIRS
+                    .iter()
+                    .any(|skipped| entry.file_name() == *skipped)
+                    && !entry.path().join(GDIGNORE_FILE).exists())
+        })
+        .build()
+}
+
+/// Find every Godot project (a directory with a `project.godot`) under `root`
+pub fn scan(root: &Path) -> Result<Vec<PathBuf>> {
+    let mut projects = Vec::new();
+    for entry in walk(root) {
+        let entry = entry?;
+        if entry.file_name() == PROJECT_FILE && entry.file_type().is_some_and(|t| t.is_file()) {
+            projects.extend(entry.path().parent().map(Path::to_path_buf));
+        }
+    }
+
+    projects.sort();
+    Ok(projects)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{godot::GodotVersion, lockfile::LOCKFILE_NAME};
+
+    #[test]
+    fn test_discover_project_version() {
+        let dir = tempfile::tempdir().unwrap();
+        let game = dir.path().join("game");
+        let nested = game.join("scenes/levels");
+        fs::create_dir_all(&nested).unwrap();
+        assert_eq!(discover_project_version(&nested).unwrap(), None);
+
+        let pinned = |version: &str, is_dotnet| {
+            Some(Pin::Version(GodotVersion::new(version, is_dotnet).unwrap()))
+        };
+        fs::write(dir.path().join(LOCKFILE_NAME), "4.1.4\n").unwrap();
+        assert_eq!(
+            discover_project_version(&nested).unwrap(),
+            pinned("4.1.4", false)
+        );
+
+        // The closest file wins over one further up
+        fs::write(game.join(LOCKFILE_NAME), "4.2.1-dotnet\n").unwrap();
+        assert_eq!(
+            discover_project_version(&nested).unwrap(),
+            pinned("4.2.1", true)
+        );
+        assert_eq!(
+            discover_project_version(dir.path()).unwrap(),
+            pinned("4.1.4", false)
+        );
+
+        fs::write(game.join(LOCKFILE_NAME), ">=4.2, <4.3\n").unwrap();
+        assert!(matches!(
+            discover_project_version(&nested).unwrap(),
+            Some(Pin::Range(_))
+        ));
+
+        // The closest file wins even when it doesn't parse, so that's an error
+        fs::write(game.join(LOCKFILE_NAME), "four\n").unwrap();
+        assert!(discover_project_version(&nested).is_err());
+    }
+
+    #[test]
+    fn test_read_features() {
+        let dir = tempfile::tempdir().unwrap();
+        let project_file = dir.path().join(PROJECT_FILE);
+        fs::write(
+            &project_file,
+            "config_version=5\n\n[application]\n\nconfig/name=\"Demo\"\nconfig/features=PackedStringArray(\"4.2\", \"C#\", \"Forward Plus\")\n",
+        )
+        .unwrap();
+
+        assert_eq!(
+            read_features(&project_file).unwrap(),
+            vec!["4.2", "C#", "Forward Plus"]
+        );
+    }
+
+    #[test]
+    fn test_walker_skips_caches() {
+        let dir = tempfile::tempdir().unwrap();
+        fs::create_dir_all(dir.path().join("scripts")).unwrap();
+        fs::create_dir_all(dir.path().join(".godot/editor")).unwrap();
+        fs::write(dir.path().join("scripts/player.gd"), "").unwrap();
+        fs::write(dir.path().join("main.gd"), "").unwrap();
+        fs::write(dir.path().join(".godot/editor/cache.gd"), "").unwrap();
+        fs::write(
+            dir.path().join("export_presets.cfg"),
+            "[preset.0]\nname=\"Linux\"\n[preset.0.options]\n[preset.1]\nname=\"Web\"\n",
+        )
+        .unwrap();
+
+        let walker = ProjectWalker::new(dir.path());
+        assert_eq!(walker.files_with_extension("gd").unwrap().len(), 2);
+        assert_eq!(count_export_presets(dir.path()).unwrap(), 2);
+    }
+
+    #[test]
+    fn test_walker_respects_ignores() {
+        let dir = tempfile::tempdir().unwrap();
+        for sub in [".godot/imported", "build", "assets/raw", "scripts"] {
+            fs::create_dir_all(dir.path().join(sub)).unwrap();
+        }
+        fs::write(dir.path().join(".gitignore"), "/build/\n").unwrap();
+        fs::write(dir.path().join("assets/raw").join(GDIGNORE_FILE), "").unwrap();
+        fs::write(dir.path().join(".godot/imported/icon.gd"), "").unwrap();
+        fs::write(dir.path().join("build/generated.gd"), "").unwrap();
+        fs::write(dir.path().join("assets/raw/tool.gd"), "").unwrap();
+        fs::write(dir.path().join("scripts/player.gd"), "").unwrap();
+
+        assert_eq!(
+            ProjectWalker::new(dir.path())
+                .files_with_extension("gd")
+                .unwrap(),
+            [dir.path().join("scripts/player.gd")]
+        );
+    }
+
+    #[test]
+    fn test_scan() {
+        let dir = tempfile::tempdir().unwrap();
+        for project in ["games/a", "games/b", "tools/.godot/c", "build/d"] {
+            fs::create_dir_all(dir.path().join(project)).unwrap();
+            fs::write(dir.path().join(project).join(PROJECT_FILE), "").unwrap();
+        }
+        fs::write(dir.path().join(".gitignore"), "build/\n").unwrap();
+
+        assert_eq!(
+            scan(dir.path()).unwrap(),
+            [dir.path().join("games/a"), dir.path().join("games/b")]
+        );
+    }
+
+    #[test]
+    fn test_export_presets() {
+        let dir = tempfile::tempdir().unwrap();
+        fs::write(
+            dir.path().join(EXPORT_PRESETS_FILE),
+            "[preset.0]\n\nname=\"Android\"\nplatform=\"Android\"\nexport_path=\"build/game.apk\"\n\n\
+             [preset.0.options]\n\nname=\"not a preset name\"\n\n\
+             [preset.1]\n\nname=\"Windows Desktop\"\nplatform=\"Windows Desktop\"\nexport_path=\"\"\n",
+        )
+        .unwrap();
+
+        assert_eq!(
+            export_presets(dir.path()).unwrap(),
+            [
+                ExportPreset {
+                    name: "Android".to_string(),
+                    platform: "Android".to_string(),
+                    export_path: "build/game.apk".to_string(),
+                },
+                ExportPreset {
+                    name: "Windows Desktop".to_string(),
+                    platform: "Windows Desktop".to_string(),
+                    export_path: String::new(),
+                },
+            ]
+        );
+    }
+}
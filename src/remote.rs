@@ -0,0 +1,402 @@
+use anyhow::{anyhow, Result};
+use reqwest::header::{HeaderMap, CONTENT_LENGTH, LINK, RETRY_AFTER};
+use reqwest::{Client, ClientBuilder, RequestBuilder, Response, StatusCode};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use crate::{github::GitHubRelease, godot::GodotVersion, ui};
+
+/// The release asset listing the SHA-512 digest of every other asset
+const SHA512_SUMS_ASSET: &str = "SHA512-SUMS.txt";
+
+/// Sent with every request; GitHub rejects requests without a User-Agent
+pub const USER_AGENT: &str = concat!(
+    "gdenv/",
+    env!("CARGO_PKG_VERSION"),
+    " (+https://github.com/bytemeadow/gdenv)"
+);
+
+/// A GitHub token to authenticate API requests with, which raises the rate limit from
+/// 60 to 5000 requests an hour
+pub const GITHUB_TOKEN_VAR: &str = "GDENV_GITHUB_TOKEN";
+
+/// The longest gdenv waits before retrying, whatever the server asks for
+pub const MAX_RETRY_WAIT: Duration = Duration::from_secs(60);
+
+/// How to retry requests that were rate limited
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Backoff {
+    pub retries: u32,
+    pub max_wait: Duration,
+}
+
+impl Backoff {
+    pub fn new(retries: u32) -> Self {
+        Self {
+            retries,
+            max_wait: MAX_RETRY_WAIT,
+        }
+    }
+
+    /// How long to wait before retry number `attempt` (counting from 0): what the
+    /// server asked for, or else 1s, 2s, 4s, ...
+    fn wait(&self, attempt: u32, retry_after: Option<Duration>) -> Duration {
+        retry_after
+            .unwrap_or_else(|| Duration::from_secs(1 << attempt.min(6)))
+            .min(self.max_wait)
+    }
+}
+
+/// A client builder with gdenv's User-Agent
+pub fn client_builder() -> ClientBuilder {
+    Client::builder().user_agent(USER_AGENT)
+}
+
+/// Send a request, waiting and retrying while the server answers 429 Too Many Requests
+/// (or, like GitHub, 403 Forbidden with no requests left in the rate limit).
+/// After `backoff.retries` retries the last response is returned as is.
+pub async fn send_with_backoff(request: RequestBuilder, backoff: Backoff) -> Result<Response> {
+    let mut attempt = 0;
+    loop {
+        let response = request
+            .try_clone()
+            .ok_or_else(|| anyhow!("Request can't be retried"))?
+            .send()
+            .await?;
+
+        if !is_rate_limited(response.status(), response.headers()) || attempt >= backoff.retries {
+            return Ok(response);
+        }
+
+        let wait = backoff.wait(attempt, retry_after(response.headers(), SystemTime::now()));
+        ui::warning(&format!(
+            "Rate limited by {}, retrying in {}s",
+            response.url().host_str().unwrap_or("the server"),
+            wait.as_secs()
+        ));
+        tokio::time::sleep(wait).await;
+        attempt += 1;
+    }
+}
+
+/// The URL of the next page of a paginated GitHub API response, from the `rel="next"`
+/// entry of its `Link` header
+pub fn next_page_url(headers: &HeaderMap) -> Option<String> {
+    headers
+        .get(LINK)?
+        .to_str()
+        .ok()?
+        .split(',')
+        .find_map(|link| {
+            let (url, params) = link.split_once(';')?;
+            params
+                .split(';')
+                .any(|param| param.trim() == r#"rel="next""#)
+                .then(|| {
+                    url.trim()
+                        .trim_start_matches('<')
+                        .trim_end_matches('>')
+                        .to_string()
+                })
+        })
+}
+
+/// The error for an unsuccessful GitHub API response. Running out of the rate limit
+/// suggests setting a token, since anonymous requests only get 60 an hour.
+pub fn github_api_error(response: &Response) -> anyhow::Error {
+    let status = response.status();
+    if rate_limit_exhausted(response.headers())
+        && [StatusCode::FORBIDDEN, StatusCode::TOO_MANY_REQUESTS].contains(&status)
+    {
+        anyhow!(
+            "GitHub API rate limit exceeded ({}); set {} to a GitHub token to raise it",
+            status,
+            GITHUB_TOKEN_VAR
+        )
+    } else {
+        anyhow!("GitHub API request failed: {}", status)
+    }
+}
+
+/// Whether GitHub's `x-ratelimit-remaining` header says no requests are left
+fn rate_limit_exhausted(headers: &HeaderMap) -> bool {
+    headers
+        .get("x-ratelimit-remaining")
+        .is_some_and(|remaining| remaining == "0")
+}
+
+/// Whether a response means "slow down": 429, or the 403 GitHub answers with once the
+/// rate limit is used up
+fn is_rate_limited(status: StatusCode, headers: &HeaderMap) -> bool {
+    status == StatusCode::TOO_MANY_REQUESTS
+        || (status == StatusCode::FORBIDDEN && rate_limit_exhausted(headers))
+}
+
+/// The delay the server asks for: a `Retry-After` header (only the delay-seconds form is
+/// supported), or else the time until GitHub's `x-ratelimit-reset` (Unix seconds)
+fn retry_after(headers: &HeaderMap, now: SystemTime) -> Option<Duration> {
+    let header = |name| headers.get(name)?.to_str().ok()?.trim().parse::<u64>().ok();
+
+    if let Some(seconds) = header(RETRY_AFTER.as_str()) {
+        return Some(Duration::from_secs(seconds));
+    }
+    let reset = UNIX_EPOCH + Duration::from_secs(header("x-ratelimit-reset")?);
+    Some(reset.duration_since(now).unwrap_or_default())
+}
+
+/// Get the download size of a version's archive for the current platform with a HEAD request
+/// Returns `None` if the server doesn't report a `Content-Length`.
+pub async fn asset_size(client: &Client, version: &GodotVersion) -> Result<Option<u64>> {
+    url_size(client, &version.download_url()?).await
+}
+
+/// Get the size of any download with a HEAD request, or `None` without a `Content-Length`
+pub async fn url_size(client: &Client, url: &str) -> Result<Option<u64>> {
+    let response = client.head(url).send().await?;
+
+    if !response.status().is_success() {
+        return Err(anyhow!("HEAD {} failed: {}", url, response.status()));
+    }
+
+    Ok(content_length(response.headers()))
+}
+
+/// Get the SHA-512 digest of one of a release's assets from its checksum file, or `None`
+/// if the release has no checksum file or it doesn't list the asset
+pub async fn release_sha512(
+    client: &Client,
+    release: &GitHubRelease,
+    asset_name: &str,
+) -> Result<Option<String>> {
+    let Some(sums) = release.assets.iter().find(|a| a.name == SHA512_SUMS_ASSET) else {
+        return Ok(None);
+    };
+
+    let response = client.get(&sums.browser_download_url).send().await?;
+    if !response.status().is_success() {
+        return Err(anyhow!(
+            "GET {} failed: {}",
+            sums.browser_download_url,
+            response.status()
+        ));
+    }
+
+    Ok(find_checksum(&response.text().await?, asset_name))
+}
+
+/// Get the URL of the checksum file published next to a release archive
+pub fn sha512_sums_url(archive_url: &str) -> String {
+    match archive_url.rsplit_once('/') {
+        Some((dir, _)) => format!("{}/{}", dir, SHA512_SUMS_ASSET),
+        None => SHA512_SUMS_ASSET.to_string(),
+    }
+}
+
+/// Download the checksum file next to a release archive, or `None` if there is none
+pub async fn fetch_sha512_sums(client: &Client, archive_url: &str) -> Result<Option<String>> {
+    let url = sha512_sums_url(archive_url);
+    let response = client.get(&url).send().await?;
+
+    if response.status() == StatusCode::NOT_FOUND {
+        return Ok(None);
+    }
+    if !response.status().is_success() {
+        return Err(anyhow!("GET {} failed: {}", url, response.status()));
+    }
+
+    Ok(Some(response.text().await?))
+}
+
+/// Whether a HEAD request for `url` succeeds
+pub async fn url_exists(client: &Client, url: &str) -> bool {
+    client
+        .head(url)
+        .send()
+        .await
+        .is_ok_and(|response| response.status().is_success())
+}
+
+/// Find a file's digest in `sha512sum` output ("<digest>  <file name>" per line)
+pub fn find_checksum(sums: &str, file_name: &str) -> Option<String> {
+    sums.lines().find_map(|line| {
+        let (digest, name) = line.trim().split_once(char::is_whitespace)?;
+        // sha512sum marks binary mode with a '*' before the name
+        (name.trim().trim_start_matches('*') == file_name).then(|| digest.to_lowercase())
+    })
+}
+
+fn content_length(headers: &HeaderMap) -> Option<u64> {
+    headers
+        .get(CONTENT_LENGTH)?
+        .to_str()
+        .ok()?
+        .trim()
+        .parse()
+        .ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use reqwest::header::HeaderValue;
+
+    #[test]
+    fn test_content_length() {
+        let mut headers = HeaderMap::new();
+        assert_eq!(content_length(&headers), None);
+
+        headers.insert(CONTENT_LENGTH, HeaderValue::from_static("73400320"));
+        assert_eq!(content_length(&headers), Some(73_400_320));
+
+        headers.insert(CONTENT_LENGTH, HeaderValue::from_static("unknown"));
+        assert_eq!(content_length(&headers), None);
+    }
+
+    #[test]
+    fn test_next_page_url() {
+        let mut headers = HeaderMap::new();
+        assert_eq!(next_page_url(&headers), None);
+
+        headers.insert(
+            LINK,
+            HeaderValue::from_static(
+                "<https://api.github.com/repositories/1/releases?per_page=100&page=2>; rel=\"next\", \
+                 <https://api.github.com/repositories/1/releases?per_page=100&page=4>; rel=\"last\"",
+            ),
+        );
+        assert_eq!(
+            next_page_url(&headers).as_deref(),
+            Some("https://api.github.com/repositories/1/releases?per_page=100&page=2")
+        );
+
+        headers.insert(
+            LINK,
+            HeaderValue::from_static(
+                "<https://api.github.com/repositories/1/releases?page=3>; rel=\"prev\"",
+            ),
+        );
+        assert_eq!(next_page_url(&headers), None);
+    }
+
+    #[test]
+    fn test_find_checksum() {
+        let sums = "abc123  Godot_v4.2.1-stable_linux.x86_64.zip\n\
+                    DEF456 *Godot_v4.2.1-stable_win64.exe.zip\n";
+        assert_eq!(
+            find_checksum(sums, "Godot_v4.2.1-stable_linux.x86_64.zip").as_deref(),
+            Some("abc123")
+        );
+        assert_eq!(
+            find_checksum(sums, "Godot_v4.2.1-stable_win64.exe.zip").as_deref(),
+            Some("def456")
+        );
+        assert_eq!(
+            find_checksum(sums, "Godot_v4.2.1-stable_macos.universal.zip"),
+            None
+        );
+    }
+
+    #[test]
+    fn test_sha512_sums_url() {
+        assert_eq!(
+            sha512_sums_url(
+                "https://github.com/godotengine/godot-builds/releases/download/4.2.1-stable/Godot_v4.2.1-stable_linux.x86_64.zip"
+            ),
+            "https://github.com/godotengine/godot-builds/releases/download/4.2.1-stable/SHA512-SUMS.txt"
+        );
+        assert_eq!(sha512_sums_url("archive.zip"), "SHA512-SUMS.txt");
+    }
+
+    #[test]
+    fn test_backoff_wait() {
+        let backoff = Backoff::new(3);
+        assert_eq!(backoff.wait(0, None), Duration::from_secs(1));
+        assert_eq!(backoff.wait(2, None), Duration::from_secs(4));
+        assert_eq!(
+            backoff.wait(0, Some(Duration::from_secs(5))),
+            Duration::from_secs(5)
+        );
+        assert_eq!(
+            backoff.wait(0, Some(Duration::from_secs(3600))),
+            MAX_RETRY_WAIT
+        );
+    }
+
+    #[test]
+    fn test_rate_limit_headers() {
+        let headers = |pairs: &[(&'static str, &str)]| {
+            let mut headers = HeaderMap::new();
+            for (name, value) in pairs {
+                headers.insert(*name, value.parse().unwrap());
+            }
+            headers
+        };
+        let exhausted = headers(&[
+            ("x-ratelimit-remaining", "0"),
+            ("x-ratelimit-reset", "1030"),
+        ]);
+
+        assert!(is_rate_limited(
+            StatusCode::TOO_MANY_REQUESTS,
+            &HeaderMap::new()
+        ));
+        assert!(is_rate_limited(StatusCode::FORBIDDEN, &exhausted));
+        assert!(!is_rate_limited(StatusCode::FORBIDDEN, &HeaderMap::new()));
+
+        let now = UNIX_EPOCH + Duration::from_secs(1000);
+        assert_eq!(retry_after(&exhausted, now), Some(Duration::from_secs(30)));
+        assert_eq!(
+            retry_after(&exhausted, now + Duration::from_secs(60)),
+            Some(Duration::ZERO)
+        );
+        // Retry-After wins over the reset time
+        let both = headers(&[("retry-after", "5"), ("x-ratelimit-reset", "1030")]);
+        assert_eq!(retry_after(&both, now), Some(Duration::from_secs(5)));
+        assert_eq!(retry_after(&HeaderMap::new(), now), None);
+    }
+
+    #[tokio::test]
+    async fn test_retry_after_429() {
+        use std::sync::{Arc, Mutex};
+        use tokio::io::{AsyncReadExt, AsyncWriteExt};
+        use tokio::net::TcpListener;
+
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = format!("http://{}/releases", listener.local_addr().unwrap());
+        let requests = Arc::new(Mutex::new(Vec::new()));
+
+        let seen = requests.clone();
+        tokio::spawn(async move {
+            let responses = [
+                "HTTP/1.1 429 Too Many Requests\r\nretry-after: 3600\r\ncontent-length: 0\r\n\r\n",
+                "HTTP/1.1 200 OK\r\ncontent-length: 2\r\n\r\n[]",
+            ];
+            for response in responses {
+                let (mut socket, _) = listener.accept().await.unwrap();
+                let mut buf = [0; 4096];
+                let n = socket.read(&mut buf).await.unwrap();
+                seen.lock()
+                    .unwrap()
+                    .push(String::from_utf8_lossy(&buf[..n]).to_lowercase());
+                socket.write_all(response.as_bytes()).await.unwrap();
+            }
+        });
+
+        // Cap the hour the server asks for so the test stays fast
+        let backoff = Backoff {
+            retries: 2,
+            max_wait: Duration::from_millis(50),
+        };
+        let client = client_builder().build().unwrap();
+        let start = std::time::Instant::now();
+        let response = send_with_backoff(client.get(&url), backoff).await.unwrap();
+
+        assert_eq!(response.status(), StatusCode::OK);
+        assert!(start.elapsed() >= Duration::from_millis(50));
+        assert!(start.elapsed() < Duration::from_secs(10));
+
+        let requests = requests.lock().unwrap();
+        assert_eq!(requests.len(), 2);
+        let user_agent = format!("user-agent: {}", USER_AGENT.to_lowercase());
+        assert!(requests.iter().all(|r| r.contains(&user_agent)));
+    }
+}
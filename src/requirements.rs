@@ -0,0 +1,215 @@
+use anyhow::{anyhow, Result};
+use semver::VersionReq;
+use std::str::FromStr;
+
+use crate::godot::GodotVersion;
+
+/// The package name Godot goes by in requirements files
+const PACKAGE: &str = "godot";
+
+/// The extra that selects .NET builds, as in `godot[dotnet]==4.2.1`
+const DOTNET_EXTRA: &str = "dotnet";
+
+/// One line of a pip-style requirements file, e.g. `godot==4.2.1` or `godot[dotnet]>=4.1,<4.3`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct VersionConstraint {
+    pub is_dotnet: bool,
+    /// The version pinned with `==`, which needs no release lookup
+    pub exact: Option<GodotVersion>,
+    pub range: VersionReq,
+}
+
+impl VersionConstraint {
+    /// Pick the newest version in `available` that satisfies the constraint
+    pub fn resolve(&self, available: &[GodotVersion]) -> Option<GodotVersion> {
+        if let Some(exact) = &self.exact {
+            return Some(exact.clone());
+        }
+
+        let newest = available
+            .iter()
+            .filter(|v| self.range.matches(&v.version))
+            .max()?;
+        GodotVersion::new(&newest.godot_version_string(), self.is_dotnet).ok()
+    }
+}
+
+impl FromStr for VersionConstraint {
+    type Err = anyhow::Error;
+
+    fn from_str(line: &str) -> Result<Self> {
+        let line = line.trim();
+        let split = line
+            .find(|c: char| "=<>~!".contains(c))
+            .ok_or_else(|| anyhow!("'{}' has no version specifier, e.g. godot==4.2.1", line))?;
+        let (name, specifiers) = line.split_at(split);
+
+        let (name, is_dotnet) = match name.trim().split_once('[') {
+            Some((name, extras)) => {
+                let extras = extras
+                    .strip_suffix(']')
+                    .ok_or_else(|| anyhow!("Unclosed '[' in '{}'", line))?;
+                if extras.trim() != DOTNET_EXTRA {
+                    return Err(anyhow!(
+                        "Unknown extra '[{}]' in '{}' (only [{}] is supported)",
+                        extras,
+                        line,
+                        DOTNET_EXTRA
+                    ));
+                }
+                (name, true)
+            }
+            None => (name.trim(), false),
+        };
+        if !name.eq_ignore_ascii_case(PACKAGE) {
+            return Err(anyhow!(
+                "'{}' is not a Godot requirement (expected '{}')",
+                line,
+                PACKAGE
+            ));
+        }
+
+        let specifiers: Vec<&str> = specifiers.split(',').map(str::trim).collect();
+        let exact = match specifiers[..] {
+            [single] => single
+                .strip_prefix("==")
+                .map(|version| GodotVersion::new(version.trim(), is_dotnet))
+                .transpose()?,
+            _ => None,
+        };
+
+        let range = specifiers
+            .iter()
+            .map(|specifier| to_semver(specifier))
+            .collect::<Result<Vec<_>>>()?
+            .join(", ");
+        let range = VersionReq::parse(&range)
+            .map_err(|e| anyhow!("Invalid version range in '{}': {}", line, e))?;
+
+        Ok(Self {
+            is_dotnet,
+            exact,
+            range,
+        })
+    }
+}
+
+/// Translate a pip version specifier into a semver comparator
+fn to_semver(specifier: &str) -> Result<String> {
+    if let Some(version) = specifier.strip_prefix("~=") {
+        // "~=4.2" allows any 4.x from 4.2 on, "~=4.2.1" any 4.2.x from 4.2.1 on
+        let version = version.trim();
+        return Ok(match version.matches('.').count() {
+            1 => format!("^{}", version),
+            _ => format!("~{}", version),
+        });
+    }
+    if specifier.starts_with("!=") {
+        return Err(anyhow!("'{}': exclusions aren't supported", specifier));
+    }
+
+    let (op, version) = match specifier.strip_prefix("==") {
+        Some(version) => ("=", version),
+        None => {
+            let end = specifier
+                .find(|c: char| c.is_ascii_digit())
+                .ok_or_else(|| anyhow!("'{}' has no version", specifier))?;
+            specifier.split_at(end)
+        }
+    };
+    if !["=", ">=", "<=", ">", "<"].contains(&op.trim()) {
+        return Err(anyhow!("Unknown version specifier '{}'", specifier));
+    }
+
+    // Godot writes prereleases as "4.3-beta2", semver needs "4.3.0-beta.2"
+    let version = GodotVersion::new(version.trim(), false)?.version;
+    Ok(format!("{}{}", op.trim(), version))
+}
+
+/// Parse a requirements file, skipping blank lines and `#` comments
+pub fn parse(content: &str) -> Result<Vec<VersionConstraint>> {
+    content
+        .lines()
+        .enumerate()
+        .map(|(i, line)| (i, line.split('#').next().unwrap_or_default().trim()))
+        .filter(|(_, line)| !line.is_empty())
+        .map(|(i, line)| {
+            line.parse()
+                .map_err(|e: anyhow::Error| anyhow!("Line {}: {}", i + 1, e))
+        })
+        .collect()
+}
+
+/// Format a version as a pinned requirement that [`VersionConstraint`] parses back
+pub fn format(version: &GodotVersion) -> String {
+    let extra = if version.is_dotnet {
+        format!("[{}]", DOTNET_EXTRA)
+    } else {
+        String::new()
+    };
+    format!("{}{}=={}", PACKAGE, extra, version.godot_version_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn v(version: &str, is_dotnet: bool) -> GodotVersion {
+        GodotVersion::new(version, is_dotnet).unwrap()
+    }
+
+    #[test]
+    fn test_parse_constraints() {
+        let constraints = parse(
+            "# Godot versions for CI\n\
+             godot==4.2.1\n\
+             \n\
+             godot[dotnet]>=4.1,<4.3  # .NET builds\n\
+             Godot ~= 3.5\n",
+        )
+        .unwrap();
+        assert_eq!(constraints.len(), 3);
+        assert_eq!(constraints[0].exact, Some(v("4.2.1", false)));
+        assert!(constraints[1].is_dotnet && constraints[1].exact.is_none());
+
+        let available = [
+            v("3.5.3", false),
+            v("4.1.4", false),
+            v("4.2.2", false),
+            v("4.3", false),
+        ];
+        let resolved: Vec<_> = constraints.iter().map(|c| c.resolve(&available)).collect();
+        assert_eq!(
+            resolved,
+            [
+                Some(v("4.2.1", false)),
+                Some(v("4.2.2", true)),
+                Some(v("3.5.3", false))
+            ]
+        );
+
+        for line in [
+            "godot",
+            "godot!=4.2",
+            "godot[mono]==4.2",
+            "blender>=4.0",
+            "godot=>4",
+        ] {
+            assert!(line.parse::<VersionConstraint>().is_err(), "{}", line);
+        }
+        assert!(parse("godot==4.2\nnot a requirement")
+            .unwrap_err()
+            .to_string()
+            .starts_with("Line 2"));
+    }
+
+    #[test]
+    fn test_format_round_trip() {
+        for version in [v("4.2.1", false), v("4.2.1", true), v("4.3-beta2", false)] {
+            let constraint: VersionConstraint = format(&version).parse().unwrap();
+            assert_eq!(constraint.exact, Some(version.clone()));
+            assert!(constraint.range.matches(&version.version));
+        }
+        assert_eq!(format(&v("4.2", true)), "godot[dotnet]==4.2.0");
+    }
+}
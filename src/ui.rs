@@ -1,4 +1,5 @@
 use colored::*;
+use std::io::{self, Write};
 
 pub fn success(msg: &str) {
     println!("{} {}", "✅".green(), msg);
@@ -15,3 +16,33 @@ pub fn info(msg: &str) {
 pub fn warning(msg: &str) {
     println!("{} {}", "⚠️".yellow(), msg);
 }
+
+/// Ask a yes/no question on the terminal, defaulting to "no"
+pub fn confirm(question: &str) -> io::Result<bool> {
+    print!("{} [y/N]: ", question);
+    io::stdout().flush()?;
+
+    let mut input = String::new();
+    io::stdin().read_line(&mut input)?;
+
+    let answer = input.trim().to_lowercase();
+    Ok(answer == "y" || answer == "yes")
+}
+
+/// Format a byte count for humans (e.g. "1.5 GB")
+pub fn format_size(bytes: u64) -> String {
+    const UNITS: &[&str] = &["B", "KB", "MB", "GB"];
+    let mut size = bytes as f64;
+    let mut unit_index = 0;
+
+    while size >= 1024.0 && unit_index < UNITS.len() - 1 {
+        size /= 1024.0;
+        unit_index += 1;
+    }
+
+    if unit_index == 0 {
+        format!("{} {}", bytes, UNITS[unit_index])
+    } else {
+        format!("{:.1} {}", size, UNITS[unit_index])
+    }
+}
@@ -0,0 +1,94 @@
+use anyhow::{anyhow, Result};
+use reqwest::Client;
+use sha2::{Digest, Sha512};
+use std::fs;
+use std::io;
+use std::path::Path;
+use thiserror::Error;
+
+use crate::{github::GitHubRelease, remote};
+
+/// A downloaded archive whose digest differs from the one the release publishes
+#[derive(Error, Debug, PartialEq, Eq)]
+#[error(
+    "{archive} doesn't match the release's SHA-512 checksum (expected {expected}, got {actual}); \
+     it was removed, so installing again downloads it afresh"
+)]
+pub struct ChecksumMismatchError {
+    pub archive: String,
+    pub expected: String,
+    pub actual: String,
+}
+
+/// Get the SHA-512 of a file as lowercase hex, as listed in Godot's `SHA512-SUMS.txt`
+pub fn file_sha512(path: &Path) -> Result<String> {
+    let mut hasher = Sha512::new();
+    io::copy(&mut fs::File::open(path)?, &mut hasher)?;
+    Ok(format!("{:x}", hasher.finalize()))
+}
+
+/// Check an archive against the hex SHA-512 digest its release publishes
+pub fn verify_archive(archive_path: &Path, expected_hex: &str) -> Result<()> {
+    let actual = file_sha512(archive_path)?;
+    if !actual.eq_ignore_ascii_case(expected_hex.trim()) {
+        return Err(ChecksumMismatchError {
+            archive: archive_path.display().to_string(),
+            expected: expected_hex.trim().to_lowercase(),
+            actual,
+        }
+        .into());
+    }
+    Ok(())
+}
+
+/// Get the digest of one of a release's archives from its `SHA512-SUMS.txt`, failing if
+/// the release has no checksum file or it doesn't list the archive
+pub async fn expected_sha512(
+    client: &Client,
+    release: &GitHubRelease,
+    archive_name: &str,
+) -> Result<String> {
+    remote::release_sha512(client, release, archive_name)
+        .await
+        .map_err(|e| {
+            anyhow!(
+                "Could not fetch the checksums of {}: {} (use --skip-verify to install without them)",
+                release.tag_name,
+                e
+            )
+        })?
+        .ok_or_else(|| {
+            anyhow!(
+                "Release {} publishes no checksum for {} (use --skip-verify to install it anyway)",
+                release.tag_name,
+                archive_name
+            )
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_verify_archive() {
+        let dir = tempfile::tempdir().unwrap();
+        let archive = dir.path().join("Godot_v4.3-stable_linux.x86_64.zip");
+        fs::write(&archive, "godot").unwrap();
+        let digest = file_sha512(&archive).unwrap();
+        assert_eq!(digest.len(), 128);
+
+        assert!(verify_archive(&archive, &digest).is_ok());
+        assert!(verify_archive(&archive, &digest.to_uppercase()).is_ok());
+
+        let error = verify_archive(&archive, &"0".repeat(128)).unwrap_err();
+        assert_eq!(
+            error.downcast_ref::<ChecksumMismatchError>(),
+            Some(&ChecksumMismatchError {
+                archive: archive.display().to_string(),
+                expected: "0".repeat(128),
+                actual: digest,
+            })
+        );
+    }
+}